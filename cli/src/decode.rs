@@ -1,3 +1,4 @@
+use anchor_lang::prelude::Pubkey;
 use anchor_lang::Discriminator;
 use anyhow::{anyhow, bail, Result};
 use serde::Serialize;
@@ -27,24 +28,81 @@ struct DisplayDepositEntry {
 #[derive(Serialize)]
 struct DisplayVoter {
     voter_authority: String,
+    delegate: Option<String>,
     registrar: String,
     deposit_entries: Vec<DisplayDepositEntry>,
 }
 
+#[derive(Serialize)]
+struct DisplayLockupCurvePoint {
+    lockup_secs: u32,
+    lockup_days: f64,
+    factor: f64,
+}
+
+#[derive(Serialize)]
+struct DisplayVotingMintConfig {
+    index: usize,
+    mint: String,
+    grant_authority: String,
+    digit_shift: i8,
+    baseline_vote_weight_factor: f64,
+    max_extra_lockup_vote_weight_factor: f64,
+    lockup_saturation_secs: u64,
+    lockup_saturation_days: f64,
+    lockup_curve_kind: String,
+    /// Breakpoints or tiers, empty for linear curves
+    lockup_curve_points: Vec<DisplayLockupCurvePoint>,
+    deprecated: bool,
+    /// None for mints configured before deposits were tracked
+    deposited_native: Option<u64>,
+    /// None for mints configured before the totals were tracked
+    locked_native: Option<u64>,
+    clawback_deposited_native: Option<u64>,
+    /// Deposit limits, zero for no limit
+    min_deposit_native: u64,
+    max_voter_deposited_native: u64,
+    max_deposited_native: u64,
+    /// Lockup restrictions, zero for no restriction
+    allowed_lockup_kinds: u16,
+    max_lockup_days: u16,
+}
+
+#[derive(Serialize)]
+struct DisplayRegistrar {
+    governance_program_id: String,
+    realm: String,
+    realm_governing_token_mint: String,
+    realm_authority: String,
+    voting_mints: Vec<DisplayVotingMintConfig>,
+    max_vote_weight_mode: String,
+    time_offset: i64,
+}
+
+#[derive(Serialize)]
+struct DisplayVoterWeightRecord {
+    realm: String,
+    governing_token_mint: String,
+    governing_token_owner: String,
+    voter_weight: u64,
+    voter_weight_expiry: Option<u64>,
+    weight_action: Option<String>,
+    weight_action_target: Option<String>,
+}
+
 /// Decode a Voter account and print its JSON to stdout
 fn decode_voter(data: &[u8]) -> Result<()> {
-    let mut data = data;
-    let voter: Voter = anchor_lang::AccountDeserialize::try_deserialize(&mut data)?;
+    let voter = VoterAccountData::try_from_account_data(data)?;
     let now_ts = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)?
         .as_secs() as i64;
     let year = 365 * 24 * 60 * 60;
     let ser = DisplayVoter {
         voter_authority: voter.voter_authority.to_string(),
+        delegate: (voter.delegate != Pubkey::default()).then(|| voter.delegate.to_string()),
         registrar: voter.registrar.to_string(),
         deposit_entries: voter
-            .deposits
-            .iter()
+            .deposits()
             .filter(|d| d.is_used)
             .map(|d| DisplayDepositEntry {
                 allow_clawback: d.allow_clawback,
@@ -63,23 +121,139 @@ fn decode_voter(data: &[u8]) -> Result<()> {
     Ok(())
 }
 
+/// Converts a factor in 1/1e9 units to a plain number
+fn scaled_factor(scaled: u64) -> f64 {
+    scaled as f64 / 1e9
+}
+
+/// Deserialize a Registrar account, including registrars with an older
+/// layout that have not been migrated with resize_registrar yet
+pub fn deserialize_registrar(data: &[u8]) -> Result<Registrar> {
+    let new_len = 8 + std::mem::size_of::<Registrar>();
+    let mut data = data.to_vec();
+    if data.len() < new_len {
+        let old_len = data.len();
+        data.resize(new_len, 0);
+        migrate_registrar_data(&mut data, old_len)?;
+    }
+    Ok(anchor_lang::AccountDeserialize::try_deserialize(
+        &mut data.as_slice(),
+    )?)
+}
+
+/// Decode a Registrar account and print its JSON to stdout
+fn decode_registrar(data: &[u8]) -> Result<()> {
+    let registrar = deserialize_registrar(data)?;
+    let day = 24 * 60 * 60;
+    let ser = DisplayRegistrar {
+        governance_program_id: registrar.governance_program_id.to_string(),
+        realm: registrar.realm.to_string(),
+        realm_governing_token_mint: registrar.realm_governing_token_mint.to_string(),
+        realm_authority: registrar.realm_authority.to_string(),
+        voting_mints: registrar
+            .voting_mints
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.in_use())
+            .map(|(index, c)| DisplayVotingMintConfig {
+                index,
+                mint: c.mint.to_string(),
+                grant_authority: c.grant_authority.to_string(),
+                digit_shift: c.digit_shift,
+                baseline_vote_weight_factor: scaled_factor(c.baseline_vote_weight_scaled_factor),
+                max_extra_lockup_vote_weight_factor: scaled_factor(
+                    c.max_extra_lockup_vote_weight_scaled_factor,
+                ),
+                lockup_saturation_secs: c.lockup_saturation_secs,
+                lockup_saturation_days: c.lockup_saturation_secs as f64 / day as f64,
+                lockup_curve_kind: format!("{:?}", c.lockup_curve_kind),
+                lockup_curve_points: c
+                    .lockup_curve()
+                    .points()
+                    .iter()
+                    .map(|p| DisplayLockupCurvePoint {
+                        lockup_secs: p.lockup_secs,
+                        lockup_days: p.lockup_secs as f64 / day as f64,
+                        factor: scaled_factor(p.scaled_factor as u64),
+                    })
+                    .collect(),
+                deprecated: c.deprecated,
+                deposited_native: c.deposited_native_tracked.then_some(c.deposited_native),
+                locked_native: registrar.voting_mint_totals_tracked[index]
+                    .then_some(registrar.voting_mint_totals[index].locked_native),
+                clawback_deposited_native: registrar.voting_mint_totals_tracked[index]
+                    .then_some(registrar.voting_mint_totals[index].clawback_deposited_native),
+                min_deposit_native: c.min_deposit_native,
+                max_voter_deposited_native: c.max_voter_deposited_native,
+                max_deposited_native: registrar.max_deposited_native[index],
+                allowed_lockup_kinds: registrar.allowed_lockup_kinds[index],
+                max_lockup_days: c.max_lockup_days,
+            })
+            .collect(),
+        max_vote_weight_mode: format!("{:?}", registrar.max_vote_weight_mode),
+        time_offset: registrar.time_offset,
+    };
+    println!("{}", serde_json::to_string(&ser)?);
+    Ok(())
+}
+
+/// Decode a VoterWeightRecord account and print its JSON to stdout
+fn decode_voter_weight_record(data: &[u8]) -> Result<()> {
+    let mut data = data;
+    let record: VoterWeightRecord = anchor_lang::AccountDeserialize::try_deserialize(&mut data)?;
+    let ser = DisplayVoterWeightRecord {
+        realm: record.realm.to_string(),
+        governing_token_mint: record.governing_token_mint.to_string(),
+        governing_token_owner: record.governing_token_owner.to_string(),
+        voter_weight: record.voter_weight,
+        voter_weight_expiry: record.voter_weight_expiry,
+        weight_action: record.weight_action.as_ref().map(|a| format!("{:?}", a)),
+        weight_action_target: record.weight_action_target.map(|t| t.to_string()),
+    };
+    println!("{}", serde_json::to_string(&ser)?);
+    Ok(())
+}
+
+/// Prints the JSON for an account, given its full data including the discriminator
+type DecodeHandler = fn(&[u8]) -> Result<()>;
+
+/// Decode a single base64 encoded account with the matching handler
+fn decode_line(account_types: &HashMap<[u8; 8], DecodeHandler>, line: &str) -> Result<()> {
+    let data = base64::decode(line.trim())?;
+
+    if data.len() < 8 {
+        bail!("data length {} too small for discriminator", data.len());
+    }
+    let discr = &data[0..8];
+    let handler = account_types
+        .get(discr)
+        .ok_or_else(|| anyhow!("discriminator {:?} not recognized", discr))?;
+
+    handler(&data)
+}
+
 // Read a sequence of base64 encoded accounts from stdin
 // and write their decoded versions back out as JSON.
+//
+// Lines that can't be decoded are reported on stderr and skipped.
 pub fn decode_account() -> Result<()> {
-    let account_types = HashMap::from([(Voter::discriminator(), &decode_voter)]);
-
-    for line in io::stdin().lock().lines() {
-        let data = base64::decode(line?)?;
+    let account_types: HashMap<[u8; 8], DecodeHandler> = HashMap::from([
+        (Voter::discriminator(), decode_voter as DecodeHandler),
+        (Registrar::discriminator(), decode_registrar),
+        (
+            spl_governance_addin_api::voter_weight::VoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+            decode_voter_weight_record,
+        ),
+    ]);
 
-        if data.len() < 8 {
-            bail!("data length {} too small for discriminator", data.len());
+    for (line_number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Err(err) = decode_line(&account_types, &line) {
+            eprintln!("line {}: {:#}", line_number + 1, err);
         }
-        let discr = &data[0..8];
-        let handler = account_types
-            .get(discr)
-            .ok_or_else(|| anyhow!("discriminator {:?} not recognized", discr))?;
-
-        handler(&data)?;
     }
     Ok(())
 }
@@ -1,7 +1,13 @@
+use anchor_lang::prelude::Pubkey;
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 mod decode;
+mod snapshot;
+#[cfg(test)]
+mod test_utils;
+mod voter;
 
 #[derive(Parser, Debug)]
 #[clap(version)]
@@ -13,11 +19,58 @@ struct Args {
 #[derive(Subcommand, Debug)]
 enum Command {
     DecodeAccount,
+    /// Fetch a voter over RPC and report on its deposits
+    Voter {
+        /// RPC endpoint to fetch accounts from
+        #[clap(long, default_value = "http://localhost:8899")]
+        url: String,
+        #[clap(long)]
+        registrar: Pubkey,
+        /// The voter authority, the owner of the voter account
+        #[clap(long)]
+        authority: Pubkey,
+    },
+    /// Export all voters of a registrar with their deposits and weights
+    Snapshot {
+        /// RPC endpoint to fetch accounts from
+        #[clap(long, default_value = "http://localhost:8899")]
+        url: String,
+        /// Read accounts from this file instead of fetching them over RPC.
+        /// Each line is an account address and its base64 encoded data,
+        /// separated by whitespace.
+        #[clap(long)]
+        accounts_file: Option<PathBuf>,
+        #[clap(long)]
+        registrar: Pubkey,
+        /// Horizons in days for the guaranteed locked vote weight columns
+        #[clap(long, value_delimiter = ',', default_value = "30,180,365")]
+        horizon_days: Vec<u64>,
+        #[clap(long, value_enum, default_value = "csv")]
+        format: snapshot::Format,
+    },
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     match args.command {
         Command::DecodeAccount => decode::decode_account(),
+        Command::Voter {
+            url,
+            registrar,
+            authority,
+        } => voter::show_voter(&url, &registrar, &authority),
+        Command::Snapshot {
+            url,
+            accounts_file,
+            registrar,
+            horizon_days,
+            format,
+        } => snapshot::snapshot(
+            &url,
+            accounts_file.as_deref(),
+            &registrar,
+            &horizon_days,
+            format,
+        ),
     }
 }
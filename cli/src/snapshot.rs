@@ -0,0 +1,470 @@
+use crate::decode::deserialize_registrar;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+use voter_stake_registry::state::*;
+
+/// Offset of Voter::registrar in the account data: after the discriminator
+/// and the voter authority.
+const VOTER_REGISTRAR_OFFSET: usize = 8 + 32;
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// One row of the snapshot, per voter.
+///
+/// The per-mint maps are keyed by mint address and contain all of the
+/// registrar's in-use voting mints.
+#[derive(Serialize)]
+struct SnapshotRow {
+    voter: String,
+    voter_authority: String,
+    deposited: BTreeMap<String, u64>,
+    locked: BTreeMap<String, u64>,
+    weight: u64,
+    weight_baseline: u64,
+    /// Keyed by the horizon in days
+    weight_locked_guaranteed: BTreeMap<u64, u64>,
+}
+
+/// Fetch the registrar and all of its voters over RPC
+fn fetch_accounts(
+    client: &RpcClient,
+    registrar_address: &Pubkey,
+) -> Result<(Registrar, Vec<(Pubkey, VoterAccountData)>)> {
+    let registrar = deserialize_registrar(&client.get_account(registrar_address)?.data)?;
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &Voter::discriminator())),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                VOTER_REGISTRAR_OFFSET,
+                registrar_address.as_ref(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: None,
+    };
+    let voters = client
+        .get_program_accounts_with_config(&voter_stake_registry::id(), config)?
+        .into_iter()
+        .map(|(address, account)| {
+            Ok((
+                address,
+                VoterAccountData::try_from_account_data(&account.data)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((registrar, voters))
+}
+
+/// Read the registrar and its voters from pre-dumped accounts.
+///
+/// Each line must be an account address followed by whitespace and the
+/// base64 encoded account data. Accounts that aren't the registrar or one
+/// of its voters are skipped.
+fn read_accounts(
+    reader: impl BufRead,
+    registrar_address: &Pubkey,
+) -> Result<(Registrar, Vec<(Pubkey, VoterAccountData)>)> {
+    let mut registrar = None;
+    let mut voters = Vec::new();
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut parse = || -> Result<()> {
+            let (address, data) = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| anyhow!("expected an address and base64 data"))?;
+            let address = Pubkey::from_str(address)?;
+            let data = base64::decode(data.trim())?;
+            if address == *registrar_address {
+                registrar = Some(deserialize_registrar(&data)?);
+            } else if data.starts_with(&Voter::discriminator()) {
+                let voter = VoterAccountData::try_from_account_data(&data)?;
+                if voter.registrar == *registrar_address {
+                    voters.push((address, voter));
+                }
+            }
+            Ok(())
+        };
+        parse().map_err(|e| anyhow!("line {}: {:#}", line_number + 1, e))?;
+    }
+    let registrar =
+        registrar.ok_or_else(|| anyhow!("registrar {} not in accounts", registrar_address))?;
+    Ok((registrar, voters))
+}
+
+/// Compute the snapshot rows as of `curr_ts`
+fn snapshot_rows(
+    registrar: &Registrar,
+    voters: &[(Pubkey, VoterAccountData)],
+    curr_ts: i64,
+    horizon_days: &[u64],
+) -> Result<Vec<SnapshotRow>> {
+    voters
+        .iter()
+        .map(|(address, voter)| {
+            let per_mint = |amount: &dyn Fn(&DepositEntry) -> u64| {
+                registrar
+                    .voting_mints
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, mint)| mint.in_use())
+                    .map(|(index, mint)| {
+                        let total = voter
+                            .deposits()
+                            .filter(|d| d.is_used && d.voting_mint_config_idx as usize == index)
+                            .map(amount)
+                            .sum();
+                        (mint.mint.to_string(), total)
+                    })
+                    .collect()
+            };
+            let weight_locked_guaranteed = horizon_days
+                .iter()
+                .map(|&days| {
+                    let at_ts = curr_ts + days as i64 * DAY;
+                    Ok((
+                        days,
+                        voter.weight_locked_guaranteed(registrar, curr_ts, at_ts)?,
+                    ))
+                })
+                .collect::<Result<_>>()?;
+            Ok(SnapshotRow {
+                voter: address.to_string(),
+                voter_authority: voter.voter_authority.to_string(),
+                deposited: per_mint(&|d| d.amount_deposited_native),
+                locked: per_mint(&|d| d.amount_locked(curr_ts)),
+                weight: voter.weight_at(registrar, curr_ts)?,
+                weight_baseline: voter.weight_baseline(registrar)?,
+                weight_locked_guaranteed,
+            })
+        })
+        .collect()
+}
+
+/// Write the rows as CSV, one column per mint and per horizon
+fn write_csv(
+    out: &mut impl Write,
+    rows: &[SnapshotRow],
+    mints: &[String],
+    horizon_days: &[u64],
+) -> Result<()> {
+    let mut header = vec!["voter".to_string(), "voter_authority".to_string()];
+    header.extend(mints.iter().map(|m| format!("deposited_{}", m)));
+    header.extend(mints.iter().map(|m| format!("locked_{}", m)));
+    header.push("weight".to_string());
+    header.push("weight_baseline".to_string());
+    header.extend(
+        horizon_days
+            .iter()
+            .map(|d| format!("weight_locked_guaranteed_{}d", d)),
+    );
+    writeln!(out, "{}", header.join(","))?;
+
+    for row in rows {
+        let mut fields = vec![row.voter.clone(), row.voter_authority.clone()];
+        fields.extend(mints.iter().map(|m| row.deposited[m].to_string()));
+        fields.extend(mints.iter().map(|m| row.locked[m].to_string()));
+        fields.push(row.weight.to_string());
+        fields.push(row.weight_baseline.to_string());
+        fields.extend(
+            horizon_days
+                .iter()
+                .map(|d| row.weight_locked_guaranteed[d].to_string()),
+        );
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Build a snapshot of all voters of a registrar and write it to `out`.
+///
+/// `now_ts` is before applying the registrar's time offset.
+fn write_snapshot(
+    out: &mut impl Write,
+    registrar: &Registrar,
+    mut voters: Vec<(Pubkey, VoterAccountData)>,
+    now_ts: i64,
+    horizon_days: &[u64],
+    format: Format,
+) -> Result<()> {
+    // stable output, independent of the account source
+    voters.sort_by_key(|(address, _)| *address);
+    let curr_ts = now_ts + registrar.time_offset;
+    let rows = snapshot_rows(registrar, &voters, curr_ts, horizon_days)?;
+    match format {
+        Format::Csv => {
+            let mints: Vec<String> = registrar
+                .voting_mints
+                .iter()
+                .filter(|m| m.in_use())
+                .map(|m| m.mint.to_string())
+                .collect();
+            write_csv(out, &rows, &mints, horizon_days)
+        }
+        Format::Json => {
+            serde_json::to_writer(&mut *out, &rows)?;
+            writeln!(out)?;
+            Ok(())
+        }
+    }
+}
+
+/// Print a snapshot of all voters of a registrar to stdout, either fetched
+/// over RPC or read from a file of pre-dumped accounts.
+pub fn snapshot(
+    url: &str,
+    accounts_file: Option<&std::path::Path>,
+    registrar_address: &Pubkey,
+    horizon_days: &[u64],
+    format: Format,
+) -> Result<()> {
+    let (registrar, voters) = match accounts_file {
+        Some(path) => read_accounts(
+            std::io::BufReader::new(std::fs::File::open(path)?),
+            registrar_address,
+        )?,
+        None => fetch_accounts(&RpcClient::new(url.to_string()), registrar_address)?,
+    };
+    let now_ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    write_snapshot(
+        &mut std::io::stdout().lock(),
+        &registrar,
+        voters,
+        now_ts,
+        horizon_days,
+        format,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use bytemuck::Zeroable;
+    use serde_json::json;
+    use solana_client::rpc_request::RpcRequest;
+    use std::collections::HashMap;
+
+    const NOW_TS: i64 = 1_000_000_000;
+
+    struct Fixture {
+        registrar_address: Pubkey,
+        registrar: Registrar,
+        voters: Vec<(Pubkey, VoterAccountData)>,
+    }
+
+    /// A registrar with two mints and two voters: one with an unlocked deposit,
+    /// one with a 100 day cliff lockup in a deposit entry beyond the first 32.
+    fn fixture() -> Result<Fixture> {
+        let registrar_address = Pubkey::new_unique();
+        let mut registrar = Registrar::zeroed();
+        for (i, factor) in [(0, 1_000_000_000), (1, 500_000_000)] {
+            let mint = &mut registrar.voting_mints[i];
+            mint.mint = Pubkey::new_unique();
+            mint.baseline_vote_weight_scaled_factor = factor;
+            mint.max_extra_lockup_vote_weight_scaled_factor = factor;
+            mint.lockup_saturation_secs = (365 * DAY) as u64;
+        }
+
+        let mut voters = Vec::new();
+        for _ in 0..2 {
+            let mut voter = Voter::zeroed();
+            voter.voter_authority = Pubkey::new_unique();
+            voter.registrar = registrar_address;
+            let voter = VoterAccount {
+                voter: Box::new(voter),
+                extra_deposits: vec![],
+            };
+            voters.push((Pubkey::new_unique(), voter));
+        }
+        voters.sort_by_key(|(address, _)| *address);
+
+        let d = &mut voters[0].1.deposits[0];
+        d.is_used = true;
+        d.amount_deposited_native = 1000;
+
+        voters[1].1.extra_deposits = vec![DepositEntry::default(); 8];
+        let d = voters[1].1.deposit_mut(35).unwrap();
+        d.is_used = true;
+        d.voting_mint_config_idx = 1;
+        d.amount_deposited_native = 3650;
+        d.amount_initially_locked_native = 3650;
+        d.lockup = Lockup::new_from_periods(LockupKind::Cliff, NOW_TS, NOW_TS, 100)?;
+
+        Ok(Fixture {
+            registrar_address,
+            registrar,
+            voters,
+        })
+    }
+
+    fn snapshot_string(
+        fixture: &Fixture,
+        voters: Vec<(Pubkey, VoterAccountData)>,
+        format: Format,
+    ) -> Result<String> {
+        let mut out = Vec::new();
+        write_snapshot(
+            &mut out,
+            &fixture.registrar,
+            voters,
+            NOW_TS,
+            &[0, 50, 100],
+            format,
+        )?;
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn snapshot_csv() -> Result<()> {
+        let f = fixture()?;
+        let mint0 = f.registrar.voting_mints[0].mint;
+        let mint1 = f.registrar.voting_mints[1].mint;
+        let csv = snapshot_string(&f, f.voters.clone(), Format::Csv)?;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            format!(
+                "voter,voter_authority,deposited_{0},deposited_{1},locked_{0},locked_{1},\
+                 weight,weight_baseline,weight_locked_guaranteed_0d,\
+                 weight_locked_guaranteed_50d,weight_locked_guaranteed_100d",
+                mint0, mint1
+            )
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{},{},1000,0,0,0,1000,1000,0,0,0",
+                f.voters[0].0, f.voters[0].1.voter_authority
+            )
+        );
+        // baseline is 3650 * 0.5, the lockup bonus 3650 * 0.5 * 100/365, which
+        // decreases linearly until the end of the cliff
+        assert_eq!(
+            lines[2],
+            format!(
+                "{},{},0,3650,0,3650,2325,1825,500,250,0",
+                f.voters[1].0, f.voters[1].1.voter_authority
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_json() -> Result<()> {
+        let f = fixture()?;
+        let mint1 = f.registrar.voting_mints[1].mint.to_string();
+        // the input order doesn't matter
+        let voters = f.voters.iter().rev().cloned().collect();
+        let rows: serde_json::Value =
+            serde_json::from_str(&snapshot_string(&f, voters, Format::Json)?)?;
+        assert_eq!(rows.as_array().unwrap().len(), 2);
+        assert_eq!(rows[1]["voter"], f.voters[1].0.to_string());
+        assert_eq!(rows[1]["deposited"][&mint1], 3650);
+        assert_eq!(rows[1]["weight"], 2325);
+        assert_eq!(
+            rows[1]["weight_locked_guaranteed"],
+            json!({ "0": 500, "50": 250, "100": 0 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_accounts_file() -> Result<()> {
+        let f = fixture()?;
+        let mut other_voter = Voter::zeroed();
+        other_voter.registrar = Pubkey::new_unique();
+
+        let mut file = format!(
+            "{} {}\n\n",
+            f.registrar_address,
+            base64::encode(zero_copy_data(&f.registrar))
+        );
+        for (address, voter) in f.voters.iter() {
+            file += &format!("{}\t{}\n", address, base64::encode(voter_data(voter)));
+        }
+        file += &format!(
+            "{} {}\n",
+            Pubkey::new_unique(),
+            base64::encode(zero_copy_data(&other_voter))
+        );
+
+        let (registrar, voters) = read_accounts(file.as_bytes(), &f.registrar_address)?;
+        assert_eq!(
+            registrar.voting_mints[1].mint,
+            f.registrar.voting_mints[1].mint
+        );
+        let addresses: Vec<Pubkey> = voters.iter().map(|(a, _)| *a).collect();
+        assert_eq!(addresses, vec![f.voters[0].0, f.voters[1].0]);
+
+        let missing = read_accounts("".as_bytes(), &f.registrar_address);
+        assert!(missing.is_err());
+        let broken = read_accounts("not-a-line".as_bytes(), &f.registrar_address);
+        assert!(broken.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_accounts_mock_rpc() -> Result<()> {
+        let f = fixture()?;
+        let registrar_response = json!({
+            "context": { "slot": 1 },
+            "value": encode_account(zero_copy_data(&f.registrar)),
+        });
+        let voters_response: Vec<serde_json::Value> = f
+            .voters
+            .iter()
+            .map(|(address, voter)| {
+                json!({
+                    "pubkey": address.to_string(),
+                    "account": encode_account(voter_data(voter)),
+                })
+            })
+            .collect();
+        let client = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([
+                (RpcRequest::GetAccountInfo, registrar_response),
+                (RpcRequest::GetProgramAccounts, json!(voters_response)),
+            ]),
+        );
+
+        let (registrar, voters) = fetch_accounts(&client, &f.registrar_address)?;
+        assert_eq!(
+            registrar.voting_mints[0].mint,
+            f.registrar.voting_mints[0].mint
+        );
+        assert_eq!(voters.len(), 2);
+        assert_eq!(voters[1].1.deposit_entry_count(), 40);
+        assert_eq!(
+            voters[1].1.deposit(35).unwrap().amount_deposited_native,
+            3650
+        );
+        Ok(())
+    }
+}
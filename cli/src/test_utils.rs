@@ -0,0 +1,30 @@
+//! Helpers for building account data and mock RPC responses in tests.
+
+use anchor_lang::Discriminator;
+use serde_json::{json, Value};
+use voter_stake_registry::state::VoterAccountData;
+
+/// The JSON an RPC node returns for an account with `data`
+pub fn encode_account(data: Vec<u8>) -> Value {
+    json!({
+        "lamports": 1_000_000,
+        "data": [base64::encode(data), "base64"],
+        "owner": voter_stake_registry::id().to_string(),
+        "executable": false,
+        "rentEpoch": 0,
+    })
+}
+
+/// The account data of a zero copy account, including the discriminator
+pub fn zero_copy_data<T: bytemuck::Pod + Discriminator>(account: &T) -> Vec<u8> {
+    let mut data = T::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(account));
+    data
+}
+
+/// The account data of a voter, including its extra deposit entries
+pub fn voter_data(voter: &VoterAccountData) -> Vec<u8> {
+    let mut data = zero_copy_data(&*voter.voter);
+    data.extend_from_slice(bytemuck::cast_slice(&voter.extra_deposits));
+    data
+}
@@ -0,0 +1,336 @@
+use crate::decode::deserialize_registrar;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use solana_client::rpc_client::RpcClient;
+use voter_stake_registry::state::*;
+
+//
+// Output format declarations. These are built from the fetched
+// accounts and then converted to JSON.
+//
+
+#[derive(Serialize)]
+struct DisplayUnlock {
+    timestamp: i64,
+    amount: u64,
+}
+
+#[derive(Serialize)]
+struct DisplayDepositReport {
+    deposit_entry_index: usize,
+    mint: String,
+    mint_index: u8,
+    allow_clawback: bool,
+    lockup_kind: String,
+    amount_deposited_native: u64,
+    amount_initially_locked_native: u64,
+    unlocked_now: u64,
+    locked_now: u64,
+    voting_power: u64,
+    voting_power_baseline: u64,
+    lockup_start_ts: i64,
+    /// None for lockups without an end, like constant lockups
+    lockup_end_ts: Option<i64>,
+    period_secs: u64,
+    periods_total: u64,
+    periods_left: u64,
+    /// Tokens unlocked per period, for vesting lockups
+    vesting_rate: Option<u64>,
+    /// End of the cliff before which nothing vests, while it's pending
+    cliff_end_ts: Option<i64>,
+    /// Every future point in time at which tokens unlock
+    unlocks: Vec<DisplayUnlock>,
+}
+
+#[derive(Serialize)]
+pub struct DisplayVoterReport {
+    voter: String,
+    voter_authority: String,
+    /// Votes with the voter's weight instead of the voter authority
+    delegate: Option<String>,
+    registrar: String,
+    voter_weight_record: String,
+    timestamp: i64,
+    voting_power: u64,
+    voting_power_baseline: u64,
+    /// The weight currently stored in the voter weight record, if it exists
+    voter_weight_record_weight: Option<u64>,
+    voter_weight_record_expiry: Option<u64>,
+    deposit_entries: Vec<DisplayDepositReport>,
+}
+
+/// Derive the Voter and VoterWeightRecord addresses for a voter authority
+pub fn voter_addresses(registrar: &Pubkey, voter_authority: &Pubkey) -> (Pubkey, Pubkey) {
+    let program_id = voter_stake_registry::id();
+    let (voter, _) = Pubkey::find_program_address(
+        &[
+            registrar.as_ref(),
+            b"voter".as_ref(),
+            voter_authority.as_ref(),
+        ],
+        &program_id,
+    );
+    let (voter_weight_record, _) = Pubkey::find_program_address(
+        &[
+            registrar.as_ref(),
+            b"voter-weight-record".as_ref(),
+            voter_authority.as_ref(),
+        ],
+        &program_id,
+    );
+    (voter, voter_weight_record)
+}
+
+/// The future points in time where tokens of the deposit unlock, with the amounts
+fn unlocks(deposit: &DepositEntry, curr_ts: i64) -> Result<Vec<DisplayUnlock>> {
+    let lockup = &deposit.lockup;
+    let seconds_left = lockup.seconds_left(curr_ts);
+    if seconds_left == 0 || lockup.kind == LockupKind::Constant {
+        return Ok(vec![]);
+    }
+    let end_ts = curr_ts + seconds_left as i64;
+    if !lockup.kind.is_vesting() {
+        return Ok(vec![DisplayUnlock {
+            timestamp: end_ts,
+            amount: deposit.amount_locked(curr_ts),
+        }]);
+    }
+
+    // A pending cliff unlocks all the periods up to its end at once
+    let period_secs = lockup.kind.period_secs() as i64;
+    let cliff_periods_left = lockup.cliff_periods_left(curr_ts) as i64;
+    let periods_left = lockup.periods_left(curr_ts)? as i64 - cliff_periods_left;
+    let mut timestamps = Vec::new();
+    if cliff_periods_left > 0 {
+        timestamps.push(lockup.cliff_end_ts());
+    }
+    timestamps.extend(
+        (0..periods_left)
+            .rev()
+            .map(|period| end_ts - period * period_secs),
+    );
+
+    let mut locked = deposit.amount_locked(curr_ts);
+    let mut result = Vec::new();
+    for timestamp in timestamps {
+        let locked_after = deposit.amount_locked(timestamp);
+        result.push(DisplayUnlock {
+            timestamp,
+            amount: locked - locked_after,
+        });
+        locked = locked_after;
+    }
+    Ok(result)
+}
+
+/// Fetch a voter with its registrar and voter weight record and report
+/// on its deposits as of `now_ts` (before applying the registrar's time offset).
+pub fn fetch_voter_report(
+    client: &RpcClient,
+    registrar_address: &Pubkey,
+    voter_authority: &Pubkey,
+    now_ts: i64,
+) -> Result<DisplayVoterReport> {
+    let (voter_address, voter_weight_record_address) =
+        voter_addresses(registrar_address, voter_authority);
+    let mut accounts = client
+        .get_multiple_accounts(&[
+            *registrar_address,
+            voter_address,
+            voter_weight_record_address,
+        ])?
+        .into_iter();
+    let mut next_data = || accounts.next().flatten().map(|a| a.data);
+
+    let registrar_data =
+        next_data().ok_or_else(|| anyhow!("registrar {} not found", registrar_address))?;
+    let registrar = deserialize_registrar(&registrar_data)?;
+    let voter_data = next_data().ok_or_else(|| anyhow!("voter {} not found", voter_address))?;
+    let voter = VoterAccountData::try_from_account_data(&voter_data)?;
+    let voter_weight_record = next_data()
+        .map(|data| -> Result<VoterWeightRecord> {
+            Ok(anchor_lang::AccountDeserialize::try_deserialize(
+                &mut data.as_slice(),
+            )?)
+        })
+        .transpose()?;
+
+    let curr_ts = now_ts + registrar.time_offset;
+    let mut deposit_entries = Vec::new();
+    for (index, deposit) in voter.deposits().enumerate() {
+        if !deposit.is_used {
+            continue;
+        }
+        let lockup = &deposit.lockup;
+        let mint_config = &registrar.voting_mints[deposit.voting_mint_config_idx as usize];
+        let periods_total = lockup.periods_total()?;
+        let has_end = lockup.kind != LockupKind::None && lockup.kind != LockupKind::Constant;
+        deposit_entries.push(DisplayDepositReport {
+            deposit_entry_index: index,
+            mint: mint_config.mint.to_string(),
+            mint_index: deposit.voting_mint_config_idx,
+            allow_clawback: deposit.allow_clawback,
+            lockup_kind: format!("{:?}", lockup.kind),
+            amount_deposited_native: deposit.amount_deposited_native,
+            amount_initially_locked_native: deposit.amount_initially_locked_native,
+            unlocked_now: deposit.amount_unlocked(curr_ts),
+            locked_now: deposit.amount_locked(curr_ts),
+            voting_power: deposit.voting_power(mint_config, curr_ts)?,
+            voting_power_baseline: mint_config
+                .baseline_vote_weight(deposit.amount_deposited_native)?,
+            lockup_start_ts: lockup.start_ts(),
+            lockup_end_ts: has_end.then(|| lockup.end_ts()),
+            period_secs: lockup.kind.period_secs(),
+            periods_total,
+            periods_left: lockup.periods_left(curr_ts)?,
+            vesting_rate: (lockup.kind.is_vesting() && periods_total > 0)
+                .then(|| deposit.amount_initially_locked_native / periods_total),
+            cliff_end_ts: (lockup.cliff_periods_left(curr_ts) > 0).then(|| lockup.cliff_end_ts()),
+            unlocks: unlocks(deposit, curr_ts)?,
+        });
+    }
+
+    Ok(DisplayVoterReport {
+        voter: voter_address.to_string(),
+        voter_authority: voter.voter_authority.to_string(),
+        delegate: (voter.delegate != Pubkey::default()).then(|| voter.delegate.to_string()),
+        registrar: registrar_address.to_string(),
+        voter_weight_record: voter_weight_record_address.to_string(),
+        timestamp: curr_ts,
+        voting_power: deposit_entries.iter().map(|d| d.voting_power).sum(),
+        voting_power_baseline: deposit_entries
+            .iter()
+            .map(|d| d.voting_power_baseline)
+            .sum(),
+        voter_weight_record_weight: voter_weight_record.as_ref().map(|r| r.voter_weight),
+        voter_weight_record_expiry: voter_weight_record.and_then(|r| r.voter_weight_expiry),
+        deposit_entries,
+    })
+}
+
+/// Fetch a voter over RPC and print its JSON report to stdout
+pub fn show_voter(url: &str, registrar: &Pubkey, voter_authority: &Pubkey) -> Result<()> {
+    let client = RpcClient::new(url.to_string());
+    let now_ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let report = fetch_voter_report(&client, registrar, voter_authority, now_ts)?;
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use anchor_lang::AnchorSerialize;
+    use bytemuck::Zeroable;
+    use serde_json::{json, Value};
+    use solana_client::rpc_request::RpcRequest;
+    use std::collections::HashMap;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn mock_client(accounts: Vec<Option<Vec<u8>>>) -> RpcClient {
+        let value: Vec<Value> = accounts
+            .into_iter()
+            .map(|a| a.map(encode_account).unwrap_or(Value::Null))
+            .collect();
+        let response = json!({ "context": { "slot": 1 }, "value": value });
+        RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([(RpcRequest::GetMultipleAccounts, response)]),
+        )
+    }
+
+    #[test]
+    fn voter_report() -> Result<()> {
+        let now_ts = 1_000_000_000;
+        let registrar_address = Pubkey::new_unique();
+        let voter_authority = Pubkey::new_unique();
+
+        let mut registrar = Registrar::zeroed();
+        registrar.voting_mints[0].mint = Pubkey::new_unique();
+        registrar.voting_mints[0].baseline_vote_weight_scaled_factor = 1_000_000_000;
+        registrar.voting_mints[0].max_extra_lockup_vote_weight_scaled_factor = 1_000_000_000;
+        registrar.voting_mints[0].lockup_saturation_secs = (10 * DAY) as u64;
+
+        let mut voter = Voter::zeroed();
+        voter.voter_authority = voter_authority;
+        voter.registrar = registrar_address;
+        // 100 tokens, unlocked
+        voter.deposits[0].is_used = true;
+        voter.deposits[0].amount_deposited_native = 100;
+        // 30 tokens, vesting daily over three days, starting now
+        voter.deposits[2].is_used = true;
+        voter.deposits[2].amount_deposited_native = 30;
+        voter.deposits[2].amount_initially_locked_native = 30;
+        voter.deposits[2].lockup = Lockup::new_from_periods(LockupKind::Daily, now_ts, now_ts, 3)?;
+
+        let voter_weight_record = spl_governance_addin_api::voter_weight::VoterWeightRecord {
+            account_discriminator:
+                spl_governance_addin_api::voter_weight::VoterWeightRecord::ACCOUNT_DISCRIMINATOR,
+            realm: Pubkey::new_unique(),
+            governing_token_mint: Pubkey::new_unique(),
+            governing_token_owner: voter_authority,
+            voter_weight: 42,
+            voter_weight_expiry: Some(7),
+            weight_action: None,
+            weight_action_target: None,
+            reserved: [0; 8],
+        };
+
+        let client = mock_client(vec![
+            Some(zero_copy_data(&registrar)),
+            Some(zero_copy_data(&voter)),
+            Some(voter_weight_record.try_to_vec()?),
+        ]);
+        let report = fetch_voter_report(&client, &registrar_address, &voter_authority, now_ts)?;
+        let report = serde_json::to_value(&report)?;
+
+        let (voter_address, _) = voter_addresses(&registrar_address, &voter_authority);
+        assert_eq!(report["voter"], voter_address.to_string());
+        assert_eq!(report["voter_weight_record_weight"], 42);
+        assert_eq!(report["voter_weight_record_expiry"], 7);
+        assert_eq!(report["voting_power_baseline"], 130);
+        // the vesting deposit gets 1/10, 2/10 and 3/10 extra for its three
+        // vesting tranches of 10 tokens each
+        assert_eq!(report["voting_power"], 130 + 1 + 2 + 3);
+
+        let deposits = report["deposit_entries"].as_array().unwrap();
+        assert_eq!(deposits.len(), 2);
+        assert_eq!(deposits[0]["deposit_entry_index"], 0);
+        assert_eq!(deposits[0]["lockup_end_ts"], Value::Null);
+        assert_eq!(deposits[0]["unlocks"], json!([]));
+
+        assert_eq!(deposits[1]["deposit_entry_index"], 2);
+        assert_eq!(deposits[1]["lockup_kind"], "Daily");
+        assert_eq!(deposits[1]["locked_now"], 30);
+        assert_eq!(deposits[1]["vesting_rate"], 10);
+        assert_eq!(deposits[1]["periods_left"], 3);
+        assert_eq!(deposits[1]["lockup_end_ts"], now_ts + 3 * DAY);
+        assert_eq!(
+            deposits[1]["unlocks"],
+            json!([
+                { "timestamp": now_ts + DAY, "amount": 10 },
+                { "timestamp": now_ts + 2 * DAY, "amount": 10 },
+                { "timestamp": now_ts + 3 * DAY, "amount": 10 },
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn voter_report_missing_voter() {
+        let registrar = Registrar::zeroed();
+        let client = mock_client(vec![Some(zero_copy_data(&registrar)), None, None]);
+        let result = fetch_voter_report(
+            &client,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            1_000_000_000,
+        );
+        assert!(result.is_err());
+    }
+}
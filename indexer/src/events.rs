@@ -0,0 +1,74 @@
+//! Decoding the program's Anchor events.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{Context, Result};
+use voter_stake_registry::events::*;
+
+/// The events that change the state the indexer tracks
+#[derive(Debug)]
+pub enum Event {
+    RegistrarCreated(RegistrarCreatedEvent),
+    VotingMintConfigured(VotingMintConfiguredEvent),
+    LockupCurveConfigured(LockupCurveConfiguredEvent),
+    VotingMintRemoved(VotingMintRemovedEvent),
+    RealmAuthoritySynced(RealmAuthoritySyncedEvent),
+    VoterCreated(VoterCreatedEvent),
+    VoterClosed(VoterClosedEvent),
+    DepositEntryCreated(DepositEntryCreatedEvent),
+    DepositEntryClosed(DepositEntryClosedEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    Grant(GrantEvent),
+    Clawback(ClawbackEvent),
+    ResetLockup(ResetLockupEvent),
+    InternalTransfer(InternalTransferEvent),
+    DepositEntrySplit(DepositEntrySplitEvent),
+    DepositEntriesMerged(DepositEntriesMergedEvent),
+    VoterTransferred(VoterTransferredEvent),
+}
+
+macro_rules! decode_event {
+    ($discriminator:expr, $payload:expr, $($variant:ident($event:ty)),* $(,)?) => {
+        $(
+            if $discriminator == <$event>::discriminator() {
+                let event = <$event>::try_from_slice($payload)
+                    .context(concat!("decoding ", stringify!($event)))?;
+                return Ok(Some(Event::$variant(event)));
+            }
+        )*
+    };
+}
+
+/// Decodes event data as logged by emit!, including the discriminator.
+///
+/// Returns None for data that isn't one of the tracked events, like the
+/// informational events of log_voter_info or VoterResizedEvent.
+pub fn decode(data: &[u8]) -> Result<Option<Event>> {
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let (discriminator, payload) = data.split_at(8);
+    decode_event!(
+        discriminator,
+        payload,
+        RegistrarCreated(RegistrarCreatedEvent),
+        VotingMintConfigured(VotingMintConfiguredEvent),
+        LockupCurveConfigured(LockupCurveConfiguredEvent),
+        VotingMintRemoved(VotingMintRemovedEvent),
+        RealmAuthoritySynced(RealmAuthoritySyncedEvent),
+        VoterCreated(VoterCreatedEvent),
+        VoterClosed(VoterClosedEvent),
+        DepositEntryCreated(DepositEntryCreatedEvent),
+        DepositEntryClosed(DepositEntryClosedEvent),
+        Deposit(DepositEvent),
+        Withdraw(WithdrawEvent),
+        Grant(GrantEvent),
+        Clawback(ClawbackEvent),
+        ResetLockup(ResetLockupEvent),
+        InternalTransfer(InternalTransferEvent),
+        DepositEntrySplit(DepositEntrySplitEvent),
+        DepositEntriesMerged(DepositEntriesMergedEvent),
+        VoterTransferred(VoterTransferredEvent),
+    );
+    Ok(None)
+}
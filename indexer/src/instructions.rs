@@ -0,0 +1,336 @@
+//! Decoding the program's Anchor instructions.
+
+use crate::logs::Transaction;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+use voter_stake_registry::instruction as ix;
+use voter_stake_registry::state::*;
+
+/// A top-level instruction of the program in a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    /// Position among the transaction's top-level instructions
+    pub index: usize,
+    /// The Anchor instruction name, like "Deposit"
+    pub name: String,
+    /// The instruction arguments by name
+    pub args: Value,
+    /// The instruction's accounts, in the order of its Accounts struct
+    /// followed by the remaining accounts
+    pub accounts: Vec<Pubkey>,
+}
+
+/// Converts instruction arguments for Instruction::args
+trait Arg {
+    fn to_json(&self) -> Value;
+}
+
+macro_rules! number_arg {
+    ($($ty:ty),*) => {
+        $(
+            impl Arg for $ty {
+                fn to_json(&self) -> Value {
+                    json!(self)
+                }
+            }
+        )*
+    };
+}
+number_arg!(u8, u16, u32, u64, i8, i64, bool);
+
+macro_rules! debug_arg {
+    ($($ty:ty),*) => {
+        $(
+            impl Arg for $ty {
+                fn to_json(&self) -> Value {
+                    json!(format!("{:?}", self))
+                }
+            }
+        )*
+    };
+}
+debug_arg!(LockupKind, LockupCurveKind, MaxVoteWeightMode);
+
+impl Arg for Pubkey {
+    fn to_json(&self) -> Value {
+        json!(self.to_string())
+    }
+}
+
+impl Arg for LockupCurvePoint {
+    fn to_json(&self) -> Value {
+        json!({ "lockup_secs": self.lockup_secs, "scaled_factor": self.scaled_factor })
+    }
+}
+
+impl<T: Arg> Arg for Option<T> {
+    fn to_json(&self) -> Value {
+        self.as_ref().map_or(Value::Null, Arg::to_json)
+    }
+}
+
+impl<T: Arg> Arg for Vec<T> {
+    fn to_json(&self) -> Value {
+        Value::Array(self.iter().map(Arg::to_json).collect())
+    }
+}
+
+macro_rules! decode_instruction {
+    ($discriminator:expr, $payload:expr, $($name:ident { $($field:ident),* }),* $(,)?) => {
+        $(
+            if $discriminator == ix::$name::discriminator() {
+                // like the program's dispatch, trailing bytes are ignored
+                #[allow(unused_variables)]
+                let ix = ix::$name::deserialize(&mut &$payload[..])
+                    .context(concat!("decoding ", stringify!($name)))?;
+                #[allow(unused_mut)]
+                let mut args = Map::new();
+                $(args.insert(stringify!($field).to_string(), ix.$field.to_json());)*
+                return Ok(Some((stringify!($name), Value::Object(args))));
+            }
+        )*
+    };
+}
+
+/// Decodes instruction data, including the discriminator, into the
+/// instruction name and arguments.
+///
+/// Returns None for data that isn't one of the program's instructions, like
+/// instructions of a newer program version.
+pub fn decode(data: &[u8]) -> Result<Option<(&'static str, Value)>> {
+    if data.len() < 8 {
+        return Ok(None);
+    }
+    let (discriminator, payload) = data.split_at(8);
+    decode_instruction!(
+        discriminator,
+        payload,
+        CreateRegistrar { registrar_bump },
+        ConfigureVotingMint {
+            idx,
+            digit_shift,
+            baseline_vote_weight_scaled_factor,
+            max_extra_lockup_vote_weight_scaled_factor,
+            lockup_saturation_secs,
+            grant_authority
+        },
+        ConfigureDepositLimits {
+            idx,
+            min_deposit_native,
+            max_voter_deposited_native,
+            max_deposited_native
+        },
+        ConfigureLockupCurve { idx, kind, points },
+        ConfigureLockupLimits {
+            idx,
+            allowed_lockup_kinds,
+            max_lockup_days
+        },
+        ConfigureMaxVoteWeightMode { mode },
+        SyncRealmAuthority {},
+        RemoveVotingMint { idx },
+        ResizeRegistrar {},
+        BackfillVotingMintTotals {},
+        FinishVotingMintBackfill { idx },
+        CreateVoter {
+            voter_bump,
+            voter_weight_record_bump
+        },
+        ResizeVoter {
+            deposit_entry_count
+        },
+        SetDelegate { delegate },
+        ProposeVoterTransfer {
+            new_voter_authority
+        },
+        AcceptVoterTransfer {},
+        CreateDepositEntry {
+            deposit_entry_index,
+            kind,
+            start_ts,
+            periods,
+            allow_clawback
+        },
+        CreateDepositEntryWithCliff {
+            deposit_entry_index,
+            kind,
+            start_ts,
+            periods,
+            cliff_periods,
+            allow_clawback
+        },
+        Deposit {
+            deposit_entry_index,
+            amount
+        },
+        DepositUnlocked { amount },
+        Withdraw {
+            deposit_entry_index,
+            amount
+        },
+        Grant {
+            voter_bump,
+            voter_weight_record_bump,
+            kind,
+            start_ts,
+            periods,
+            allow_clawback,
+            amount
+        },
+        GrantWithCliff {
+            voter_bump,
+            voter_weight_record_bump,
+            kind,
+            start_ts,
+            periods,
+            cliff_periods,
+            allow_clawback,
+            amount
+        },
+        Clawback {
+            deposit_entry_index
+        },
+        PartialClawback {
+            deposit_entry_index,
+            amount
+        },
+        CloseDepositEntry {
+            deposit_entry_index
+        },
+        ResetLockup {
+            deposit_entry_index,
+            kind,
+            periods
+        },
+        InternalTransferLocked {
+            source_deposit_entry_index,
+            target_deposit_entry_index,
+            amount
+        },
+        SplitDepositEntry {
+            source_deposit_entry_index,
+            amount
+        },
+        MergeDepositEntries {
+            deposit_entry_indexes
+        },
+        InternalTransferUnlocked {
+            source_deposit_entry_index,
+            target_deposit_entry_index,
+            amount
+        },
+        UpdateVoterWeightRecord {},
+        UpdateVoterWeightRecords {},
+        CreateMaxVoterWeightRecord {},
+        UpdateMaxVoteWeight {},
+        CloseVoter {},
+        LogRegistrarInfo {},
+        LogVoterInfo {
+            deposit_entry_begin,
+            deposit_entry_count
+        },
+        SetTimeOffset { time_offset },
+    );
+    Ok(None)
+}
+
+/// Decodes the transaction's top-level instructions of the program.
+///
+/// Instructions of other programs are skipped, including the program's
+/// instructions that they invoke.
+pub fn decode_transaction(tx: &Transaction) -> Result<Vec<Instruction>> {
+    let account_keys = tx
+        .account_keys()
+        .into_iter()
+        .map(Pubkey::from_str)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("invalid account key")?;
+    let account_key = |index: u8| {
+        account_keys
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| anyhow!("account index {} out of range", index))
+    };
+
+    let mut result = vec![];
+    for (index, compiled) in tx.transaction.message.instructions.iter().enumerate() {
+        if account_key(compiled.program_id_index)? != voter_stake_registry::id() {
+            continue;
+        }
+        let data = bs58::decode(&compiled.data)
+            .into_vec()
+            .with_context(|| format!("instruction {}: invalid data", index))?;
+        let (name, args) = match decode(&data)? {
+            Some(decoded) => decoded,
+            None => continue,
+        };
+        let accounts = compiled
+            .accounts
+            .iter()
+            .map(|&i| account_key(i))
+            .collect::<Result<_>>()?;
+        result.push(Instruction {
+            index,
+            name: name.to_string(),
+            args,
+            accounts,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::InstructionData;
+
+    #[test]
+    fn decode_arguments() -> Result<()> {
+        let data = ix::CreateDepositEntry {
+            deposit_entry_index: 2,
+            kind: LockupKind::Cliff,
+            start_ts: None,
+            periods: 365,
+            allow_clawback: true,
+        }
+        .data();
+        let (name, args) = decode(&data)?.unwrap();
+        assert_eq!(name, "CreateDepositEntry");
+        assert_eq!(
+            args,
+            json!({
+                "deposit_entry_index": 2,
+                "kind": "Cliff",
+                "start_ts": null,
+                "periods": 365,
+                "allow_clawback": true,
+            })
+        );
+
+        let data = ix::MergeDepositEntries {
+            deposit_entry_indexes: vec![1, 3],
+        }
+        .data();
+        assert_eq!(
+            decode(&data)?.unwrap(),
+            (
+                "MergeDepositEntries",
+                json!({ "deposit_entry_indexes": [1, 3] })
+            )
+        );
+        assert_eq!(
+            decode(&ix::UpdateMaxVoteWeight {}.data())?.unwrap().1,
+            json!({})
+        );
+
+        assert!(decode(&[0; 8])?.is_none());
+        assert!(decode(&ix::Deposit::discriminator())
+            .unwrap_err()
+            .to_string()
+            .contains("Deposit"));
+        Ok(())
+    }
+}
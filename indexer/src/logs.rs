@@ -0,0 +1,220 @@
+//! Reading transactions and extracting the program's log output.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::io::BufRead;
+
+/// The subset of an RPC getTransaction response that the indexer needs, in
+/// the "json" encoding
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub transaction: TransactionData,
+    pub meta: TransactionMeta,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TransactionData {
+    pub signatures: Vec<String>,
+    pub message: Message,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub account_keys: Vec<String>,
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    /// Base58 encoded instruction data
+    pub data: String,
+}
+
+/// Accounts of versioned transactions that were loaded from address lookup tables
+#[derive(Deserialize, Debug, Default)]
+pub struct LoadedAddresses {
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    /// Set for failed transactions, whose logs describe no state changes
+    pub err: Option<serde_json::Value>,
+    #[serde(default)]
+    pub log_messages: Vec<String>,
+    #[serde(default)]
+    pub loaded_addresses: LoadedAddresses,
+}
+
+impl Transaction {
+    pub fn signature(&self) -> &str {
+        self.transaction
+            .signatures
+            .first()
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+    }
+
+    /// The accounts that compiled instructions index into: the message's
+    /// account keys, then the loaded writable and readonly addresses
+    pub fn account_keys(&self) -> Vec<&str> {
+        let loaded = &self.meta.loaded_addresses;
+        self.transaction
+            .message
+            .account_keys
+            .iter()
+            .chain(&loaded.writable)
+            .chain(&loaded.readonly)
+            .map(|key| key.as_str())
+            .collect()
+    }
+}
+
+/// Reads transactions from JSON lines, one getTransaction response per line.
+///
+/// Empty lines are skipped.
+pub fn read_transactions(reader: impl BufRead) -> Result<Vec<Transaction>> {
+    let mut transactions = vec![];
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tx = serde_json::from_str(&line)
+            .with_context(|| format!("line {}: invalid transaction", line_number + 1))?;
+        transactions.push(tx);
+    }
+    Ok(transactions)
+}
+
+/// Data logged by an invocation of the program
+#[derive(Debug, PartialEq, Eq)]
+pub struct ProgramData {
+    /// The Anchor instruction that was executing, like "Deposit"
+    pub instruction: Option<String>,
+    /// Position of the top-level instruction that the data was logged in,
+    /// which may be an instruction of another program that invoked this one
+    pub instruction_index: usize,
+    pub data: Vec<u8>,
+}
+
+/// Collects the "Program data:" entries that the program logged, in order.
+///
+/// Entries logged by other programs, including programs that the program
+/// invokes or that invoke it, are skipped. Fails if the logs were truncated,
+/// since events could be missing.
+///
+/// Top-level instructions are told apart by the invocation stack rather than
+/// the logged invoke depth, which program-test doesn't log reliably.
+pub fn program_data(log_messages: &[String], program_id: &str) -> Result<Vec<ProgramData>> {
+    let invoke_prefix = format!("Program {} invoke", program_id);
+    let mut stack: Vec<&str> = vec![];
+    let mut instruction = None;
+    let mut top_level_count = 0;
+    let mut result = vec![];
+    for message in log_messages {
+        if message == "Log truncated" {
+            bail!("logs were truncated");
+        }
+        let top = stack.last().copied();
+        if let Some(rest) = message.strip_prefix("Program data: ") {
+            if top == Some(program_id) {
+                for field in rest.split(' ') {
+                    result.push(ProgramData {
+                        instruction: instruction.clone(),
+                        instruction_index: top_level_count - 1,
+                        data: base64::decode(field)?,
+                    });
+                }
+            }
+        } else if let Some(name) = message.strip_prefix("Program log: Instruction: ") {
+            if top == Some(program_id) {
+                instruction = Some(name.to_string());
+            }
+        } else if message.starts_with("Program log: ") {
+            continue;
+        } else if let Some(rest) = message.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let invoked = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => {
+                    if stack.is_empty() {
+                        top_level_count += 1;
+                    }
+                    if message.starts_with(&invoke_prefix) {
+                        instruction = None;
+                    }
+                    stack.push(invoked);
+                }
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn program_data_filters_by_program() -> Result<()> {
+        let vsr = "4Q6WW2ouZ6V3iaNm56MTd5n2tnTm4C5fiH8miFHnAFHo";
+        let other = "GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw";
+        let messages = logs(&[
+            &format!("Program {} invoke [1]", other),
+            &format!("Program {} success", other),
+            &format!("Program {} invoke [1]", other),
+            "Program data: AQI=",
+            &format!("Program {} invoke [2]", vsr),
+            "Program log: Instruction: Deposit",
+            "Program data: AwQ=",
+            &format!("Program {} consumed 1000 of 200000 compute units", vsr),
+            &format!("Program {} success", vsr),
+            "Program data: BQY=",
+            &format!("Program {} success", other),
+            // program-test logs the invocations of builtins twice
+            &format!("Program {} invoke [1]", vsr),
+            &format!("Program {} invoke [1]", vsr),
+            "Program data: Bwg=",
+            &format!("Program {} success", vsr),
+            &format!("Program {} success", vsr),
+        ]);
+        let data = program_data(&messages, vsr)?;
+        assert_eq!(
+            data,
+            vec![
+                ProgramData {
+                    instruction: Some("Deposit".to_string()),
+                    instruction_index: 1,
+                    data: vec![3, 4],
+                },
+                ProgramData {
+                    instruction: None,
+                    instruction_index: 2,
+                    data: vec![7, 8],
+                },
+            ]
+        );
+
+        let truncated = logs(&[&format!("Program {} invoke [1]", vsr), "Log truncated"]);
+        assert!(program_data(&truncated, vsr).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+mod events;
+mod instructions;
+mod logs;
+mod store;
+
+#[derive(Serialize)]
+struct DisplayTimelineEntry {
+    signature: String,
+    slot: u64,
+    block_time: i64,
+    instruction: Option<String>,
+    instruction_args: Option<serde_json::Value>,
+    kind: String,
+    deposit_entry_index: u8,
+    amount: Option<u64>,
+    /// The deposit entry after the change, None once it was closed
+    amount_deposited_native: Option<u64>,
+    amount_initially_locked_native: Option<u64>,
+    lockup_kind: Option<String>,
+    lockup_start_ts: Option<i64>,
+    lockup_end_ts: Option<i64>,
+    voter_weight: Option<u64>,
+}
+
+impl From<store::TimelineEntry> for DisplayTimelineEntry {
+    fn from(entry: store::TimelineEntry) -> Self {
+        let state = entry.state;
+        Self {
+            signature: entry.signature,
+            slot: entry.slot,
+            block_time: entry.block_time,
+            instruction: entry.instruction,
+            instruction_args: entry.instruction_args,
+            kind: entry.kind,
+            deposit_entry_index: entry.deposit_entry_index,
+            amount: entry.amount,
+            amount_deposited_native: state.map(|s| s.amount_deposited_native),
+            amount_initially_locked_native: state.map(|s| s.amount_initially_locked_native),
+            lockup_kind: state.map(|s| format!("{:?}", s.lockup.kind)),
+            lockup_start_ts: state.map(|s| s.lockup.start_ts),
+            lockup_end_ts: state.map(|s| s.lockup.end_ts),
+            voter_weight: entry.voter_weight,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DisplayInstruction {
+    signature: String,
+    slot: u64,
+    block_time: i64,
+    instruction_index: usize,
+    name: String,
+    args: serde_json::Value,
+    accounts: Vec<String>,
+}
+
+impl From<store::IndexedInstruction> for DisplayInstruction {
+    fn from(entry: store::IndexedInstruction) -> Self {
+        let instruction = entry.instruction;
+        Self {
+            signature: entry.signature,
+            slot: entry.slot,
+            block_time: entry.block_time,
+            instruction_index: instruction.index,
+            name: instruction.name,
+            args: instruction.args,
+            accounts: instruction.accounts.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// SQLite database to write to or query, created if needed
+    #[clap(long, default_value = "vsr-history.sqlite")]
+    db: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Index transactions, given as getTransaction responses in JSON lines.
+    ///
+    /// The transactions must be in execution order. Failed and already
+    /// indexed transactions are skipped.
+    Index {
+        /// Read transactions from this file instead of stdin
+        #[clap(long)]
+        input: Option<PathBuf>,
+    },
+    /// Print the history of a voter's deposit entries as JSON lines
+    Timeline {
+        #[clap(long)]
+        voter: Pubkey,
+    },
+    /// Print the program's instructions that were passed an account, like a
+    /// voter or registrar, as JSON lines
+    Instructions {
+        #[clap(long)]
+        account: Pubkey,
+    },
+    /// Recompute a voter's vote weight at a unix timestamp
+    VotingPower {
+        #[clap(long)]
+        voter: Pubkey,
+        #[clap(long)]
+        timestamp: i64,
+    },
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut store = store::Store::open(&args.db)?;
+    match args.command {
+        Command::Index { input } => {
+            let transactions = match input {
+                Some(path) => logs::read_transactions(BufReader::new(File::open(path)?))?,
+                None => logs::read_transactions(io::stdin().lock())?,
+            };
+            let mut indexed = 0;
+            for tx in &transactions {
+                if store.index_transaction(tx)? {
+                    indexed += 1;
+                }
+            }
+            eprintln!("indexed {} of {} transactions", indexed, transactions.len());
+        }
+        Command::Timeline { voter } => {
+            for entry in store.timeline(&voter)? {
+                let entry = DisplayTimelineEntry::from(entry);
+                println!("{}", serde_json::to_string(&entry)?);
+            }
+        }
+        Command::Instructions { account } => {
+            for entry in store.instructions(&account)? {
+                let entry = DisplayInstruction::from(entry);
+                println!("{}", serde_json::to_string(&entry)?);
+            }
+        }
+        Command::VotingPower { voter, timestamp } => {
+            println!("{}", store.voting_power_at(&voter, timestamp)?);
+        }
+    }
+    Ok(())
+}
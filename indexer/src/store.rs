@@ -0,0 +1,960 @@
+//! The SQLite store: applies decoded events, records decoded instructions and
+//! answers history queries.
+//!
+//! Transactions must be indexed in the order they were executed. Voting
+//! power can only be recomputed for registrars whose history was indexed
+//! from the start, since it depends on the voting mint configuration.
+
+use crate::events::{self, Event};
+use crate::instructions::{self, Instruction};
+use crate::logs::{self, Transaction};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use anyhow::{anyhow, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use voter_stake_registry::events::DepositEntryState;
+use voter_stake_registry::state::*;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature TEXT PRIMARY KEY,
+    slot INTEGER NOT NULL,
+    block_time INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS registrars (
+    registrar TEXT PRIMARY KEY,
+    realm TEXT NOT NULL,
+    realm_authority TEXT NOT NULL
+);
+
+-- Every version of every voting mint config. config is the borsh encoded
+-- VotingMintConfig.
+CREATE TABLE IF NOT EXISTS voting_mint_configs (
+    version INTEGER PRIMARY KEY,
+    registrar TEXT NOT NULL,
+    voting_mint_config_index INTEGER NOT NULL,
+    block_time INTEGER NOT NULL,
+    mint TEXT NOT NULL,
+    config BLOB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS voters (
+    voter TEXT PRIMARY KEY,
+    registrar TEXT NOT NULL,
+    voter_authority TEXT NOT NULL,
+    created_slot INTEGER NOT NULL,
+    closed_slot INTEGER
+);
+
+-- The program's top-level instructions. args is a JSON object of the
+-- arguments, accounts a JSON array of the account addresses.
+CREATE TABLE IF NOT EXISTS instructions (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    args TEXT NOT NULL,
+    accounts TEXT NOT NULL,
+    PRIMARY KEY (signature, instruction_index)
+);
+
+-- One row per change of a deposit entry. state is the borsh encoded
+-- DepositEntryState after the change, NULL once the entry was closed.
+-- instruction_index is the top-level instruction of the change, which is
+-- only in instructions if it's one of the program's.
+CREATE TABLE IF NOT EXISTS deposit_history (
+    seq INTEGER PRIMARY KEY,
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    instruction TEXT,
+    kind TEXT NOT NULL,
+    registrar TEXT NOT NULL,
+    voter TEXT NOT NULL,
+    deposit_entry_index INTEGER NOT NULL,
+    amount INTEGER,
+    amount_deposited_native INTEGER,
+    lockup_kind TEXT,
+    lockup_end_ts INTEGER,
+    state BLOB,
+    voter_weight INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS deposit_history_voter
+    ON deposit_history (voter, deposit_entry_index, seq);
+CREATE INDEX IF NOT EXISTS voting_mint_configs_index
+    ON voting_mint_configs (registrar, voting_mint_config_index, version);
+";
+
+/// The transaction that is being indexed
+struct TxContext<'a> {
+    signature: &'a str,
+    slot: u64,
+    block_time: i64,
+    instruction_index: usize,
+    instruction: Option<&'a str>,
+}
+
+/// A row of deposit_history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: i64,
+    pub instruction: Option<String>,
+    /// Arguments of the instruction, if it was a top-level instruction of
+    /// the program
+    pub instruction_args: Option<serde_json::Value>,
+    pub kind: String,
+    pub deposit_entry_index: u8,
+    pub amount: Option<u64>,
+    pub state: Option<DepositEntryState>,
+    pub voter_weight: Option<u64>,
+}
+
+/// A row of instructions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedInstruction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: i64,
+    pub instruction: Instruction,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Records the program's instructions and applies the events of a
+    /// transaction.
+    ///
+    /// Returns false if the transaction failed or was indexed before, so
+    /// replaying overlapping inputs is harmless.
+    pub fn index_transaction(&mut self, tx: &Transaction) -> Result<bool> {
+        let signature = tx.signature();
+        if tx.meta.err.is_some() {
+            return Ok(false);
+        }
+        let known: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM transactions WHERE signature = ?1",
+                params![signature],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if known.is_some() {
+            return Ok(false);
+        }
+        let block_time = tx
+            .block_time
+            .ok_or_else(|| anyhow!("transaction {} has no block time", signature))?;
+        let program_id = voter_stake_registry::id().to_string();
+        let program_data = logs::program_data(&tx.meta.log_messages, &program_id)
+            .with_context(|| format!("transaction {}", signature))?;
+        let instructions = instructions::decode_transaction(tx)
+            .with_context(|| format!("transaction {}", signature))?;
+
+        let db = self.conn.transaction()?;
+        db.execute(
+            "INSERT INTO transactions (signature, slot, block_time) VALUES (?1, ?2, ?3)",
+            params![signature, tx.slot, block_time],
+        )?;
+        for instruction in &instructions {
+            let accounts: Vec<_> = instruction.accounts.iter().map(|a| a.to_string()).collect();
+            db.execute(
+                "INSERT INTO instructions
+                     (signature, instruction_index, slot, block_time, name, args, accounts)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    signature,
+                    instruction.index,
+                    tx.slot,
+                    block_time,
+                    instruction.name,
+                    instruction.args.to_string(),
+                    serde_json::to_string(&accounts)?,
+                ],
+            )?;
+        }
+        for entry in program_data {
+            let event = match events::decode(&entry.data)
+                .with_context(|| format!("transaction {}", signature))?
+            {
+                Some(event) => event,
+                None => continue,
+            };
+            // The logged name is the only one for invocations by other programs
+            let instruction = instructions
+                .iter()
+                .find(|i| i.index == entry.instruction_index)
+                .map(|i| i.name.as_str())
+                .or(entry.instruction.as_deref());
+            let ctx = TxContext {
+                signature,
+                slot: tx.slot,
+                block_time,
+                instruction_index: entry.instruction_index,
+                instruction,
+            };
+            apply(&db, &ctx, event)?;
+        }
+        db.commit()?;
+        Ok(true)
+    }
+
+    /// All changes to the voter's deposit entries, oldest first
+    pub fn timeline(&self, voter: &Pubkey) -> Result<Vec<TimelineEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT h.signature, h.slot, h.block_time, h.instruction, h.kind,
+                    h.deposit_entry_index, h.amount, h.state, h.voter_weight, i.args
+             FROM deposit_history h
+             LEFT JOIN instructions i
+                 ON i.signature = h.signature AND i.instruction_index = h.instruction_index
+             WHERE h.voter = ?1 ORDER BY h.seq",
+        )?;
+        let rows = stmt.query_map(params![voter.to_string()], |row| {
+            Ok((
+                TimelineEntry {
+                    signature: row.get(0)?,
+                    slot: row.get(1)?,
+                    block_time: row.get(2)?,
+                    instruction: row.get(3)?,
+                    instruction_args: None,
+                    kind: row.get(4)?,
+                    deposit_entry_index: row.get(5)?,
+                    amount: row.get(6)?,
+                    state: None,
+                    voter_weight: row.get(8)?,
+                },
+                row.get::<_, Option<Vec<u8>>>(7)?,
+                row.get::<_, Option<String>>(9)?,
+            ))
+        })?;
+        rows.map(|row| {
+            let (mut entry, state, args) = row?;
+            entry.state = state
+                .map(|s| DepositEntryState::try_from_slice(&s))
+                .transpose()?;
+            entry.instruction_args = args.map(|a| serde_json::from_str(&a)).transpose()?;
+            Ok(entry)
+        })
+        .collect()
+    }
+
+    /// The program's instructions that were passed the account, oldest first
+    pub fn instructions(&self, account: &Pubkey) -> Result<Vec<IndexedInstruction>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT i.signature, i.slot, i.block_time, i.instruction_index, i.name, i.args,
+                    i.accounts
+             FROM instructions i
+             WHERE EXISTS (SELECT 1 FROM json_each(i.accounts) WHERE value = ?1)
+             ORDER BY i.rowid",
+        )?;
+        let rows = stmt.query_map(params![account.to_string()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, usize>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })?;
+        rows.map(|row| {
+            let (signature, slot, block_time, index, name, args, accounts) = row?;
+            let accounts: Vec<String> = serde_json::from_str(&accounts)?;
+            Ok(IndexedInstruction {
+                signature,
+                slot,
+                block_time,
+                instruction: Instruction {
+                    index,
+                    name,
+                    args: serde_json::from_str(&args)?,
+                    accounts: accounts
+                        .iter()
+                        .map(|a| a.parse())
+                        .collect::<std::result::Result<_, _>>()?,
+                },
+            })
+        })
+        .collect()
+    }
+
+    /// Recomputes the voter's vote weight at `ts`, from the deposit entries
+    /// and voting mint configs as they were at that time.
+    pub fn voting_power_at(&self, voter: &Pubkey, ts: i64) -> Result<u64> {
+        let mut stmt = self.conn.prepare(
+            "SELECT h.registrar, h.state FROM deposit_history h
+             WHERE h.voter = ?1 AND h.state IS NOT NULL AND h.seq = (
+                 SELECT MAX(seq) FROM deposit_history
+                 WHERE voter = h.voter AND deposit_entry_index = h.deposit_entry_index
+                     AND block_time <= ?2
+             )",
+        )?;
+        let rows = stmt.query_map(params![voter.to_string(), ts], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        let mut weight = 0u64;
+        for row in rows {
+            let (registrar, state) = row?;
+            let deposit_entry = DepositEntry::from(&DepositEntryState::try_from_slice(&state)?);
+            let mint_config = voting_mint_config_at(
+                &self.conn,
+                &registrar,
+                deposit_entry.voting_mint_config_idx,
+                ts,
+            )?
+            .ok_or_else(|| {
+                anyhow!(
+                    "no voting mint config {} for registrar {} at {}",
+                    deposit_entry.voting_mint_config_idx,
+                    registrar,
+                    ts
+                )
+            })?;
+            weight = weight
+                .checked_add(deposit_entry.voting_power(&mint_config, ts)?)
+                .ok_or_else(|| anyhow!("vote weight overflow"))?;
+        }
+        Ok(weight)
+    }
+}
+
+/// The latest version of a voting mint config at `ts`
+fn voting_mint_config_at(
+    conn: &Connection,
+    registrar: &str,
+    idx: u8,
+    ts: i64,
+) -> Result<Option<VotingMintConfig>> {
+    let config: Option<Vec<u8>> = conn
+        .query_row(
+            "SELECT config FROM voting_mint_configs
+             WHERE registrar = ?1 AND voting_mint_config_index = ?2 AND block_time <= ?3
+             ORDER BY version DESC LIMIT 1",
+            params![registrar, idx, ts],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(config
+        .map(|c| VotingMintConfig::try_from_slice(&c))
+        .transpose()?)
+}
+
+fn insert_voting_mint_config(
+    conn: &Connection,
+    ctx: &TxContext,
+    registrar: &Pubkey,
+    idx: u16,
+    config: &VotingMintConfig,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO voting_mint_configs
+             (registrar, voting_mint_config_index, block_time, mint, config)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            registrar.to_string(),
+            idx,
+            ctx.block_time,
+            config.mint.to_string(),
+            config.try_to_vec()?,
+        ],
+    )?;
+    Ok(())
+}
+
+/// The latest state of each of the voter's open deposit entries
+fn open_deposit_entries(conn: &Connection, voter: &Pubkey) -> Result<Vec<(u8, DepositEntryState)>> {
+    let mut stmt = conn.prepare(
+        "SELECT h.deposit_entry_index, h.state FROM deposit_history h
+         WHERE h.voter = ?1 AND h.state IS NOT NULL AND h.seq = (
+             SELECT MAX(seq) FROM deposit_history
+             WHERE voter = h.voter AND deposit_entry_index = h.deposit_entry_index
+         )
+         ORDER BY h.deposit_entry_index",
+    )?;
+    let rows = stmt.query_map(params![voter.to_string()], |row| {
+        Ok((row.get::<_, u8>(0)?, row.get::<_, Vec<u8>>(1)?))
+    })?;
+    rows.map(|row| {
+        let (index, state) = row?;
+        Ok((index, DepositEntryState::try_from_slice(&state)?))
+    })
+    .collect()
+}
+
+/// Ensures the voter is known, grants may create voters without a
+/// VoterCreatedEvent
+fn insert_voter(
+    conn: &Connection,
+    ctx: &TxContext,
+    registrar: &Pubkey,
+    voter: &Pubkey,
+    voter_authority: &Pubkey,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO voters (voter, registrar, voter_authority, created_slot)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            voter.to_string(),
+            registrar.to_string(),
+            voter_authority.to_string(),
+            ctx.slot
+        ],
+    )?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn insert_history(
+    conn: &Connection,
+    ctx: &TxContext,
+    kind: &str,
+    registrar: &Pubkey,
+    voter: &Pubkey,
+    deposit_entry_index: u8,
+    amount: Option<u64>,
+    state: Option<&DepositEntryState>,
+    voter_weight: Option<u64>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO deposit_history
+             (signature, slot, block_time, instruction_index, instruction, kind, registrar,
+              voter, deposit_entry_index, amount, amount_deposited_native, lockup_kind,
+              lockup_end_ts, state, voter_weight)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        params![
+            ctx.signature,
+            ctx.slot,
+            ctx.block_time,
+            ctx.instruction_index,
+            ctx.instruction,
+            kind,
+            registrar.to_string(),
+            voter.to_string(),
+            deposit_entry_index,
+            amount,
+            state.map(|s| s.amount_deposited_native),
+            state.map(|s| format!("{:?}", s.lockup.kind)),
+            state.map(|s| s.lockup.end_ts),
+            state.map(|s| s.try_to_vec()).transpose()?,
+            voter_weight,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Applies a single event, mirroring what the instruction did on-chain
+fn apply(conn: &Connection, ctx: &TxContext, event: Event) -> Result<()> {
+    match event {
+        Event::RegistrarCreated(e) => {
+            conn.execute(
+                "INSERT OR REPLACE INTO registrars (registrar, realm, realm_authority)
+                 VALUES (?1, ?2, ?3)",
+                params![
+                    e.registrar.to_string(),
+                    e.realm.to_string(),
+                    e.realm_authority.to_string()
+                ],
+            )?;
+        }
+        Event::RealmAuthoritySynced(e) => {
+            conn.execute(
+                "UPDATE registrars SET realm_authority = ?2 WHERE registrar = ?1",
+                params![e.registrar.to_string(), e.new_realm_authority.to_string()],
+            )?;
+        }
+        Event::VotingMintConfigured(e) => {
+            let registrar = e.registrar.to_string();
+            let idx = e.voting_mint_config_index;
+            let previous = voting_mint_config_at(conn, &registrar, idx as u8, ctx.block_time)?;
+            // Like configure_voting_mint: reconfiguring a mint keeps its
+            // lockup curve, a new mint starts out linear.
+            let curve = match previous {
+                Some(previous) if previous.mint == e.mint => LockupCurve::new(
+                    previous.lockup_curve_kind,
+                    e.lockup_saturation_secs,
+                    previous.lockup_curve().points(),
+                )?,
+                _ => LockupCurve::linear(e.lockup_saturation_secs),
+            };
+            let mut config = VotingMintConfig {
+                mint: e.mint,
+                grant_authority: e.grant_authority,
+                baseline_vote_weight_scaled_factor: e.baseline_vote_weight_scaled_factor,
+                max_extra_lockup_vote_weight_scaled_factor: e
+                    .max_extra_lockup_vote_weight_scaled_factor,
+                lockup_saturation_secs: e.lockup_saturation_secs,
+                digit_shift: e.digit_shift,
+                ..VotingMintConfig::default()
+            };
+            config.set_lockup_curve(&curve);
+            insert_voting_mint_config(conn, ctx, &e.registrar, idx, &config)?;
+        }
+        Event::LockupCurveConfigured(e) => {
+            let idx = e.voting_mint_config_index;
+            let mut config =
+                voting_mint_config_at(conn, &e.registrar.to_string(), idx as u8, ctx.block_time)?
+                    .ok_or_else(|| anyhow!("lockup curve configured for unknown voting mint"))?;
+            let curve = LockupCurve::new(e.kind, e.lockup_saturation_secs, &e.points)?;
+            config.set_lockup_curve(&curve);
+            insert_voting_mint_config(conn, ctx, &e.registrar, idx, &config)?;
+        }
+        Event::VotingMintRemoved(e) => {
+            let idx = e.voting_mint_config_index;
+            let mut config =
+                voting_mint_config_at(conn, &e.registrar.to_string(), idx as u8, ctx.block_time)?
+                    .ok_or_else(|| anyhow!("removed unknown voting mint"))?;
+            // Like remove_voting_mint: deprecated mints grant no vote weight
+            config.deprecated = true;
+            config.baseline_vote_weight_scaled_factor = 0;
+            config.max_extra_lockup_vote_weight_scaled_factor = 0;
+            config.grant_authority = Pubkey::default();
+            insert_voting_mint_config(conn, ctx, &e.registrar, idx, &config)?;
+        }
+        Event::VoterCreated(e) => {
+            insert_voter(conn, ctx, &e.registrar, &e.voter, &e.voter_authority)?;
+        }
+        Event::VoterClosed(e) => {
+            conn.execute(
+                "UPDATE voters SET closed_slot = ?2 WHERE voter = ?1",
+                params![e.voter.to_string(), ctx.slot],
+            )?;
+        }
+        Event::DepositEntryCreated(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "create",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                None,
+                Some(&e.deposit_entry),
+                None,
+            )?;
+        }
+        Event::DepositEntryClosed(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "close",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                None,
+                None,
+                None,
+            )?;
+        }
+        Event::Deposit(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "deposit",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                Some(e.amount),
+                Some(&e.after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::Withdraw(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "withdraw",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                Some(e.amount),
+                Some(&e.after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::Grant(e) => {
+            insert_voter(conn, ctx, &e.registrar, &e.voter, &e.voter_authority)?;
+            insert_history(
+                conn,
+                ctx,
+                "grant",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                Some(e.amount),
+                Some(&e.deposit_entry),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::Clawback(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "clawback",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                Some(e.amount),
+                Some(&e.after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::ResetLockup(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "reset_lockup",
+                &e.registrar,
+                &e.voter,
+                e.deposit_entry_index,
+                None,
+                Some(&e.after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::InternalTransfer(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "transfer_out",
+                &e.registrar,
+                &e.voter,
+                e.source_deposit_entry_index,
+                Some(e.amount),
+                Some(&e.source_after),
+                Some(e.voter_weight),
+            )?;
+            insert_history(
+                conn,
+                ctx,
+                "transfer_in",
+                &e.registrar,
+                &e.voter,
+                e.target_deposit_entry_index,
+                Some(e.amount),
+                Some(&e.target_after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::DepositEntrySplit(e) => {
+            insert_history(
+                conn,
+                ctx,
+                "split_out",
+                &e.registrar,
+                &e.voter,
+                e.source_deposit_entry_index,
+                Some(e.amount),
+                Some(&e.source_after),
+                Some(e.voter_weight),
+            )?;
+            insert_history(
+                conn,
+                ctx,
+                "split_in",
+                &e.registrar,
+                &e.voter,
+                e.target_deposit_entry_index,
+                Some(e.amount),
+                Some(&e.target),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::DepositEntriesMerged(e) => {
+            // the source entries are freed, like with close
+            for &index in &e.source_deposit_entry_indexes {
+                insert_history(
+                    conn,
+                    ctx,
+                    "merge_out",
+                    &e.registrar,
+                    &e.voter,
+                    index,
+                    None,
+                    None,
+                    None,
+                )?;
+            }
+            insert_history(
+                conn,
+                ctx,
+                "merge_in",
+                &e.registrar,
+                &e.voter,
+                e.target_deposit_entry_index,
+                Some(e.amount),
+                Some(&e.target_after),
+                Some(e.voter_weight),
+            )?;
+        }
+        Event::VoterTransferred(e) => {
+            insert_voter(
+                conn,
+                ctx,
+                &e.registrar,
+                &e.new_voter,
+                &e.new_voter_authority,
+            )?;
+            conn.execute(
+                "UPDATE voters SET closed_slot = ?2 WHERE voter = ?1",
+                params![e.old_voter.to_string(), ctx.slot],
+            )?;
+            // Like accept_voter_transfer: the deposit entries move to the
+            // new voter unchanged, at the same indexes
+            for (index, state) in open_deposit_entries(conn, &e.old_voter)? {
+                insert_history(
+                    conn,
+                    ctx,
+                    "voter_transfer_out",
+                    &e.registrar,
+                    &e.old_voter,
+                    index,
+                    None,
+                    None,
+                    None,
+                )?;
+                insert_history(
+                    conn,
+                    ctx,
+                    "voter_transfer_in",
+                    &e.registrar,
+                    &e.new_voter,
+                    index,
+                    None,
+                    Some(&state),
+                    None,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    const SECS_PER_YEAR: i64 = 365 * 24 * 60 * 60;
+
+    /// A registrar with one voting mint at a baseline and max extra factor
+    /// of 1 and one year saturation, and a voter with a cliff lockup and an
+    /// unlocked deposit entry. Includes a failed transaction.
+    ///
+    /// Recorded by the program's test_indexer_fixture test.
+    fn index_fixture() -> Result<(Store, Pubkey)> {
+        let fixture = include_str!("../tests/fixtures/transactions.jsonl");
+        let transactions = logs::read_transactions(fixture.as_bytes())?;
+        assert_eq!(transactions.len(), 12);
+
+        let mut store = Store::open_in_memory()?;
+        let mut indexed = 0;
+        for tx in &transactions {
+            if store.index_transaction(tx)? {
+                indexed += 1;
+            }
+        }
+        assert_eq!(indexed, 11);
+
+        // replaying is a no-op
+        for tx in &transactions {
+            assert!(!store.index_transaction(tx)?);
+        }
+
+        let voter: String = store
+            .conn
+            .query_row("SELECT voter FROM voters", [], |row| row.get(0))?;
+        Ok((store, Pubkey::from_str(&voter)?))
+    }
+
+    #[test]
+    fn replay_timeline() -> Result<()> {
+        let (store, voter) = index_fixture()?;
+        let timeline = store.timeline(&voter)?;
+        let steps: Vec<_> = timeline
+            .iter()
+            .map(|e| {
+                (
+                    e.kind.as_str(),
+                    e.instruction.as_deref().unwrap(),
+                    e.deposit_entry_index,
+                    e.amount,
+                )
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("create", "CreateDepositEntry", 0, None),
+                ("deposit", "Deposit", 0, Some(1000)),
+                ("create", "CreateDepositEntry", 1, None),
+                ("deposit", "Deposit", 1, Some(500)),
+                ("withdraw", "Withdraw", 1, Some(200)),
+                ("reset_lockup", "ResetLockup", 0, None),
+            ]
+        );
+
+        let reset = timeline.last().unwrap().state.unwrap();
+        assert_eq!(reset.lockup.kind, LockupKind::Cliff);
+        assert_eq!(
+            reset.lockup.end_ts - reset.lockup.start_ts,
+            2 * SECS_PER_YEAR
+        );
+        assert_eq!(timeline[4].state.unwrap().amount_deposited_native, 300);
+        assert_eq!(
+            timeline.last().unwrap().instruction_args,
+            Some(json!({ "deposit_entry_index": 0, "kind": "Cliff", "periods": 730 }))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replay_instructions() -> Result<()> {
+        let (store, voter) = index_fixture()?;
+        let instructions = store.instructions(&voter)?;
+        let names: Vec<_> = instructions
+            .iter()
+            .map(|i| i.instruction.name.as_str())
+            .collect();
+        // the failed deposit isn't indexed
+        assert_eq!(
+            names,
+            vec![
+                "CreateVoter",
+                "CreateDepositEntry",
+                "Deposit",
+                "CreateDepositEntry",
+                "Deposit",
+                "Withdraw",
+                "LogVoterInfo",
+                "ResetLockup",
+            ]
+        );
+
+        let withdraw = &instructions[5];
+        assert_eq!(
+            withdraw.instruction.args,
+            json!({ "deposit_entry_index": 1, "amount": 200 })
+        );
+        // registrar, voter, voter authority, ...
+        assert_eq!(withdraw.instruction.accounts[1], voter);
+        let timeline = store.timeline(&voter)?;
+        assert_eq!(withdraw.signature, timeline[4].signature);
+        assert_eq!(withdraw.block_time, timeline[4].block_time);
+        Ok(())
+    }
+
+    #[test]
+    fn replay_voting_power() -> Result<()> {
+        let (store, voter) = index_fixture()?;
+        let timeline = store.timeline(&voter)?;
+
+        // the recomputed vote weight matches what the program reported
+        for entry in timeline.iter().filter(|e| e.voter_weight.is_some()) {
+            assert_eq!(
+                store.voting_power_at(&voter, entry.block_time)?,
+                entry.voter_weight.unwrap()
+            );
+        }
+
+        let reset = timeline.last().unwrap().state.unwrap();
+        let start = reset.lockup.start_ts;
+        let end = reset.lockup.end_ts;
+        assert_eq!(
+            store.voting_power_at(&voter, timeline[0].block_time - 1)?,
+            0
+        );
+        assert_eq!(store.voting_power_at(&voter, timeline[0].block_time)?, 0);
+        // half the saturation left on the cliff lockup
+        assert_eq!(
+            store.voting_power_at(&voter, start + SECS_PER_YEAR + SECS_PER_YEAR / 2)?,
+            1000 + 500 + 300
+        );
+        assert_eq!(store.voting_power_at(&voter, end)?, 1000 + 300);
+
+        // the voting mint was removed afterwards
+        assert_eq!(store.voting_power_at(&voter, end + 999)?, 1000 + 300);
+        assert_eq!(store.voting_power_at(&voter, end + 1000)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn replay_voter_transfer() -> Result<()> {
+        let (store, voter) = index_fixture()?;
+        let registrar: String = store.conn.query_row(
+            "SELECT registrar FROM voters WHERE voter = ?1",
+            params![voter.to_string()],
+            |row| row.get(0),
+        )?;
+        let end = store
+            .timeline(&voter)?
+            .last()
+            .unwrap()
+            .state
+            .unwrap()
+            .lockup
+            .end_ts;
+        let ts = end + 10;
+
+        let new_voter = Pubkey::new_unique();
+        let ctx = TxContext {
+            signature: "transfer",
+            slot: 2000,
+            block_time: ts,
+            instruction_index: 0,
+            instruction: Some("AcceptVoterTransfer"),
+        };
+        apply(
+            &store.conn,
+            &ctx,
+            Event::VoterTransferred(voter_stake_registry::events::VoterTransferredEvent {
+                registrar: Pubkey::from_str(&registrar)?,
+                old_voter: voter,
+                old_voter_authority: Pubkey::new_unique(),
+                new_voter,
+                new_voter_authority: Pubkey::new_unique(),
+            }),
+        )?;
+
+        let steps: Vec<_> = store
+            .timeline(&new_voter)?
+            .iter()
+            .map(|e| (e.kind.clone(), e.deposit_entry_index))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("voter_transfer_in".to_string(), 0),
+                ("voter_transfer_in".to_string(), 1),
+            ]
+        );
+        assert_eq!(store.voting_power_at(&new_voter, ts)?, 1000 + 300);
+        assert_eq!(store.voting_power_at(&voter, ts)?, 0);
+        assert_eq!(store.voting_power_at(&voter, ts - 1)?, 1000 + 300);
+        Ok(())
+    }
+}
@@ -116,4 +116,70 @@ pub enum VsrError {
     // 6037 / 0x1795
     #[msg("")]
     InvalidTimestampArguments,
+    // 6038 / 0x1796
+    #[msg("Token program must be spl-token or token-2022")]
+    InvalidTokenProgram,
+    // 6039 / 0x1797
+    #[msg("")]
+    InvalidVault,
+    // 6040 / 0x1798
+    #[msg("")]
+    InvalidRegistrarSize,
+    // 6041 / 0x1799
+    #[msg("")]
+    InvalidVoterSize,
+    // 6042 / 0x179a
+    #[msg("Voting mint was removed and accepts no new deposits")]
+    VotingMintDeprecated,
+    // 6043 / 0x179b
+    #[msg("Voting mint was removed, but tokens of it may still be deposited")]
+    VotingMintHasDeposits,
+    // 6044 / 0x179c
+    #[msg("Lockup curve points must increase up to the full factor at lockup saturation")]
+    InvalidLockupCurve,
+    // 6045 / 0x179d
+    #[msg("Lockup cliffs are only allowed for CliffMonthly and can't be shortened")]
+    InvalidLockupCliff,
+    // 6046 / 0x179e
+    #[msg("The delegate must differ from the voter authority")]
+    InvalidDelegate,
+    // 6047 / 0x179f
+    #[msg("The new voter authority must be proposed and differ from the current one")]
+    InvalidNewVoterAuthority,
+    // 6048 / 0x17a0
+    #[msg("The vaults of all deposited mints must be transferred in full")]
+    VaultNotTransferred,
+    // 6049 / 0x17a1
+    #[msg("Split amount must be positive and less than the deposited amount")]
+    InvalidSplitAmount,
+    // 6050 / 0x17a2
+    #[msg("Merging needs at least two distinct deposit entries")]
+    InvalidMergeDepositEntries,
+    // 6051 / 0x17a3
+    #[msg("Deposit is below the voting mint's minimum deposit")]
+    DepositBelowMinimum,
+    // 6052 / 0x17a4
+    #[msg("Deposit exceeds the voting mint's per-voter cap")]
+    VoterDepositCapExceeded,
+    // 6053 / 0x17a5
+    #[msg("Deposit exceeds the voting mint's registrar-wide cap")]
+    RegistrarDepositCapExceeded,
+    // 6054 / 0x17a6
+    #[msg("Registrar-wide deposit caps and the Deposited max vote weight mode need voting mints with tracked deposits")]
+    VotingMintDepositsNotTracked,
+    // 6055 / 0x17a7
+    #[msg("Lockup kind is not allowed for the voting mint")]
+    LockupKindNotAllowed,
+    // 6056 / 0x17a8
+    #[msg("Lockup is longer than the voting mint's maximum lockup duration")]
+    LockupTooLong,
+    // 6057 / 0x17a9
+    #[msg("Remaining accounts must be pairs of a voter and its voter weight record")]
+    InvalidVoterWeightRecordPairs,
+    // 6058 / 0x17aa
+    #[msg("Partial clawback amount must be positive")]
+    InvalidClawbackAmount,
+    // 6059 / 0x17ab
+    #[msg("Deposits and voting mint totals of the voting mint are already tracked")]
+    VotingMintNotBackfilling,
 }
@@ -1,3 +1,4 @@
+use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -15,6 +16,8 @@ pub struct VestingInfo {
     pub rate: u64,
     /// Time of the next upcoming vesting
     pub next_timestamp: u64,
+    /// End of the cliff before which nothing vests, while it's pending
+    pub cliff_end_timestamp: Option<u64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug)]
@@ -41,3 +44,370 @@ pub struct DepositEntryInfo {
     /// Information about locking, if any
     pub locking: Option<LockingInfo>,
 }
+
+#[event]
+#[derive(Debug)]
+pub struct VotingMintInfo {
+    pub voting_mint_config_index: u8,
+    pub mint: Pubkey,
+    /// Native tokens deposited across all voters, if tracked
+    pub deposited: Option<u64>,
+    /// Native tokens in deposit entries with a lockup, if tracked
+    pub locked: Option<u64>,
+    /// Native tokens in deposit entries that allow clawback, if tracked
+    pub clawback_deposited: Option<u64>,
+}
+
+/// Lockup of a deposit entry, see Lockup
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockupState {
+    pub kind: LockupKind,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub cliff_periods: u32,
+}
+
+impl From<&Lockup> for LockupState {
+    fn from(lockup: &Lockup) -> Self {
+        Self {
+            kind: lockup.kind,
+            start_ts: lockup.start_ts(),
+            end_ts: lockup.end_ts(),
+            cliff_periods: lockup.cliff_periods(),
+        }
+    }
+}
+
+/// Rebuilds the lockup, useful off-chain for replaying events
+impl From<&LockupState> for Lockup {
+    fn from(state: &LockupState) -> Self {
+        Self {
+            start_ts: state.start_ts,
+            end_ts: state.end_ts,
+            kind: state.kind,
+            cliff_periods: state.cliff_periods,
+            reserved1: [0; 3],
+            reserved: [0; 8],
+        }
+    }
+}
+
+/// Amounts and lockup of a deposit entry, see DepositEntry
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositEntryState {
+    pub voting_mint_config_index: u8,
+    pub amount_deposited_native: u64,
+    pub amount_initially_locked_native: u64,
+    pub allow_clawback: bool,
+    pub lockup: LockupState,
+}
+
+impl From<&DepositEntry> for DepositEntryState {
+    fn from(deposit_entry: &DepositEntry) -> Self {
+        let lockup = deposit_entry.lockup;
+        Self {
+            voting_mint_config_index: deposit_entry.voting_mint_config_idx,
+            amount_deposited_native: deposit_entry.amount_deposited_native,
+            amount_initially_locked_native: deposit_entry.amount_initially_locked_native,
+            allow_clawback: deposit_entry.allow_clawback,
+            lockup: (&lockup).into(),
+        }
+    }
+}
+
+/// Rebuilds a used deposit entry, useful off-chain for replaying events
+impl From<&DepositEntryState> for DepositEntry {
+    fn from(state: &DepositEntryState) -> Self {
+        Self {
+            lockup: (&state.lockup).into(),
+            amount_deposited_native: state.amount_deposited_native,
+            amount_initially_locked_native: state.amount_initially_locked_native,
+            is_used: true,
+            allow_clawback: state.allow_clawback,
+            voting_mint_config_idx: state.voting_mint_config_index,
+            reserved: [0; 29],
+        }
+    }
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RegistrarCreatedEvent {
+    pub registrar: Pubkey,
+    pub realm: Pubkey,
+    pub realm_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VotingMintConfiguredEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub mint: Pubkey,
+    pub digit_shift: i8,
+    pub baseline_vote_weight_scaled_factor: u64,
+    pub max_extra_lockup_vote_weight_scaled_factor: u64,
+    pub lockup_saturation_secs: u64,
+    pub grant_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LockupCurveConfiguredEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub kind: LockupCurveKind,
+    pub lockup_saturation_secs: u64,
+    pub points: Vec<LockupCurvePoint>,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DepositLimitsConfiguredEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub min_deposit_native: u64,
+    pub max_voter_deposited_native: u64,
+    pub max_deposited_native: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LockupLimitsConfiguredEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub allowed_lockup_kinds: u16,
+    pub max_lockup_days: u16,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct MaxVoteWeightModeConfiguredEvent {
+    pub registrar: Pubkey,
+    pub mode: MaxVoteWeightMode,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VotingMintRemovedEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub mint: Pubkey,
+    /// Native tokens of the mint that are still deposited
+    pub deposited_native: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VotingMintBackfillFinishedEvent {
+    pub registrar: Pubkey,
+    pub voting_mint_config_index: u16,
+    pub mint: Pubkey,
+    /// Native tokens of the mint that are deposited
+    pub deposited_native: u64,
+    pub locked_native: u64,
+    pub clawback_deposited_native: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RealmAuthoritySyncedEvent {
+    pub registrar: Pubkey,
+    pub old_realm_authority: Pubkey,
+    pub new_realm_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VoterCreatedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub voter_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VoterResizedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_count: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VoterClosedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub voter_authority: Pubkey,
+    /// Receiver of the voter's and closed vaults' lamports
+    pub sol_destination: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DelegateSetEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub voter_authority: Pubkey,
+    pub old_delegate: Option<Pubkey>,
+    /// None if the voter authority votes again
+    pub new_delegate: Option<Pubkey>,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VoterTransferProposedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub voter_authority: Pubkey,
+    /// None if a pending transfer was cancelled
+    pub new_voter_authority: Option<Pubkey>,
+}
+
+/// The voter's deposit entries moved to a new voter account, the old voter
+/// and voter weight record were closed.
+#[event]
+#[derive(Debug)]
+pub struct VoterTransferredEvent {
+    pub registrar: Pubkey,
+    pub old_voter: Pubkey,
+    pub old_voter_authority: Pubkey,
+    pub new_voter: Pubkey,
+    pub new_voter_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DepositEntryCreatedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+    pub deposit_entry: DepositEntryState,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DepositEntryClosedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DepositEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+    /// Amount that arrived in the vault and was credited
+    pub amount: u64,
+    pub before: DepositEntryState,
+    pub after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct WithdrawEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub before: DepositEntryState,
+    pub after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct GrantEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub voter_authority: Pubkey,
+    pub grant_authority: Pubkey,
+    pub deposit_entry_index: u8,
+    /// Amount that arrived in the vault and was credited
+    pub amount: u64,
+    pub deposit_entry: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ClawbackEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub before: DepositEntryState,
+    pub after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ResetLockupEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub deposit_entry_index: u8,
+    pub before: DepositEntryState,
+    pub after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct InternalTransferEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    /// True for internal_transfer_locked, false for internal_transfer_unlocked
+    pub locked: bool,
+    pub amount: u64,
+    pub source_deposit_entry_index: u8,
+    pub source_before: DepositEntryState,
+    pub source_after: DepositEntryState,
+    pub target_deposit_entry_index: u8,
+    pub target_before: DepositEntryState,
+    pub target_after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct DepositEntrySplitEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub source_deposit_entry_index: u8,
+    pub source_before: DepositEntryState,
+    pub source_after: DepositEntryState,
+    /// The newly used deposit entry
+    pub target_deposit_entry_index: u8,
+    pub target: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
+
+/// The source deposit entries were emptied and freed.
+#[event]
+#[derive(Debug)]
+pub struct DepositEntriesMergedEvent {
+    pub registrar: Pubkey,
+    pub voter: Pubkey,
+    /// Sum of the tokens that moved from the source entries
+    pub amount: u64,
+    pub source_deposit_entry_indexes: Vec<u8>,
+    pub target_deposit_entry_index: u8,
+    pub target_before: DepositEntryState,
+    pub target_after: DepositEntryState,
+    /// Voter's total voting power afterwards
+    pub voter_weight: u64,
+}
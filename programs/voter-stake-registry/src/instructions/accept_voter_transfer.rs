@@ -0,0 +1,245 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use crate::token_interface;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as tx_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use std::mem::size_of;
+
+// Remaining accounts must be groups of four for each voting mint the voter has
+// deposit entries for: the voter's vault, the new voter's vault (created if
+// needed), the mint and its token program. Vaults are writable.
+#[derive(Accounts)]
+pub struct AcceptVoterTransfer<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter.load()?.voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = registrar,
+        constraint = voter.load()?.pending_voter_authority == new_voter_authority.key()
+            @ VsrError::InvalidNewVoterAuthority,
+        close = sol_destination,
+    )]
+    pub voter: AccountLoader<'info, Voter>,
+
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter-weight-record".as_ref(), voter.load()?.voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_weight_record_bump,
+        close = sol_destination,
+    )]
+    pub voter_weight_record: Box<Account<'info, VoterWeightRecord>>,
+
+    /// The token owner record of the voter's current voting owner, see
+    /// set_delegate. Votes cast with it must be relinquished first.
+    ///
+    /// CHECK: token_owner_record is validated in the instruction:
+    /// - the token owner record address for the current voting owner
+    /// - if it exists, owned by registrar.governance_program_id
+    pub token_owner_record: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), new_voter_authority.key().as_ref()],
+        bump,
+        payer = payer,
+        space = voter.as_ref().data_len(),
+    )]
+    pub new_voter: AccountLoader<'info, Voter>,
+
+    /// The wallet that was proposed with propose_voter_transfer
+    pub new_voter_authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [registrar.key().as_ref(), b"voter-weight-record".as_ref(), new_voter_authority.key().as_ref()],
+        bump,
+        payer = payer,
+        space = size_of::<VoterWeightRecord>(),
+    )]
+    pub new_voter_weight_record: Box<Account<'info, VoterWeightRecord>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Receives the lamports of the closed voter, voter weight record and vaults
+    ///
+    /// CHECK: Destination may be any address.
+    #[account(mut)]
+    pub sol_destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Address constraint is set
+    #[account(address = tx_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Moves the voter to the new voter authority proposed with
+/// propose_voter_transfer.
+///
+/// The new voter authority gets a new voter account with the same deposit
+/// entries at the same indexes, so all lockups stay intact, and a new voter
+/// weight record. The vault balances are moved to the new voter's vaults.
+/// The old voter, its voter weight record and vaults are closed. A delegate
+/// is not carried over.
+///
+/// The new voter authority must not have a voter yet. Other limitations:
+/// - The voting owner's token owner record must have no outstanding votes or
+///   proposals, like for withdrawing.
+/// - Deposit entries that allow clawback must be empty, since clawback
+///   proposals would target the old voter account.
+/// - Mints that charge transfer fees can't be moved.
+pub fn accept_voter_transfer<'key, 'accounts, 'remaining, 'info>(
+    ctx: Context<'key, 'accounts, 'remaining, 'info, AcceptVoterTransfer<'info>>,
+) -> Result<()> {
+    Voter::assert_not_cpi(&ctx.accounts.instructions, ctx.program_id)?;
+
+    let registrar = &ctx.accounts.registrar.load()?;
+    let registrar_key = ctx.accounts.registrar.key();
+    let voter_key = ctx.accounts.voter.key();
+    let new_voter_key = ctx.accounts.new_voter.key();
+    let new_voter_authority = ctx.accounts.new_voter_authority.key();
+
+    let (old_voter_authority, voter_bump, mint_indexes) = {
+        let voter = VoterRef::load(ctx.accounts.voter.as_ref())?;
+        voter.assert_voting_owner_can_withdraw(
+            &ctx.accounts.token_owner_record.to_account_info(),
+            registrar,
+        )?;
+
+        // Clawback proposals name the voter account. Moving clawback-enabled
+        // deposits to a new one would make them fail, like in
+        // internal_transfer_locked.
+        require!(
+            voter
+                .deposits()
+                .all(|d| !d.is_used || !d.allow_clawback || d.amount_deposited_native == 0),
+            VsrError::InvalidChangeToClawbackDepositEntry
+        );
+
+        let mut mint_indexes: Vec<usize> = voter
+            .deposits()
+            .filter(|d| d.is_used)
+            .map(|d| d.voting_mint_config_idx as usize)
+            .collect();
+        mint_indexes.sort_unstable();
+        mint_indexes.dedup();
+        (voter.voter_authority, voter.voter_bump, mint_indexes)
+    };
+
+    // Move the full vault balances over and close the old vaults.
+    let voter_info = ctx.accounts.voter.to_account_info();
+    let voter_seeds: &[&[u8]] = &[
+        registrar_key.as_ref(),
+        b"voter".as_ref(),
+        old_voter_authority.as_ref(),
+        &[voter_bump],
+    ];
+    require_eq!(
+        ctx.remaining_accounts.len() % 4,
+        0,
+        VsrError::VaultNotTransferred
+    );
+    let mut transferred_mint_indexes = vec![];
+    for accounts in ctx.remaining_accounts.chunks_exact(4) {
+        let (vault, new_vault, mint, token_program) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        token_interface::check_token_program(token_program)?;
+        transferred_mint_indexes.push(registrar.voting_mint_config_index(mint.key())?);
+
+        let amount =
+            token_interface::load_vault(vault, &voter_key, mint.key, token_program.key)?.amount;
+        token_interface::create_vault_if_needed(
+            &ctx.accounts.payer,
+            new_vault,
+            &ctx.accounts.new_voter.to_account_info(),
+            mint,
+            &ctx.accounts.system_program,
+            token_program,
+        )?;
+        let new_vault_before =
+            token_interface::load_vault(new_vault, &new_voter_key, mint.key, token_program.key)?
+                .amount;
+
+        token_interface::transfer_checked(
+            token_program,
+            vault,
+            mint,
+            new_vault,
+            &voter_info,
+            &[voter_seeds],
+            amount,
+        )?;
+        let received = token_interface::load_token_account(new_vault)?
+            .amount
+            .checked_sub(new_vault_before)
+            .unwrap();
+        require_eq!(received, amount, VsrError::VaultNotTransferred);
+
+        token_interface::close_account(
+            token_program,
+            vault,
+            &ctx.accounts.sol_destination,
+            &voter_info,
+            &[voter_seeds],
+        )?;
+    }
+    require!(
+        mint_indexes
+            .iter()
+            .all(|idx| transferred_mint_indexes.contains(idx)),
+        VsrError::VaultNotTransferred
+    );
+
+    // Copy the voter with all its deposit entries, then make it the new
+    // authority's.
+    {
+        let data = ctx.accounts.voter.as_ref().try_borrow_data()?;
+        let mut new_data = ctx.accounts.new_voter.as_ref().try_borrow_mut_data()?;
+        new_data[8..].copy_from_slice(&data[8..]);
+    }
+    {
+        let mut new_voter = VoterRefMut::load(ctx.accounts.new_voter.as_ref())?;
+        new_voter.voter_authority = new_voter_authority;
+        new_voter.voter_bump = *ctx.bumps.get("new_voter").unwrap();
+        new_voter.voter_weight_record_bump = *ctx.bumps.get("new_voter_weight_record").unwrap();
+        new_voter.delegate = Pubkey::default();
+        new_voter.delegate_revoked = false;
+        new_voter.pending_voter_authority = Pubkey::default();
+    }
+
+    let voter_weight_record = &mut ctx.accounts.new_voter_weight_record;
+    voter_weight_record.account_discriminator =
+        spl_governance_addin_api::voter_weight::VoterWeightRecord::ACCOUNT_DISCRIMINATOR;
+    voter_weight_record.realm = registrar.realm;
+    voter_weight_record.governing_token_mint = registrar.realm_governing_token_mint;
+    voter_weight_record.governing_token_owner = new_voter_authority;
+
+    // zero out the old voter account to prevent reinit attacks, including the
+    // extra deposit entries of voters that were grown with resize_voter
+    ctx.accounts.voter.as_ref().try_borrow_mut_data()?.fill(0);
+
+    msg!(
+        "Transferred voter from {} to {}",
+        old_voter_authority,
+        new_voter_authority
+    );
+    emit!(VoterTransferredEvent {
+        registrar: registrar_key,
+        old_voter: voter_key,
+        old_voter_authority,
+        new_voter: new_voter_key,
+        new_voter_authority,
+    });
+
+    Ok(())
+}
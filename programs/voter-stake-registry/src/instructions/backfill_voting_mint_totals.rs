@@ -0,0 +1,43 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+// Remaining accounts are the voters to count, they are writable.
+#[derive(Accounts)]
+pub struct BackfillVotingMintTotals<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+}
+
+/// Adds the deposits of voters to the totals of voting mints that were
+/// configured before deposits and voting mint totals were tracked.
+///
+/// For these mints, Registrar::voting_mints[].deposited_native and
+/// Registrar::voting_mint_totals only count voters that were passed here or
+/// changed their deposits since. Each voter is counted once per mint, passing
+/// it again has no effect. Once all voters with deposits are counted, the realm
+/// authority calls finish_voting_mint_backfill to start relying on the totals.
+///
+/// Anyone may call this. The number of voters is only limited by the
+/// transaction size and the compute budget.
+pub fn backfill_voting_mint_totals<'key, 'accounts, 'remaining, 'info>(
+    ctx: Context<'key, 'accounts, 'remaining, 'info, BackfillVotingMintTotals<'info>>,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let registrar_key = ctx.accounts.registrar.key();
+
+    for voter_info in ctx.remaining_accounts {
+        // Checks the owner and discriminator
+        AccountLoader::<Voter>::try_from(voter_info)?;
+        let voter = &mut VoterRefMut::load(voter_info)?;
+        require_keys_eq!(voter.registrar, registrar_key, ErrorCode::ConstraintHasOne);
+        registrar.count_voter(voter);
+    }
+
+    msg!("Counted {} voters", ctx.remaining_accounts.len());
+
+    Ok(())
+}
@@ -1,12 +1,16 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token;
-use anchor_spl::token::{Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct Clawback<'info> {
-    #[account(has_one = realm_authority)]
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
     pub realm_authority: Signer<'info>,
 
@@ -19,28 +23,35 @@ pub struct Clawback<'info> {
         has_one = registrar)]
     pub voter: AccountLoader<'info, Voter>,
 
-    #[account(
-        mut,
-        associated_token::authority = voter,
-        associated_token::mint = destination.mint,
-    )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in the instruction:
+    /// - the associated token account of voter for mint
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
+    /// CHECK: validated in the instruction:
+    /// - a token account for mint
     #[account(mut)]
-    pub destination: Box<Account<'info, TokenAccount>>,
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub mint: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
 }
 
 impl<'info> Clawback<'info> {
-    pub fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
-        let program = self.token_program.to_account_info();
-        let accounts = token::Transfer {
-            from: self.vault.to_account_info(),
-            to: self.destination.to_account_info(),
-            authority: self.voter.to_account_info(),
-        };
-        CpiContext::new(program, accounts)
+    pub fn transfer(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.vault,
+            &self.mint,
+            &self.destination,
+            &self.voter.to_account_info(),
+            signer_seeds,
+            amount,
+        )
     }
 }
 
@@ -53,10 +64,52 @@ impl<'info> Clawback<'info> {
 /// The instruction will always reclaim all locked tokens, while leaving tokens
 /// that have already vested in place.
 pub fn clawback(ctx: Context<Clawback>, deposit_entry_index: u8) -> Result<()> {
-    let locked_amount = {
+    clawback_locked(ctx, deposit_entry_index, None)
+}
+
+/// Claws back some of the locked tokens from a deposit entry.
+///
+/// `deposit_entry_index`: The index of the deposit entry to claw back tokens on.
+/// `amount`: The amount of locked tokens to claw back, positive and at most
+/// all of them.
+///
+/// The deposit entry must have been created with `allow_clawback=true`.
+///
+/// Unlike `clawback`, the lockup stays in place: the remaining locked tokens
+/// keep vesting on the same schedule, in proportionally smaller amounts per
+/// period. Tokens that have already vested are left in place. If no locked
+/// tokens remain, the lockup ends like with `clawback`.
+pub fn partial_clawback(
+    ctx: Context<Clawback>,
+    deposit_entry_index: u8,
+    amount: u64,
+) -> Result<()> {
+    clawback_locked(ctx, deposit_entry_index, Some(amount))
+}
+
+/// Claws back `amount` locked tokens, or all of them and ends the lockup.
+fn clawback_locked(
+    ctx: Context<Clawback>,
+    deposit_entry_index: u8,
+    amount: Option<u64>,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.mint.key();
+    token_interface::check_token_program(&ctx.accounts.token_program)?;
+    token_interface::load_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.key(),
+        &mint,
+        &token_program,
+    )?;
+    token_interface::load_token_account_for_mint(&ctx.accounts.destination, &mint, &token_program)?;
+
+    let clawback_amount = {
         // Load the accounts.
-        let registrar = &ctx.accounts.registrar.load()?;
-        let voter = &mut ctx.accounts.voter.load_mut()?;
+        let registrar = &mut ctx.accounts.registrar.load_mut()?;
+        let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+        registrar.count_voter(voter);
+        let totals_before = voter.voting_mint_totals();
 
         // Note: don't assert if token_owner_record is engaged in active proposals
         // since this way a grantee could block clawback
@@ -67,34 +120,70 @@ pub fn clawback(ctx: Context<Clawback>, deposit_entry_index: u8) -> Result<()> {
             deposit_entry.allow_clawback,
             VsrError::ClawbackNotAllowedOnDeposit
         );
+        let mint_idx = registrar.voting_mint_config_index(mint)?;
+        require_eq!(
+            mint_idx,
+            deposit_entry.voting_mint_config_idx as usize,
+            VsrError::InvalidMint
+        );
 
         let curr_ts = registrar.clock_unix_timestamp();
-        let locked_amount = deposit_entry.amount_locked(curr_ts);
+        let before = DepositEntryState::from(&*deposit_entry);
+        let clawback_amount = match amount {
+            None => deposit_entry.amount_locked(curr_ts),
+            Some(amount) => {
+                require_gt!(amount, 0, VsrError::InvalidClawbackAmount);
+                // Afterwards amount_initially_locked_native is exactly the locked
+                // amount and the lockup only covers the remaining periods, so
+                // reducing it keeps the schedule for the rest.
+                deposit_entry.resolve_vesting(curr_ts)?;
+                require_gte!(
+                    deposit_entry.amount_initially_locked_native,
+                    amount,
+                    VsrError::InsufficientLockedTokens
+                );
+                amount
+            }
+        };
 
         // Update deposit book keeping.
         require_gte!(
             deposit_entry.amount_deposited_native,
-            locked_amount,
+            clawback_amount,
             VsrError::InternalProgramError
         );
-        deposit_entry.amount_deposited_native -= locked_amount;
+        deposit_entry.amount_deposited_native -= clawback_amount;
+        if amount.is_none() || deposit_entry.amount_initially_locked_native == clawback_amount {
+            // Now that all locked funds are withdrawn, end the lockup
+            deposit_entry.amount_initially_locked_native = 0;
+            deposit_entry.lockup = Lockup::new_from_periods(LockupKind::None, curr_ts, curr_ts, 0)?;
+            deposit_entry.allow_clawback = false;
+        } else {
+            deposit_entry.amount_initially_locked_native -= clawback_amount;
+        }
+        registrar.voting_mints[mint_idx].sub_deposited(clawback_amount)?;
 
-        // Now that all locked funds are withdrawn, end the lockup
-        let curr_ts = registrar.clock_unix_timestamp();
-        deposit_entry.amount_initially_locked_native = 0;
-        deposit_entry.lockup = Lockup::new_from_periods(LockupKind::None, curr_ts, curr_ts, 0)?;
-        deposit_entry.allow_clawback = false;
-        locked_amount
+        let after = DepositEntryState::from(&*deposit_entry);
+        registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+        emit!(ClawbackEvent {
+            registrar: ctx.accounts.registrar.key(),
+            voter: ctx.accounts.voter.key(),
+            deposit_entry_index,
+            destination: ctx.accounts.destination.key(),
+            amount: clawback_amount,
+            before,
+            after,
+            voter_weight: voter.weight(registrar)?,
+        });
+        clawback_amount
     };
 
     {
         // Transfer the tokens to withdraw.
         let voter = &mut ctx.accounts.voter.load()?;
         let voter_seeds = voter_seeds!(voter);
-        token::transfer(
-            ctx.accounts.transfer_ctx().with_signer(&[voter_seeds]),
-            locked_amount,
-        )?;
+        ctx.accounts.transfer(&[voter_seeds], clawback_amount)?;
     }
 
     Ok(())
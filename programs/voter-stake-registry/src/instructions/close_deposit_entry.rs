@@ -1,4 +1,5 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
@@ -22,7 +23,7 @@ pub struct CloseDepositEntry<'info> {
 /// If the deposit entry has `allow_clawback` set, it can only be closed once
 /// the lockup period has expired.
 pub fn close_deposit_entry(ctx: Context<CloseDepositEntry>, deposit_entry_index: u8) -> Result<()> {
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
     let d = voter.active_deposit_mut(deposit_entry_index)?;
     require_eq!(d.amount_deposited_native, 0, VsrError::VotingTokenNonZero);
 
@@ -39,5 +40,11 @@ pub fn close_deposit_entry(ctx: Context<CloseDepositEntry>, deposit_entry_index:
     *d = DepositEntry::default();
     d.is_used = false;
 
+    emit!(DepositEntryClosedEvent {
+        registrar: voter.registrar,
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index,
+    });
+
     Ok(())
 }
@@ -1,16 +1,16 @@
-use std::ops::DerefMut;
-
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
-use bytemuck::bytes_of_mut;
 
-// Remaining accounts must be all the token token accounts owned by voter, he wants to close,
-// they should be writable so that they can be closed and sol required for rent
-// can then be sent back to the sol_destination
+// Remaining accounts must be pairs of a token account owned by voter that should
+// be closed and the token program that owns it. The token accounts should be
+// writable so that they can be closed and sol required for rent can then be sent
+// back to the sol_destination
 #[derive(Accounts)]
 pub struct CloseVoter<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -29,8 +29,6 @@ pub struct CloseVoter<'info> {
     #[account(mut)]
     /// CHECK: Destination may be any address.
     pub sol_destination: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
 }
 
 /// Closes the voter account (Optionally, also token vaults, as part of remaining_accounts),
@@ -40,15 +38,33 @@ pub fn close_voter<'key, 'accounts, 'remaining, 'info>(
     ctx: Context<'key, 'accounts, 'remaining, 'info, CloseVoter<'info>>,
 ) -> Result<()> {
     {
-        let voter = ctx.accounts.voter.load()?;
-        let amount = voter.deposits.iter().fold(0u64, |sum, d| {
+        let voter = VoterRef::load(ctx.accounts.voter.as_ref())?;
+        emit!(VoterClosedEvent {
+            registrar: voter.registrar,
+            voter: ctx.accounts.voter.key(),
+            voter_authority: voter.voter_authority,
+            sol_destination: ctx.accounts.sol_destination.key(),
+        });
+
+        let amount = voter.deposits().fold(0u64, |sum, d| {
             sum.checked_add(d.amount_deposited_native).unwrap()
         });
         require_eq!(amount, 0, VsrError::VotingTokenNonZero);
 
+        require!(
+            ctx.remaining_accounts.len().is_multiple_of(2),
+            VsrError::InvalidTokenProgram
+        );
         let voter_seeds = voter_seeds!(voter);
-        for account in &mut ctx.remaining_accounts.iter() {
-            let token = Account::<TokenAccount>::try_from(&account.clone()).unwrap();
+        for accounts in ctx.remaining_accounts.chunks_exact(2) {
+            let (account, token_program) = (&accounts[0], &accounts[1]);
+            token_interface::check_token_program(token_program)?;
+            require_keys_eq!(
+                *account.owner,
+                token_program.key(),
+                VsrError::InvalidTokenProgram
+            );
+            let token = token_interface::load_token_account(account)?;
             require_keys_eq!(
                 token.owner,
                 ctx.accounts.voter.key(),
@@ -56,30 +72,19 @@ pub fn close_voter<'key, 'accounts, 'remaining, 'info>(
             );
             require_eq!(token.amount, 0, VsrError::VaultTokenNonZero);
 
-            let cpi_accounts = CloseAccount {
-                account: account.to_account_info(),
-                destination: ctx.accounts.sol_destination.to_account_info(),
-                authority: ctx.accounts.voter.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            token::close_account(CpiContext::new_with_signer(
-                cpi_program,
-                cpi_accounts,
+            token_interface::close_account(
+                token_program,
+                account,
+                &ctx.accounts.sol_destination,
+                &ctx.accounts.voter.to_account_info(),
                 &[voter_seeds],
-            ))?;
-
-            account.exit(ctx.program_id)?;
+            )?;
         }
     }
 
-    // zero out voter account to prevent reinit attacks
-    // appease rust borrow checker
-    {
-        let mut voter = ctx.accounts.voter.load_mut()?;
-        let voter_dereffed = voter.deref_mut();
-        let voter_bytes = bytes_of_mut(voter_dereffed);
-        voter_bytes.fill(0);
-    }
+    // zero out voter account to prevent reinit attacks, including the
+    // extra deposit entries of voters that were grown with resize_voter
+    ctx.accounts.voter.as_ref().try_borrow_mut_data()?.fill(0);
 
     Ok(())
 }
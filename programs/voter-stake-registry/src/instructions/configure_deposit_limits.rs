@@ -0,0 +1,65 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureDepositLimits<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Limits deposits of the voting mint at index `idx`. Each limit is zero for
+/// no limit.
+///
+/// * `min_deposit_native`: smallest number of native tokens a deposit or grant
+///   may add, which keeps dust entries out
+/// * `max_voter_deposited_native`: maximum native tokens of the mint a voter may
+///   have deposited across their deposit entries
+/// * `max_deposited_native`: maximum native tokens of the mint that may be
+///   deposited across all voters, for example to cap the vote weight a new mint
+///   can bring in. Can't be set for mints configured before deposits were
+///   tracked.
+///
+/// Lowering a cap below what is already deposited only blocks new deposits.
+/// The limits stay when configure_voting_mint reconfigures the mint.
+pub fn configure_deposit_limits(
+    ctx: Context<ConfigureDepositLimits>,
+    idx: u16,
+    min_deposit_native: u64,
+    max_voter_deposited_native: u64,
+    max_deposited_native: u64,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let idx = idx as usize;
+    require_gt!(
+        registrar.voting_mints.len(),
+        idx,
+        VsrError::OutOfBoundsVotingMintConfigIndex
+    );
+
+    let mint_config = &mut registrar.voting_mints[idx];
+    require!(mint_config.in_use(), VsrError::VotingMintNotFound);
+    require!(
+        max_deposited_native == 0 || mint_config.deposited_native_tracked,
+        VsrError::VotingMintDepositsNotTracked
+    );
+    mint_config.min_deposit_native = min_deposit_native;
+    mint_config.max_voter_deposited_native = max_voter_deposited_native;
+    registrar.max_deposited_native[idx] = max_deposited_native;
+
+    emit!(DepositLimitsConfiguredEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        min_deposit_native,
+        max_voter_deposited_native,
+        max_deposited_native,
+    });
+
+    Ok(())
+}
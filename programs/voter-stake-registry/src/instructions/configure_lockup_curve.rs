@@ -0,0 +1,70 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureLockupCurve<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Changes how the extra lockup vote weight of the voting mint at index `idx`
+/// grows with the remaining lockup time.
+///
+/// * `kind`: shape of the curve, see LockupCurveKind
+/// * `points`: breakpoints or tiers of the curve, ordered by lockup time
+///
+/// Linear curves take no points and keep the mint's lockup_saturation_secs.
+/// Other curves take up to MAX_LOCKUP_CURVE_POINTS points with strictly increasing
+/// lockup times and non-decreasing factors. The last point must have the full
+/// factor of 1e9, and its lockup time becomes the mint's lockup_saturation_secs.
+///
+/// Example: Tiers giving 1/4, 1/2 and all of the max extra lockup vote weight for
+/// lockups of at least 1, 2 and 4 years:
+/// ```
+/// kind = Step
+/// points = [(1y, 0.25e9), (2y, 0.5e9), (4y, 1e9)]
+/// ```
+///
+/// The max vote weight does not change, because no curve grants more than
+/// the max extra lockup vote weight. Voter weight records are only updated
+/// when voters refresh them.
+pub fn configure_lockup_curve(
+    ctx: Context<ConfigureLockupCurve>,
+    idx: u16,
+    kind: LockupCurveKind,
+    points: Vec<LockupCurvePoint>,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let idx = idx as usize;
+    require_gt!(
+        registrar.voting_mints.len(),
+        idx,
+        VsrError::OutOfBoundsVotingMintConfigIndex
+    );
+
+    let mint_config = &mut registrar.voting_mints[idx];
+    require!(mint_config.in_use(), VsrError::VotingMintNotFound);
+
+    let lockup_saturation_secs = points
+        .last()
+        .map_or(mint_config.lockup_saturation_secs, |p| p.lockup_secs as u64);
+    let curve = LockupCurve::new(kind, lockup_saturation_secs, &points)?;
+    mint_config.set_lockup_curve(&curve);
+
+    emit!(LockupCurveConfiguredEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        kind,
+        lockup_saturation_secs,
+        points,
+    });
+
+    Ok(())
+}
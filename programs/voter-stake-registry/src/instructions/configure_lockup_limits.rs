@@ -0,0 +1,57 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureLockupLimits<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Restricts the lockups deposits of the voting mint at index `idx` may use.
+/// Each restriction is zero for no restriction.
+///
+/// * `allowed_lockup_kinds`: bitmask of the lockup kinds deposit entries for the
+///   mint may use, see LockupKind::mask
+/// * `max_lockup_days`: maximum time in days that tokens may be locked up for,
+///   counted from when they get locked
+///
+/// They are checked whenever tokens get a new lockup: by create_deposit_entry,
+/// grant, reset_lockup and when moving locked tokens between deposit entries.
+/// Existing lockups are not affected.
+///
+/// The restrictions stay when configure_voting_mint reconfigures the mint.
+pub fn configure_lockup_limits(
+    ctx: Context<ConfigureLockupLimits>,
+    idx: u16,
+    allowed_lockup_kinds: u16,
+    max_lockup_days: u16,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let idx = idx as usize;
+    require_gt!(
+        registrar.voting_mints.len(),
+        idx,
+        VsrError::OutOfBoundsVotingMintConfigIndex
+    );
+
+    let mint_config = &mut registrar.voting_mints[idx];
+    require!(mint_config.in_use(), VsrError::VotingMintNotFound);
+    mint_config.max_lockup_days = max_lockup_days;
+    registrar.allowed_lockup_kinds[idx] = allowed_lockup_kinds;
+
+    emit!(LockupLimitsConfiguredEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        allowed_lockup_kinds,
+        max_lockup_days,
+    });
+
+    Ok(())
+}
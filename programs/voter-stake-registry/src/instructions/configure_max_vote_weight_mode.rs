@@ -0,0 +1,47 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureMaxVoteWeightMode<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Chooses how update_max_vote_weight computes the max vote weight that
+/// spl-governance uses for quorum, see MaxVoteWeightMode.
+///
+/// The MintSupply default assumes the whole supply of all voting mints could
+/// be locked up to the saturation. Deposited only counts the deposited tokens,
+/// which is still an upper bound for the total voter weight, but one that
+/// quorum can realistically reach.
+///
+/// Deposited needs deposits to be tracked for all voting mints that grant
+/// vote weight, which is the case for all mints configured after the
+/// tracking was introduced. Older mints are tracked once their deposits were
+/// counted with backfill_voting_mint_totals and finish_voting_mint_backfill.
+pub fn configure_max_vote_weight_mode(
+    ctx: Context<ConfigureMaxVoteWeightMode>,
+    mode: MaxVoteWeightMode,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    registrar.max_vote_weight_mode = mode;
+
+    // Check that the max vote weight can be computed in the new mode
+    if mode == MaxVoteWeightMode::Deposited {
+        registrar.max_deposited_vote_weight()?;
+    }
+
+    emit!(MaxVoteWeightModeConfiguredEvent {
+        registrar: ctx.accounts.registrar.key(),
+        mode,
+    });
+
+    Ok(())
+}
@@ -1,18 +1,26 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
 
 // Remaining accounts must be all the token mints that have registered
 // as voting mints, including the newly registered one.
 #[derive(Accounts)]
 pub struct ConfigureVotingMint<'info> {
-    #[account(mut, has_one = realm_authority)]
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
     pub realm_authority: Signer<'info>,
 
     /// Tokens of this mint will produce vote weight
-    pub mint: Account<'info, Mint>,
+    ///
+    /// CHECK: must be a mint of the spl-token or token-2022 program,
+    /// validated in the instruction
+    pub mint: UncheckedAccount<'info>,
     // This instruction expects that all voting mint addresses, including a
     // newly registered one, are passed in ctx.remainingAccounts.
 }
@@ -29,7 +37,19 @@ pub struct ConfigureVotingMint<'info> {
 ///   bonus is given to locked up deposits
 ///
 /// This instruction can be called several times for the same mint and index to
-/// change the voting mint configuration.
+/// change the voting mint configuration. Doing that for a mint that was removed
+/// with remove_voting_mint makes it a regular voting mint again.
+///
+/// The index of a removed mint can't be used for a different mint: voters'
+/// deposit entries may still refer to it, even once they are empty.
+///
+/// Reconfiguring keeps the mint's deposit limits and lockup restrictions, see
+/// configure_deposit_limits and configure_lockup_limits. A new mint starts out
+/// without them.
+///
+/// Reconfiguring keeps the mint's lockup curve. If it isn't linear,
+/// `lockup_saturation_secs` must stay the lockup time of the curve's last point;
+/// use configure_lockup_curve to change it.
 ///
 /// The vote weight for `amount` of native tokens will be
 /// ```
@@ -40,7 +60,8 @@ pub struct ConfigureVotingMint<'info> {
 /// ```
 /// where lockup_duration_factor is a value between 0 and 1, depending on how long
 /// the amount is locked up. It is 1 when the lockup duration is greater or equal
-/// lockup_saturation_secs.
+/// lockup_saturation_secs. By default it grows linearly with the lockup duration,
+/// see configure_lockup_curve for alternatives.
 ///
 /// Warning: Choose values that ensure that the vote weight will not overflow the
 /// u64 limit! There is a check based on the supply of all configured mints, but
@@ -69,6 +90,7 @@ pub struct ConfigureVotingMint<'info> {
 ///    * B with digit_shift=0, baseline_vote_weight_scaled_factor=1e9, max_extra_lockup_vote_weight_scaled_factor=1e9
 /// to not lose precision on B tokens.
 ///
+#[allow(clippy::too_many_arguments)]
 pub fn configure_voting_mint(
     ctx: Context<ConfigureVotingMint>,
     idx: u16,
@@ -83,6 +105,7 @@ pub fn configure_voting_mint(
         0,
         VsrError::LockupSaturationMustBePositive
     );
+    token_interface::load_mint(&ctx.accounts.mint)?;
     let registrar = &mut ctx.accounts.registrar.load_mut()?;
     let mint = ctx.accounts.mint.key();
     let idx = idx as usize;
@@ -94,18 +117,43 @@ pub fn configure_voting_mint(
 
     // Either it's reconfiguring an existing mint with the correct index,
     // or configuring a new mint on an unused index.
-    match registrar.voting_mint_config_index(mint) {
-        Ok(existing_idx) => require_eq!(
-            existing_idx,
-            idx,
-            VsrError::VotingMintConfiguredWithDifferentIndex
-        ),
-        Err(_) => require!(
-            !registrar.voting_mints[idx].in_use(),
-            VsrError::VotingMintConfigIndexAlreadyInUse
-        ),
+    let (kept, new_mint) = match registrar.voting_mint_config_index(mint) {
+        Ok(existing_idx) => {
+            require_eq!(
+                existing_idx,
+                idx,
+                VsrError::VotingMintConfiguredWithDifferentIndex
+            );
+            (registrar.voting_mints[idx], false)
+        }
+        Err(_) => {
+            let existing = &registrar.voting_mints[idx];
+            if existing.deprecated {
+                require!(
+                    existing.removed_without_deposits(),
+                    VsrError::VotingMintHasDeposits
+                );
+            } else {
+                require!(
+                    !existing.in_use(),
+                    VsrError::VotingMintConfigIndexAlreadyInUse
+                );
+            }
+            let new_config = VotingMintConfig {
+                deposited_native_tracked: true,
+                ..VotingMintConfig::default()
+            };
+            (new_config, true)
+        }
     };
+    // Check that the kept lockup curve still matches the saturation
+    let lockup_curve = LockupCurve::new(
+        kept.lockup_curve_kind,
+        lockup_saturation_secs,
+        kept.lockup_curve().points(),
+    )?;
 
+    // Deposit tracking, limits and lockup restrictions stay when reconfiguring
     registrar.voting_mints[idx] = VotingMintConfig {
         mint,
         digit_shift,
@@ -113,12 +161,35 @@ pub fn configure_voting_mint(
         max_extra_lockup_vote_weight_scaled_factor,
         lockup_saturation_secs,
         grant_authority: grant_authority.unwrap_or_default(),
-        reserved1: [0; 7],
-        reserved2: [0; 7],
+        deprecated: false,
+        ..kept
     };
+    registrar.voting_mints[idx].set_lockup_curve(&lockup_curve);
+    if new_mint {
+        registrar.max_deposited_native[idx] = 0;
+        registrar.allowed_lockup_kinds[idx] = 0;
+        registrar.voting_mint_totals[idx] = VotingMintTotals::default();
+        registrar.voting_mint_totals_tracked[idx] = true;
+    }
 
     // Check for overflow in vote weight
     registrar.max_vote_weight(ctx.remaining_accounts)?;
+    // Reconfiguring a removed mint whose deposits aren't tracked would break
+    // update_max_vote_weight
+    if registrar.max_vote_weight_mode == MaxVoteWeightMode::Deposited {
+        registrar.max_deposited_vote_weight()?;
+    }
+
+    emit!(VotingMintConfiguredEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        mint,
+        digit_shift,
+        baseline_vote_weight_scaled_factor,
+        max_extra_lockup_vote_weight_scaled_factor,
+        lockup_saturation_secs,
+        grant_authority: grant_authority.unwrap_or_default(),
+    });
 
     Ok(())
 }
@@ -1,12 +1,14 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::convert::TryFrom;
 
 #[derive(Accounts)]
 pub struct CreateDepositEntry<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -19,23 +21,24 @@ pub struct CreateDepositEntry<'info> {
         has_one = voter_authority)]
     pub voter: AccountLoader<'info, Voter>,
 
-    #[account(
-        init_if_needed,
-        associated_token::authority = voter,
-        associated_token::mint = deposit_mint,
-        payer = payer
-    )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// The associated token account of voter for deposit_mint, created if needed.
+    ///
+    /// CHECK: validated in the instruction
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
     pub voter_authority: Signer<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    pub deposit_mint: Box<Account<'info, Mint>>,
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub deposit_mint: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -64,20 +67,69 @@ pub fn create_deposit_entry(
     periods: u32,
     allow_clawback: bool,
 ) -> Result<()> {
+    create_deposit_entry_with_cliff(
+        ctx,
+        deposit_entry_index,
+        kind,
+        start_ts,
+        periods,
+        0,
+        allow_clawback,
+    )
+}
+
+/// Creates a new deposit entry whose lockup starts with a cliff.
+///
+/// Like `create_deposit_entry`, with:
+/// - `cliff_periods`: For `CliffMonthly`, the number of periods before `periods`
+///   during which nothing vests. Must be 0 for other kinds.
+///
+/// The lockup will end after `start + (cliff_periods + periods) * period_secs()`.
+pub fn create_deposit_entry_with_cliff(
+    ctx: Context<CreateDepositEntry>,
+    deposit_entry_index: u8,
+    kind: LockupKind,
+    start_ts: Option<u64>,
+    periods: u32,
+    cliff_periods: u32,
+    allow_clawback: bool,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.deposit_mint.key();
+    token_interface::check_token_program(&ctx.accounts.token_program)?;
+    token_interface::create_vault_if_needed(
+        &ctx.accounts.payer,
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.to_account_info(),
+        &ctx.accounts.deposit_mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    token_interface::load_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.key(),
+        &mint,
+        &token_program,
+    )?;
+
     // Load accounts.
     let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
 
     // Get the exchange rate entry associated with this deposit.
-    let mint_idx = registrar.voting_mint_config_index(ctx.accounts.deposit_mint.key())?;
+    let mint_idx = registrar.voting_mint_config_index(mint)?;
+    require!(
+        !registrar.voting_mints[mint_idx].deprecated,
+        VsrError::VotingMintDeprecated
+    );
 
     // Get and set up the deposit entry.
     require_gt!(
-        voter.deposits.len(),
+        voter.deposit_entry_count(),
         deposit_entry_index as usize,
         VsrError::OutOfBoundsDepositEntryIndex
     );
-    let d_entry = &mut voter.deposits[deposit_entry_index as usize];
+    let d_entry = voter.deposit_mut(deposit_entry_index as usize).unwrap();
     require!(!d_entry.is_used, VsrError::UnusedDepositEntryIndex);
 
     let curr_ts = registrar.clock_unix_timestamp();
@@ -93,7 +145,15 @@ pub fn create_deposit_entry(
     d_entry.amount_deposited_native = 0;
     d_entry.amount_initially_locked_native = 0;
     d_entry.allow_clawback = allow_clawback;
-    d_entry.lockup = Lockup::new_from_periods(kind, curr_ts, start_ts, periods)?;
+    d_entry.lockup = Lockup::new_with_cliff(kind, curr_ts, start_ts, cliff_periods, periods)?;
+    registrar.check_lockup(mint_idx, &d_entry.lockup, curr_ts)?;
+
+    emit!(DepositEntryCreatedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index,
+        deposit_entry: DepositEntryState::from(&*d_entry),
+    });
 
     Ok(())
 }
@@ -0,0 +1,45 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct CreateMaxVoterWeightRecord<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    /// The max voter weight record is the account that will be shown to spl-governance
+    /// to provide the maximum vote weight of the registrar. See update_max_vote_weight.
+    #[account(
+        init,
+        seeds = [registrar.key().as_ref(), b"max-voter-weight-record".as_ref()],
+        bump,
+        payer = payer,
+        space = size_of::<MaxVoterWeightRecord>(),
+    )]
+    pub max_voter_weight_record: Box<Account<'info, MaxVoterWeightRecord>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the max voter weight record for a registrar. There can only be
+/// a single one per registrar.
+///
+/// The record must be registered as the max voter weight addin of the realm
+/// to be used by spl-governance. It is kept up to date with update_max_vote_weight.
+pub fn create_max_voter_weight_record(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar.load()?;
+
+    let record = &mut ctx.accounts.max_voter_weight_record;
+    record.account_discriminator =
+        spl_governance_addin_api::max_voter_weight::MaxVoterWeightRecord::ACCOUNT_DISCRIMINATOR;
+    record.realm = registrar.realm;
+    record.governing_token_mint = registrar.realm_governing_token_mint;
+    record.max_voter_weight = 0;
+    record.max_voter_weight_expiry = Some(0);
+
+    Ok(())
+}
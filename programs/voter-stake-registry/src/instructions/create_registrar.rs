@@ -1,4 +1,5 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
@@ -70,5 +71,11 @@ pub fn create_registrar(ctx: Context<CreateRegistrar>, registrar_bump: u8) -> Re
         VsrError::InvalidRealmAuthority
     );
 
+    emit!(RegistrarCreatedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        realm: registrar.realm,
+        realm_authority: registrar.realm_authority,
+    });
+
     Ok(())
 }
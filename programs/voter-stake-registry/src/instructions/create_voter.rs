@@ -1,4 +1,5 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions as tx_instructions;
@@ -6,6 +7,7 @@ use std::mem::size_of;
 
 #[derive(Accounts)]
 pub struct CreateVoter<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
 
     #[account(
@@ -54,19 +56,7 @@ pub fn create_voter(
     voter_bump: u8,
     voter_weight_record_bump: u8,
 ) -> Result<()> {
-    // Forbid creating voter accounts from CPI. The goal is to make automation
-    // impossible that weakens some of the limitations intentionally imposed on
-    // locked tokens.
-    {
-        let ixns = ctx.accounts.instructions.to_account_info();
-        let current_index = tx_instructions::load_current_index_checked(&ixns)? as usize;
-        let current_ixn = tx_instructions::load_instruction_at_checked(current_index, &ixns)?;
-        require_keys_eq!(
-            current_ixn.program_id,
-            *ctx.program_id,
-            VsrError::ForbiddenCpi
-        );
-    }
+    Voter::assert_not_cpi(&ctx.accounts.instructions, ctx.program_id)?;
 
     require_eq!(voter_bump, *ctx.bumps.get("voter").unwrap());
     require_eq!(
@@ -91,5 +81,11 @@ pub fn create_voter(
     voter_weight_record.governing_token_mint = registrar.realm_governing_token_mint;
     voter_weight_record.governing_token_owner = voter_authority;
 
+    emit!(VoterCreatedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        voter_authority,
+    });
+
     Ok(())
 }
@@ -1,10 +1,15 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -16,32 +21,35 @@ pub struct Deposit<'info> {
         has_one = registrar)]
     pub voter: AccountLoader<'info, Voter>,
 
-    #[account(
-        mut,
-        associated_token::authority = voter,
-        associated_token::mint = deposit_token.mint,
-    )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in the instruction:
+    /// - the associated token account of voter for deposit_mint
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        constraint = deposit_token.owner == deposit_authority.key(),
-    )]
-    pub deposit_token: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in the instruction:
+    /// - a token account for deposit_mint owned by deposit_authority
+    #[account(mut)]
+    pub deposit_token: UncheckedAccount<'info>,
     pub deposit_authority: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub deposit_mint: UncheckedAccount<'info>,
+
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
 }
 
 impl<'info> Deposit<'info> {
-    pub fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
-        let program = self.token_program.to_account_info();
-        let accounts = token::Transfer {
-            from: self.deposit_token.to_account_info(),
-            to: self.vault.to_account_info(),
-            authority: self.deposit_authority.to_account_info(),
-        };
-        CpiContext::new(program, accounts)
+    pub fn transfer(&self, amount: u64) -> Result<u64> {
+        token_interface::transfer_to_vault(
+            &self.token_program,
+            &self.deposit_token,
+            &self.deposit_mint,
+            &self.vault,
+            &self.deposit_authority,
+            amount,
+        )
     }
 }
 
@@ -54,6 +62,8 @@ impl<'info> Deposit<'info> {
 /// `deposit_entry_index`: Index of the deposit entry.
 /// `amount`: Number of native tokens to transfer.
 ///
+/// The deposit limits of the voting mint apply, see configure_deposit_limits.
+///
 /// Note that adding tokens to a deposit entry with vesting, where some vesting
 /// periods are already in the past is supported. What happens is that the tokens
 /// get distributed over vesting points in the future.
@@ -66,18 +76,44 @@ pub fn deposit(ctx: Context<Deposit>, deposit_entry_index: u8, amount: u64) -> R
         return Ok(());
     }
 
-    let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
+
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.deposit_mint.key();
+    token_interface::check_token_program(&ctx.accounts.token_program)?;
+    let deposit_token = token_interface::load_token_account_for_mint(
+        &ctx.accounts.deposit_token,
+        &mint,
+        &token_program,
+    )?;
+    require_keys_eq!(
+        deposit_token.owner,
+        ctx.accounts.deposit_authority.key(),
+        VsrError::InvalidAuthority
+    );
+    token_interface::load_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.key(),
+        &mint,
+        &token_program,
+    )?;
 
     let d_entry = voter.active_deposit_mut(deposit_entry_index)?;
 
     // Get the exchange rate entry associated with this deposit.
-    let mint_idx = registrar.voting_mint_config_index(ctx.accounts.deposit_token.mint)?;
+    let mint_idx = registrar.voting_mint_config_index(mint)?;
     require_eq!(
         mint_idx,
         d_entry.voting_mint_config_idx as usize,
         VsrError::InvalidMint
     );
+    require!(
+        !registrar.voting_mints[mint_idx].deprecated,
+        VsrError::VotingMintDeprecated
+    );
 
     // Adding funds to a lockup that is already in progress can be complicated
     // for linear vesting schedules because all added funds should be paid out
@@ -88,15 +124,17 @@ pub fn deposit(ctx: Context<Deposit>, deposit_entry_index: u8, amount: u64) -> R
     // - add the new funds to the locked up token count, so they will vest over
     //   the remaining periods.
     let curr_ts = registrar.clock_unix_timestamp();
+    let before = DepositEntryState::from(&*d_entry);
     d_entry.resolve_vesting(curr_ts)?;
 
     // Deposit tokens into the vault and increase the lockup amount too.
-    token::transfer(ctx.accounts.transfer_ctx(), amount)?;
+    let amount = ctx.accounts.transfer(amount)?;
     d_entry.amount_deposited_native = d_entry.amount_deposited_native.checked_add(amount).unwrap();
     d_entry.amount_initially_locked_native = d_entry
         .amount_initially_locked_native
         .checked_add(amount)
         .unwrap();
+    registrar.voting_mints[mint_idx].add_deposited(amount);
 
     msg!(
         "Deposited amount {} at deposit index {} with lockup kind {:?} and {} seconds left",
@@ -106,5 +144,19 @@ pub fn deposit(ctx: Context<Deposit>, deposit_entry_index: u8, amount: u64) -> R
         d_entry.lockup.seconds_left(curr_ts),
     );
 
+    let after = DepositEntryState::from(&*d_entry);
+    registrar.check_deposit_limits(mint_idx, amount, voter.deposited_native(mint_idx))?;
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(DepositEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index,
+        amount,
+        before,
+        after,
+        voter_weight: voter.weight(registrar)?,
+    });
+
     Ok(())
 }
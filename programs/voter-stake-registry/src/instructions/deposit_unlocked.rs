@@ -0,0 +1,191 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use crate::token_interface;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+
+#[derive(Accounts)]
+pub struct DepositUnlocked<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter.load()?.voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = registrar)]
+    pub voter: AccountLoader<'info, Voter>,
+
+    /// The associated token account of voter for deposit_mint, created if needed.
+    ///
+    /// CHECK: validated in the instruction
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: validated in the instruction:
+    /// - a token account for deposit_mint owned by deposit_authority
+    #[account(mut)]
+    pub deposit_token: UncheckedAccount<'info>,
+    pub deposit_authority: Signer<'info>,
+
+    /// Rent payer if the vault needs to be created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub deposit_mint: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> DepositUnlocked<'info> {
+    pub fn transfer(&self, amount: u64) -> Result<u64> {
+        token_interface::transfer_to_vault(
+            &self.token_program,
+            &self.deposit_token,
+            &self.deposit_mint,
+            &self.vault,
+            &self.deposit_authority,
+            amount,
+        )
+    }
+}
+
+/// Adds unlocked tokens to a voter, without the voter authority's signature.
+///
+/// Tokens will be transfered from deposit_token to vault using the deposit_authority.
+/// They go to the first deposit entry of the voter with `LockupKind::None` for
+/// deposit_mint. If there is none, the first free deposit entry is set up
+/// for it, like create_deposit_entry would.
+///
+/// `amount`: Number of native tokens to transfer.
+///
+/// The deposit limits of the voting mint apply, see configure_deposit_limits.
+///
+/// Unlike grant, no special authority is needed: the voter authority can
+/// always withdraw unlocked tokens and close the entry again, so this can't
+/// be used to permanently occupy deposit entries. Use deposit to add tokens
+/// to a specific entry, including locked ones.
+pub fn deposit_unlocked(ctx: Context<DepositUnlocked>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // Check the token accounts and create the vault, before the voter
+    // account data gets borrowed.
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.deposit_mint.key();
+    token_interface::check_token_program(&ctx.accounts.token_program)?;
+    let deposit_token = token_interface::load_token_account_for_mint(
+        &ctx.accounts.deposit_token,
+        &mint,
+        &token_program,
+    )?;
+    require_keys_eq!(
+        deposit_token.owner,
+        ctx.accounts.deposit_authority.key(),
+        VsrError::InvalidAuthority
+    );
+    token_interface::create_vault_if_needed(
+        &ctx.accounts.payer,
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.to_account_info(),
+        &ctx.accounts.deposit_mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    token_interface::load_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.key(),
+        &mint,
+        &token_program,
+    )?;
+
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
+
+    // Get the exchange rate entry associated with this deposit.
+    let mint_idx = registrar.voting_mint_config_index(mint)?;
+    require!(
+        !registrar.voting_mints[mint_idx].deprecated,
+        VsrError::VotingMintDeprecated
+    );
+
+    // Reuse an unlocked entry for the mint, so repeated deposits don't use
+    // up deposit entries.
+    let existing_entry_idx = voter.deposits().position(|d_entry| {
+        d_entry.is_used
+            && d_entry.voting_mint_config_idx as usize == mint_idx
+            && d_entry.lockup.kind == LockupKind::None
+    });
+    let curr_ts = registrar.clock_unix_timestamp();
+    let deposit_entry_index = match existing_entry_idx {
+        Some(idx) => idx,
+        None => {
+            let free_entry_idx = voter
+                .deposits()
+                .position(|d_entry| !d_entry.is_used)
+                .ok_or(VsrError::DepositEntryFull)?;
+            let d_entry = voter.deposit_mut(free_entry_idx).unwrap();
+            *d_entry = DepositEntry::default();
+            d_entry.is_used = true;
+            d_entry.voting_mint_config_idx = mint_idx as u8;
+            d_entry.lockup = Lockup::new_with_cliff(LockupKind::None, curr_ts, curr_ts, 0, 0)?;
+            registrar.check_lockup(mint_idx, &d_entry.lockup, curr_ts)?;
+
+            emit!(DepositEntryCreatedEvent {
+                registrar: ctx.accounts.registrar.key(),
+                voter: ctx.accounts.voter.key(),
+                deposit_entry_index: free_entry_idx as u8,
+                deposit_entry: DepositEntryState::from(&*d_entry),
+            });
+            free_entry_idx
+        }
+    };
+    let d_entry = voter.deposit_mut(deposit_entry_index).unwrap();
+    let before = DepositEntryState::from(&*d_entry);
+
+    // Deposit tokens into the vault.
+    let amount = ctx.accounts.transfer(amount)?;
+    d_entry.amount_deposited_native = d_entry.amount_deposited_native.checked_add(amount).unwrap();
+    d_entry.amount_initially_locked_native = d_entry
+        .amount_initially_locked_native
+        .checked_add(amount)
+        .unwrap();
+    registrar.voting_mints[mint_idx].add_deposited(amount);
+
+    msg!(
+        "Deposited unlocked amount {} at deposit index {}",
+        amount,
+        deposit_entry_index,
+    );
+
+    let after = DepositEntryState::from(&*d_entry);
+    registrar.check_deposit_limits(mint_idx, amount, voter.deposited_native(mint_idx))?;
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(DepositEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index: deposit_entry_index as u8,
+        amount,
+        before,
+        after,
+        voter_weight: voter.weight(registrar)?,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinishVotingMintBackfill<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Marks the deposits and voting mint totals of the voting mint at index
+/// `idx` as tracked, ending its backfill.
+///
+/// The realm authority must only call this once backfill_voting_mint_totals
+/// counted every voter with deposits of the mint that didn't change them
+/// since resize_registrar. The program can't check that: voters that are
+/// missed are left out of the totals for good, and the Deposited max vote
+/// weight mode and the registrar-wide deposit cap undercount their tokens.
+pub fn finish_voting_mint_backfill(ctx: Context<FinishVotingMintBackfill>, idx: u16) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let idx = idx as usize;
+    require_gt!(
+        registrar.voting_mints.len(),
+        idx,
+        VsrError::OutOfBoundsVotingMintConfigIndex
+    );
+    require!(
+        registrar.voting_mint_backfilling(idx),
+        VsrError::VotingMintNotBackfilling
+    );
+
+    registrar.voting_mints[idx].deposited_native_tracked = true;
+    registrar.voting_mint_totals_tracked[idx] = true;
+
+    let mint_config = &registrar.voting_mints[idx];
+    let totals = &registrar.voting_mint_totals[idx];
+    msg!(
+        "Finished backfill of voting mint {} at index {}: {} native tokens deposited, {} locked",
+        mint_config.mint,
+        idx,
+        mint_config.deposited_native,
+        totals.locked_native
+    );
+    emit!(VotingMintBackfillFinishedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        mint: mint_config.mint,
+        deposited_native: mint_config.deposited_native,
+        locked_native: totals.locked_native,
+        clawback_deposited_native: totals.clawback_deposited_native,
+    });
+
+    Ok(())
+}
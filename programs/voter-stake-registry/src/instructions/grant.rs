@@ -1,13 +1,18 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use std::convert::TryFrom;
 use std::mem::size_of;
 
 #[derive(Accounts)]
 pub struct Grant<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     #[account(
@@ -35,20 +40,16 @@ pub struct Grant<'info> {
     )]
     pub voter_weight_record: Account<'info, VoterWeightRecord>,
 
-    #[account(
-        init_if_needed,
-        associated_token::authority = voter,
-        associated_token::mint = deposit_mint,
-        payer = payer
-    )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// The associated token account of voter for deposit_mint, created if needed.
+    ///
+    /// CHECK: validated in the instruction
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        constraint = deposit_token.owner == token_authority.key(),
-        constraint = deposit_token.mint == deposit_mint.key(),
-    )]
-    pub deposit_token: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in the instruction:
+    /// - a token account for deposit_mint owned by token_authority
+    #[account(mut)]
+    pub deposit_token: UncheckedAccount<'info>,
 
     /// Authority for transfering tokens away from deposit_token
     pub token_authority: Signer<'info>,
@@ -62,23 +63,27 @@ pub struct Grant<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    pub deposit_mint: Box<Account<'info, Mint>>,
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub deposit_mint: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> Grant<'info> {
-    pub fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
-        let program = self.token_program.to_account_info();
-        let accounts = token::Transfer {
-            from: self.deposit_token.to_account_info(),
-            to: self.vault.to_account_info(),
-            authority: self.token_authority.to_account_info(),
-        };
-        CpiContext::new(program, accounts)
+    pub fn transfer(&self, amount: u64) -> Result<u64> {
+        token_interface::transfer_to_vault(
+            &self.token_program,
+            &self.deposit_token,
+            &self.deposit_mint,
+            &self.vault,
+            &self.token_authority,
+            amount,
+        )
     }
 }
 
@@ -95,6 +100,8 @@ pub fn is_freshly_initialized(account_info: &AccountInfo) -> Result<bool> {
 ///
 /// The voter may or may not exist in advance.
 /// Creates a new deposit entry -- errors if no free ones are available.
+///
+/// The deposit limits of the voting mint apply, see configure_deposit_limits.
 #[allow(clippy::too_many_arguments)]
 pub fn grant(
     ctx: Context<Grant>,
@@ -105,6 +112,34 @@ pub fn grant(
     periods: u32,
     allow_clawback: bool,
     amount: u64,
+) -> Result<()> {
+    grant_with_cliff(
+        ctx,
+        voter_bump,
+        voter_weight_record_bump,
+        kind,
+        start_ts,
+        periods,
+        0,
+        allow_clawback,
+        amount,
+    )
+}
+
+/// Gives a grant whose lockup starts with a cliff to a voter.
+///
+/// Like `grant`, with `cliff_periods` as for `create_deposit_entry_with_cliff`.
+#[allow(clippy::too_many_arguments)]
+pub fn grant_with_cliff(
+    ctx: Context<Grant>,
+    voter_bump: u8,
+    voter_weight_record_bump: u8,
+    kind: LockupKind,
+    start_ts: Option<u64>,
+    periods: u32,
+    cliff_periods: u32,
+    allow_clawback: bool,
+    amount: u64,
 ) -> Result<()> {
     require_eq!(voter_bump, *ctx.bumps.get("voter").unwrap());
     require_eq!(
@@ -112,13 +147,44 @@ pub fn grant(
         *ctx.bumps.get("voter_weight_record").unwrap()
     );
 
+    // Check the token accounts and create the vault, before the voter
+    // account data gets borrowed.
+    let token_program = ctx.accounts.token_program.key();
+    let mint = ctx.accounts.deposit_mint.key();
+    token_interface::check_token_program(&ctx.accounts.token_program)?;
+    let deposit_token = token_interface::load_token_account_for_mint(
+        &ctx.accounts.deposit_token,
+        &mint,
+        &token_program,
+    )?;
+    require_keys_eq!(
+        deposit_token.owner,
+        ctx.accounts.token_authority.key(),
+        VsrError::InvalidAuthority
+    );
+    token_interface::create_vault_if_needed(
+        &ctx.accounts.payer,
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.to_account_info(),
+        &ctx.accounts.deposit_mint,
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    token_interface::load_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.voter.key(),
+        &mint,
+        &token_program,
+    )?;
+
     // Load accounts.
-    let registrar = &ctx.accounts.registrar.load()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
     let voter_authority = ctx.accounts.voter_authority.key();
 
     // Get the exchange rate entry associated with this deposit.
-    let mint_idx = registrar.voting_mint_config_index(ctx.accounts.deposit_token.mint)?;
+    let mint_idx = registrar.voting_mint_config_index(mint)?;
     let mint_config = &registrar.voting_mints[mint_idx];
+    require!(!mint_config.deprecated, VsrError::VotingMintDeprecated);
 
     // The grant instruction creates a new deposit entry for the target voter. This is a
     // limited resource. If anyone could call "grant" then it could be used for denial of
@@ -133,12 +199,8 @@ pub fn grant(
 
     // Init the voter if it hasn't been already.
     let new_voter = is_freshly_initialized(ctx.accounts.voter.as_ref())?;
-    let mut voter = if new_voter {
-        ctx.accounts.voter.load_init()?
-    } else {
-        ctx.accounts.voter.load_mut()?
-    };
     if new_voter {
+        let mut voter = ctx.accounts.voter.load_init()?;
         voter.voter_bump = voter_bump;
         voter.voter_weight_record_bump = voter_weight_record_bump;
         voter.voter_authority = voter_authority;
@@ -154,14 +216,16 @@ pub fn grant(
         voter_weight_record.governing_token_mint = registrar.realm_governing_token_mint;
         voter_weight_record.governing_token_owner = voter_authority;
     }
+    let mut voter = VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(&mut voter);
+    let totals_before = voter.voting_mint_totals();
 
     // Get and init the first free deposit entry.
     let free_entry_idx = voter
-        .deposits
-        .iter()
+        .deposits()
         .position(|d_entry| !d_entry.is_used)
         .ok_or(VsrError::DepositEntryFull)?;
-    let d_entry = &mut voter.deposits[free_entry_idx];
+    let d_entry = voter.deposit_mut(free_entry_idx).unwrap();
 
     let curr_ts = registrar.clock_unix_timestamp();
     let start_ts = if let Some(v) = start_ts {
@@ -175,20 +239,38 @@ pub fn grant(
     d_entry.is_used = true;
     d_entry.voting_mint_config_idx = mint_idx as u8;
     d_entry.allow_clawback = allow_clawback;
-    d_entry.lockup = Lockup::new_from_periods(kind, curr_ts, start_ts, periods)?;
+    d_entry.lockup = Lockup::new_with_cliff(kind, curr_ts, start_ts, cliff_periods, periods)?;
+    registrar.check_lockup(mint_idx, &d_entry.lockup, curr_ts)?;
 
     // Deposit tokens, locking them all.
-    token::transfer(ctx.accounts.transfer_ctx(), amount)?;
+    let amount = ctx.accounts.transfer(amount)?;
     d_entry.amount_deposited_native = amount;
     d_entry.amount_initially_locked_native = amount;
+    registrar.voting_mints[mint_idx].add_deposited(amount);
 
     msg!(
-        "Granted amount {} at deposit index {} with lockup kind {:?} for {} periods",
+        "Granted amount {} at deposit index {} with lockup kind {:?} for {} periods after a cliff of {} periods",
         amount,
         free_entry_idx,
         d_entry.lockup.kind,
         periods,
+        cliff_periods,
     );
 
+    let deposit_entry = DepositEntryState::from(&*d_entry);
+    registrar.check_deposit_limits(mint_idx, amount, voter.deposited_native(mint_idx))?;
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(GrantEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        voter_authority,
+        grant_authority,
+        deposit_entry_index: free_entry_idx as u8,
+        amount,
+        deposit_entry,
+        voter_weight: voter.weight(registrar)?,
+    });
+
     Ok(())
 }
@@ -1,9 +1,14 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct InternalTransferLocked<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -37,22 +42,21 @@ pub fn internal_transfer_locked(
     target_deposit_entry_index: u8,
     amount: u64,
 ) -> Result<()> {
-    let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
     let curr_ts = registrar.clock_unix_timestamp();
 
     let source = voter.active_deposit_mut(source_deposit_entry_index)?;
+    let source_before = DepositEntryState::from(&*source);
     source.resolve_vesting(curr_ts)?;
     let source_seconds_left = source.lockup.seconds_left(curr_ts);
     let source_strictness = source.lockup.kind.strictness();
+    let source_lockup = source.lockup;
     let source_mint_idx = source.voting_mint_config_idx;
 
-    // Allowing transfers from clawback-enabled deposits could be used to avoid
-    // clawback by making proposal instructions target the wrong entry index.
-    require!(
-        !source.allow_clawback,
-        VsrError::InvalidChangeToClawbackDepositEntry
-    );
+    source.assert_tokens_movable()?;
 
     // Reduce source amounts
     require_gte!(
@@ -63,9 +67,11 @@ pub fn internal_transfer_locked(
     source.amount_deposited_native = source.amount_deposited_native.checked_sub(amount).unwrap();
     source.amount_initially_locked_native =
         source.amount_initially_locked_native.saturating_sub(amount);
+    let source_after = DepositEntryState::from(&*source);
 
     // Check target compatibility
     let target = voter.active_deposit_mut(target_deposit_entry_index)?;
+    let target_before = DepositEntryState::from(&*target);
     target.resolve_vesting(curr_ts)?;
     require_eq!(
         target.voting_mint_config_idx,
@@ -82,6 +88,8 @@ pub fn internal_transfer_locked(
         source_strictness,
         VsrError::InvalidLockupKind
     );
+    source_lockup.require_cliff_kept(&target.lockup, curr_ts)?;
+    registrar.check_lockup(source_mint_idx as usize, &target.lockup, curr_ts)?;
 
     // Add target amounts
     target.amount_deposited_native = target.amount_deposited_native.checked_add(amount).unwrap();
@@ -89,6 +97,22 @@ pub fn internal_transfer_locked(
         .amount_initially_locked_native
         .checked_add(amount)
         .unwrap();
+    let target_after = DepositEntryState::from(&*target);
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(InternalTransferEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        locked: true,
+        amount,
+        source_deposit_entry_index,
+        source_before,
+        source_after,
+        target_deposit_entry_index,
+        target_before,
+        target_after,
+        voter_weight: voter.weight(registrar)?,
+    });
 
     Ok(())
 }
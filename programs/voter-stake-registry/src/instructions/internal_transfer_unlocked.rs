@@ -1,9 +1,14 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct InternalTransferUnlocked<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -31,11 +36,14 @@ pub fn internal_transfer_unlocked(
     target_deposit_entry_index: u8,
     amount: u64,
 ) -> Result<()> {
-    let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
     let curr_ts = registrar.clock_unix_timestamp();
 
     let source = voter.active_deposit_mut(source_deposit_entry_index)?;
+    let source_before = DepositEntryState::from(&*source);
     let source_mint_idx = source.voting_mint_config_idx;
 
     // Reduce source amounts
@@ -45,9 +53,11 @@ pub fn internal_transfer_unlocked(
         VsrError::InsufficientUnlockedTokens
     );
     source.amount_deposited_native = source.amount_deposited_native.checked_sub(amount).unwrap();
+    let source_after = DepositEntryState::from(&*source);
 
     // Check target compatibility
     let target = voter.active_deposit_mut(target_deposit_entry_index)?;
+    let target_before = DepositEntryState::from(&*target);
     require_eq!(
         target.voting_mint_config_idx,
         source_mint_idx,
@@ -56,6 +66,22 @@ pub fn internal_transfer_unlocked(
 
     // Add target amounts
     target.amount_deposited_native = target.amount_deposited_native.checked_add(amount).unwrap();
+    let target_after = DepositEntryState::from(&*target);
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(InternalTransferEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        locked: false,
+        amount,
+        source_deposit_entry_index,
+        source_before,
+        source_after,
+        target_deposit_entry_index,
+        target_before,
+        target_after,
+        voter_weight: voter.weight(registrar)?,
+    });
 
     Ok(())
 }
@@ -0,0 +1,39 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct LogRegistrarInfo<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+}
+
+/// A no-effect instruction that logs the deposited, locked and
+/// clawback-enabled totals of each voting mint.
+///
+/// Totals are omitted for mints configured before they were tracked.
+/// The locked total includes tokens that vested or whose lockup expired
+/// since the deposit entry was last changed, see VotingMintTotals.
+pub fn log_registrar_info(ctx: Context<LogRegistrarInfo>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar.load()?;
+
+    msg!("voting_mints");
+    for (idx, mint_config) in registrar.voting_mints.iter().enumerate() {
+        if !mint_config.in_use() {
+            continue;
+        }
+        let totals = &registrar.voting_mint_totals[idx];
+        let totals_tracked = registrar.voting_mint_totals_tracked[idx];
+        emit!(VotingMintInfo {
+            voting_mint_config_index: idx as u8,
+            mint: mint_config.mint,
+            deposited: mint_config
+                .deposited_native_tracked
+                .then_some(mint_config.deposited_native),
+            locked: totals_tracked.then_some(totals.locked_native),
+            clawback_deposited: totals_tracked.then_some(totals.clawback_deposited_native),
+        });
+    }
+    Ok(())
+}
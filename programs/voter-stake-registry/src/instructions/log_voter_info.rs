@@ -1,9 +1,11 @@
+use crate::error::*;
 use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct LogVoterInfo<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
 
     #[account(has_one = registrar)]
@@ -15,15 +17,17 @@ pub struct LogVoterInfo<'info> {
 /// Logs deposit information about deposits with an index between `deposit_entry_begin`
 /// and `deposit_entry_begin + deposit_entry_count`.
 ///
-/// With the current setup, all information about deposits can be logged by calling
-/// this with deposit_entry_begin=0, =8, =16, =24 and deposit_entry_count=8.
+/// All information about deposits can be logged by calling this with
+/// deposit_entry_begin=0, =8, =16, ... and deposit_entry_count=8, until
+/// deposit_entry_begin reaches the voter's number of deposit entries: 32, or
+/// more for voters that were grown with resize_voter.
 pub fn log_voter_info(
     ctx: Context<LogVoterInfo>,
     deposit_entry_begin: u8,
     deposit_entry_count: u8,
 ) -> Result<()> {
     let registrar = &ctx.accounts.registrar.load()?;
-    let voter = ctx.accounts.voter.load()?;
+    let voter = VoterRef::load(ctx.accounts.voter.as_ref())?;
     let curr_ts = registrar.clock_unix_timestamp();
     let deposit_entry_begin = deposit_entry_begin as usize;
     let deposit_entry_count = deposit_entry_count as usize;
@@ -35,7 +39,7 @@ pub fn log_voter_info(
     });
 
     msg!("deposit_entries");
-    for (deposit_index, deposit) in voter.deposits.iter().enumerate() {
+    for (deposit_index, deposit) in voter.deposits().enumerate() {
         if !deposit.is_used
             || deposit_index < deposit_entry_begin
             || deposit_index >= deposit_entry_begin + deposit_entry_count
@@ -47,6 +51,8 @@ pub fn log_voter_info(
         let end_ts = curr_ts as u64 + seconds_left;
         let periods_total = lockup.periods_total()?;
         let periods_left = lockup.periods_left(curr_ts)?;
+        let cliff_end_ts =
+            (lockup.cliff_periods_left(curr_ts) > 0).then(|| lockup.cliff_end_ts() as u64);
         let voting_mint_config = &registrar.voting_mints[deposit.voting_mint_config_idx as usize];
         let locking_info = (seconds_left > 0).then(|| LockingInfo {
             amount: deposit.amount_locked(curr_ts),
@@ -56,12 +62,15 @@ pub fn log_voter_info(
                     .amount_initially_locked_native
                     .checked_div(periods_total)
                     .unwrap(),
-                next_timestamp: end_ts.saturating_sub(
-                    periods_left
-                        .saturating_sub(1)
-                        .checked_mul(lockup.kind.period_secs())
-                        .unwrap(),
-                ),
+                next_timestamp: cliff_end_ts.unwrap_or_else(|| {
+                    end_ts.saturating_sub(
+                        periods_left
+                            .saturating_sub(1)
+                            .checked_mul(lockup.kind.period_secs())
+                            .unwrap(),
+                    )
+                }),
+                cliff_end_timestamp: cliff_end_ts,
             }),
         });
 
@@ -0,0 +1,163 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MergeDepositEntries<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = voter_authority,
+        has_one = registrar)]
+    pub voter: AccountLoader<'info, Voter>,
+    pub voter_authority: Signer<'info>,
+}
+
+/// Moves all tokens of the listed deposit entries into one of them and
+/// frees the others.
+///
+/// The target is the listed entry with the strictest lockup kind that hasn't
+/// expired, and among those the one with the most time left. Vesting is
+/// resolved for all entries first, so the locked tokens of the others keep
+/// being locked in the target and unlocked tokens stay unlocked. Like for
+/// internal_transfer_locked, the target's lockup must be at least as long and
+/// strict as that of every entry that still has locked tokens.
+///
+/// All entries must be for the same mint and none may allow clawback.
+pub fn merge_deposit_entries(
+    ctx: Context<MergeDepositEntries>,
+    deposit_entry_indexes: Vec<u8>,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
+    let curr_ts = registrar.clock_unix_timestamp();
+
+    let mut distinct = deposit_entry_indexes.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    require!(
+        distinct.len() >= 2 && distinct.len() == deposit_entry_indexes.len(),
+        VsrError::InvalidMergeDepositEntries
+    );
+
+    let mut befores = Vec::with_capacity(deposit_entry_indexes.len());
+    for &index in &deposit_entry_indexes {
+        let d_entry = voter.active_deposit_mut(index)?;
+        befores.push(DepositEntryState::from(&*d_entry));
+        d_entry.assert_tokens_movable()?;
+        d_entry.resolve_vesting(curr_ts)?;
+    }
+
+    // Expired lockups hold no locked tokens after resolve_vesting and rank
+    // below all others.
+    let rank = |d_entry: &DepositEntry| {
+        let seconds_left = d_entry.lockup.seconds_left(curr_ts);
+        (
+            seconds_left > 0,
+            d_entry.lockup.kind.strictness(),
+            seconds_left,
+        )
+    };
+    let mut target_pos = 0;
+    for (pos, &index) in deposit_entry_indexes.iter().enumerate().skip(1) {
+        let candidate = voter.deposit(index as usize).unwrap();
+        let target = voter
+            .deposit(deposit_entry_indexes[target_pos] as usize)
+            .unwrap();
+        if rank(candidate) > rank(target) {
+            target_pos = pos;
+        }
+    }
+    let target_deposit_entry_index = deposit_entry_indexes[target_pos];
+    let target = voter.deposit(target_deposit_entry_index as usize).unwrap();
+    let target_lockup = target.lockup;
+    let target_mint_idx = target.voting_mint_config_idx;
+
+    let mut amount_deposited = 0u64;
+    let mut amount_locked = 0u64;
+    for &index in &deposit_entry_indexes {
+        if index == target_deposit_entry_index {
+            continue;
+        }
+        let source = voter.active_deposit_mut(index)?;
+        require_eq!(
+            source.voting_mint_config_idx,
+            target_mint_idx,
+            VsrError::InvalidMint
+        );
+        if source.amount_initially_locked_native > 0 {
+            require_gte!(
+                target_lockup.seconds_left(curr_ts),
+                source.lockup.seconds_left(curr_ts),
+                VsrError::InvalidLockupPeriod
+            );
+            require_gte!(
+                target_lockup.kind.strictness(),
+                source.lockup.kind.strictness(),
+                VsrError::InvalidLockupKind
+            );
+            source.lockup.require_cliff_kept(&target_lockup, curr_ts)?;
+        }
+        amount_deposited = amount_deposited
+            .checked_add(source.amount_deposited_native)
+            .unwrap();
+        amount_locked = amount_locked
+            .checked_add(source.amount_initially_locked_native)
+            .unwrap();
+
+        *source = DepositEntry::default();
+        source.is_used = false;
+    }
+    if amount_locked > 0 {
+        registrar.check_lockup(target_mint_idx as usize, &target_lockup, curr_ts)?;
+    }
+
+    let target = voter.active_deposit_mut(target_deposit_entry_index)?;
+    target.amount_deposited_native = target
+        .amount_deposited_native
+        .checked_add(amount_deposited)
+        .unwrap();
+    target.amount_initially_locked_native = target
+        .amount_initially_locked_native
+        .checked_add(amount_locked)
+        .unwrap();
+    let target_after = DepositEntryState::from(&*target);
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    msg!(
+        "Merged amount {} ({} locked) from deposit indexes {:?} into deposit index {}",
+        amount_deposited,
+        amount_locked,
+        deposit_entry_indexes,
+        target_deposit_entry_index,
+    );
+
+    emit!(DepositEntriesMergedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        amount: amount_deposited,
+        source_deposit_entry_indexes: deposit_entry_indexes
+            .iter()
+            .copied()
+            .filter(|&index| index != target_deposit_entry_index)
+            .collect(),
+        target_deposit_entry_index,
+        target_before: befores.swap_remove(target_pos),
+        target_after,
+        voter_weight: voter.weight(registrar)?,
+    });
+
+    Ok(())
+}
@@ -1,35 +1,73 @@
+pub use accept_voter_transfer::*;
+pub use backfill_voting_mint_totals::*;
 pub use clawback::*;
 pub use close_deposit_entry::*;
 pub use close_voter::*;
+pub use configure_deposit_limits::*;
+pub use configure_lockup_curve::*;
+pub use configure_lockup_limits::*;
+pub use configure_max_vote_weight_mode::*;
 pub use configure_voting_mint::*;
 pub use create_deposit_entry::*;
+pub use create_max_voter_weight_record::*;
 pub use create_registrar::*;
 pub use create_voter::*;
 pub use deposit::*;
+pub use deposit_unlocked::*;
+pub use finish_voting_mint_backfill::*;
 pub use grant::*;
 pub use internal_transfer_locked::*;
 pub use internal_transfer_unlocked::*;
+pub use log_registrar_info::*;
 pub use log_voter_info::*;
+pub use merge_deposit_entries::*;
+pub use propose_voter_transfer::*;
+pub use remove_voting_mint::*;
 pub use reset_lockup::*;
+pub use resize_registrar::*;
+pub use resize_voter::*;
+pub use set_delegate::*;
 pub use set_time_offset::*;
+pub use split_deposit_entry::*;
+pub use sync_realm_authority::*;
 pub use update_max_vote_weight::*;
 pub use update_voter_weight_record::*;
+pub use update_voter_weight_records::*;
 pub use withdraw::*;
 
+mod accept_voter_transfer;
+mod backfill_voting_mint_totals;
 mod clawback;
 mod close_deposit_entry;
 mod close_voter;
+mod configure_deposit_limits;
+mod configure_lockup_curve;
+mod configure_lockup_limits;
+mod configure_max_vote_weight_mode;
 mod configure_voting_mint;
 mod create_deposit_entry;
+mod create_max_voter_weight_record;
 mod create_registrar;
 mod create_voter;
 mod deposit;
+mod deposit_unlocked;
+mod finish_voting_mint_backfill;
 mod grant;
 mod internal_transfer_locked;
 mod internal_transfer_unlocked;
+mod log_registrar_info;
 mod log_voter_info;
+mod merge_deposit_entries;
+mod propose_voter_transfer;
+mod remove_voting_mint;
 mod reset_lockup;
+mod resize_registrar;
+mod resize_voter;
+mod set_delegate;
 mod set_time_offset;
+mod split_deposit_entry;
+mod sync_realm_authority;
 mod update_max_vote_weight;
 mod update_voter_weight_record;
+mod update_voter_weight_records;
 mod withdraw;
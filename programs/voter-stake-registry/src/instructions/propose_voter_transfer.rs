@@ -0,0 +1,59 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as tx_instructions;
+
+#[derive(Accounts)]
+pub struct ProposeVoterTransfer<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = registrar,
+        has_one = voter_authority,
+    )]
+    pub voter: AccountLoader<'info, Voter>,
+    pub voter_authority: Signer<'info>,
+
+    /// CHECK: Address constraint is set
+    #[account(address = tx_instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+/// Allows `new_voter_authority` to take over the voter with
+/// accept_voter_transfer, or cancels a pending transfer if it is None.
+///
+/// This is the first step of moving a voter to a new wallet, for example
+/// when the voter authority's key is to be rotated. Proposing again replaces
+/// the pending new voter authority.
+pub fn propose_voter_transfer(
+    ctx: Context<ProposeVoterTransfer>,
+    new_voter_authority: Option<Pubkey>,
+) -> Result<()> {
+    Voter::assert_not_cpi(&ctx.accounts.instructions, ctx.program_id)?;
+
+    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let new_voter_authority = new_voter_authority.unwrap_or_default();
+    require_keys_neq!(
+        new_voter_authority,
+        voter.voter_authority,
+        VsrError::InvalidNewVoterAuthority
+    );
+    voter.pending_voter_authority = new_voter_authority;
+
+    emit!(VoterTransferProposedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        voter_authority: voter.voter_authority,
+        new_voter_authority: (new_voter_authority != Pubkey::default())
+            .then_some(new_voter_authority),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveVotingMint<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+    pub realm_authority: Signer<'info>,
+}
+
+/// Retires the voting mint at index `idx`.
+///
+/// The mint's vote weight factors are zeroed, so its deposits stop counting
+/// towards voter weights and the max vote weight. The mint accepts no new
+/// deposits or grants, but voters can still withdraw their tokens when they
+/// unlock. Lockups and clawback of existing deposits are unaffected.
+///
+/// Voter weight records are only updated when voters refresh them, so this
+/// should be followed by update_voter_weight_record calls for affected voters.
+///
+/// Calling configure_voting_mint for the same mint and index makes it a
+/// regular voting mint again. Once all its tokens are withdrawn, a different
+/// mint can be configured at the index instead.
+pub fn remove_voting_mint(ctx: Context<RemoveVotingMint>, idx: u16) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let idx = idx as usize;
+    require_gt!(
+        registrar.voting_mints.len(),
+        idx,
+        VsrError::OutOfBoundsVotingMintConfigIndex
+    );
+
+    let mint_config = &mut registrar.voting_mints[idx];
+    require!(mint_config.in_use(), VsrError::VotingMintNotFound);
+    mint_config.deprecated = true;
+    mint_config.baseline_vote_weight_scaled_factor = 0;
+    mint_config.max_extra_lockup_vote_weight_scaled_factor = 0;
+    mint_config.grant_authority = Pubkey::default();
+
+    msg!(
+        "Removed voting mint {} at index {}, {} native tokens still deposited",
+        mint_config.mint,
+        idx,
+        mint_config.deposited_native
+    );
+    emit!(VotingMintRemovedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voting_mint_config_index: idx as u16,
+        mint: mint_config.mint,
+        deposited_native: mint_config.deposited_native,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct ResetLockup<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -27,11 +32,14 @@ pub fn reset_lockup(
     kind: LockupKind,
     periods: u32,
 ) -> Result<()> {
-    let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
     let curr_ts = registrar.clock_unix_timestamp();
 
     let source = voter.active_deposit_mut(deposit_entry_index)?;
+    let before = DepositEntryState::from(&*source);
 
     // Must not decrease duration or strictness
     require_gte!(
@@ -51,10 +59,26 @@ pub fn reset_lockup(
         VsrError::InvalidChangeToClawbackDepositEntry
     );
 
+    // The new lockup has no cliff, it must not end a pending one early.
+    let lockup = Lockup::new_from_periods(kind, curr_ts, curr_ts, periods)?;
+    source.lockup.require_cliff_kept(&lockup, curr_ts)?;
+    registrar.check_lockup(source.voting_mint_config_idx as usize, &lockup, curr_ts)?;
+
     // Change the deposit entry.
     let d_entry = voter.active_deposit_mut(deposit_entry_index)?;
     d_entry.amount_initially_locked_native = d_entry.amount_deposited_native;
-    d_entry.lockup = Lockup::new_from_periods(kind, curr_ts, curr_ts, periods)?;
+    d_entry.lockup = lockup;
+    let after = DepositEntryState::from(&*d_entry);
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    emit!(ResetLockupEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index,
+        before,
+        after,
+        voter_weight: voter.weight(registrar)?,
+    });
 
     Ok(())
 }
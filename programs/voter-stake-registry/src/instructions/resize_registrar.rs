@@ -0,0 +1,73 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct ResizeRegistrar<'info> {
+    /// A registrar that may still have an older layout with fewer voting mints,
+    /// so it can't be loaded as a Registrar.
+    ///
+    /// CHECK: validated in the instruction:
+    /// - owned by this program
+    /// - has the Registrar discriminator
+    #[account(mut, owner = crate::ID)]
+    pub registrar: UncheckedAccount<'info>,
+
+    /// Pays for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a registrar that was created with fewer voting mints than
+/// MAX_VOTING_MINTS, or without voting mint totals, to the current layout.
+///
+/// The existing voting mint configs keep their indexes, so deposit entries
+/// that refer to them stay valid. The new voting mint configs are unused.
+/// Deposits and voting mint totals start out untracked for the existing
+/// voting mints, see backfill_voting_mint_totals.
+///
+/// Anyone may call this, the payer covers the additional rent. It's a no-op
+/// for registrars that already have the current layout.
+pub fn resize_registrar(ctx: Context<ResizeRegistrar>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    {
+        let data = registrar.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == Registrar::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    let old_len = registrar.data_len();
+    let new_len = REGISTRAR_ACCOUNT_LEN;
+    if old_len == new_len {
+        return Ok(());
+    }
+    require_gt!(new_len, old_len, VsrError::InvalidRegistrarSize);
+
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+    let missing_lamports = rent_exempt_lamports.saturating_sub(registrar.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: registrar.to_account_info(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    registrar.realloc(new_len, true)?;
+    migrate_registrar_data(&mut registrar.try_borrow_mut_data()?, old_len)?;
+
+    msg!("Resized registrar from {} to {} bytes", old_len, new_len);
+
+    Ok(())
+}
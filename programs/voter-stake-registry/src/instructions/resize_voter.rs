@@ -0,0 +1,91 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct ResizeVoter<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = registrar,
+        has_one = voter_authority,
+    )]
+    pub voter: AccountLoader<'info, Voter>,
+
+    pub voter_authority: Signer<'info>,
+
+    /// Pays for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows the voter account so it has room for `deposit_entry_count` deposit
+/// entries, up to MAX_DEPOSIT_ENTRIES.
+///
+/// Voter accounts start out with 32 deposit entries. The additional entries
+/// are stored after the Voter data and are unused initially. They are used
+/// with their index like the first 32 entries.
+///
+/// Voter accounts can't shrink. It's a no-op if the voter already has
+/// `deposit_entry_count` entries.
+pub fn resize_voter(ctx: Context<ResizeVoter>, deposit_entry_count: u8) -> Result<()> {
+    let voter = &ctx.accounts.voter;
+    let old_len = voter.as_ref().data_len();
+    let old_count = VoterRef::load(voter.as_ref())?.deposit_entry_count();
+    let new_count = deposit_entry_count as usize;
+    require_gte!(
+        MAX_DEPOSIT_ENTRIES,
+        new_count,
+        VsrError::OutOfBoundsDepositEntryIndex
+    );
+    if new_count == old_count {
+        return Ok(());
+    }
+    require_gt!(new_count, old_count, VsrError::InvalidVoterSize);
+
+    let fixed_count = voter.load()?.deposits.len();
+    let new_len = VOTER_ACCOUNT_LEN + (new_count - fixed_count) * size_of::<DepositEntry>();
+
+    let voter_info = voter.to_account_info();
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(new_len);
+    let missing_lamports = rent_exempt_lamports.saturating_sub(voter_info.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: voter_info.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    voter_info.realloc(new_len, true)?;
+
+    msg!(
+        "Resized voter from {} to {} bytes, {} deposit entries",
+        old_len,
+        new_len,
+        new_count
+    );
+    emit!(VoterResizedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: voter.key(),
+        deposit_entry_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = registrar,
+        has_one = voter_authority,
+    )]
+    pub voter: AccountLoader<'info, Voter>,
+    pub voter_authority: Signer<'info>,
+
+    /// The token owner record of the current voting owner: the current or
+    /// revoked delegate, or the voter authority if there is none. Votes cast
+    /// with it must be relinquished before the voting owner can change.
+    ///
+    /// CHECK: token_owner_record is validated in the instruction:
+    /// - the token owner record address for the current voting owner
+    /// - if it exists, owned by registrar.governance_program_id
+    pub token_owner_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter-weight-record".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_weight_record_bump,
+        constraint = voter_weight_record.realm == registrar.load()?.realm,
+        constraint = voter_weight_record.governing_token_owner == voter.load()?.voter_weight_record_owner(),
+        constraint = voter_weight_record.governing_token_mint == registrar.load()?.realm_governing_token_mint,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+}
+
+/// Lets `delegate` vote with the voter's weight, or the voter authority
+/// again if `delegate` is None.
+///
+/// The voter weight record is handed over to the delegate: it becomes
+/// usable with the delegate's own token owner record and unusable with the
+/// voter authority's. The voter authority keeps all other rights, like
+/// depositing and withdrawing.
+///
+/// Changing the delegate is only possible while the token owner record of
+/// the current voting owner has no outstanding votes or proposals, like
+/// withdrawing. Otherwise the same vote weight could be used twice. Note
+/// that a delegate can use only one voter weight record per proposal, so
+/// delegating several voters to the same wallet does not add up their
+/// weights.
+///
+/// The voter authority can always revoke the delegate, so that an
+/// uncooperative delegate can't keep the voter's tokens locked. While the
+/// delegate still has unrelinquished votes the revocation stays pending:
+/// nobody can vote with the voter weight record, and only those votes
+/// block withdrawing. Anyone can relinquish them once voting on them has
+/// ended; calling set_delegate again then completes the revocation.
+pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar.load()?;
+    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let old_record_owner = voter.voter_weight_record_owner();
+    let old_delegate = if voter.delegate_revoked {
+        Pubkey::default()
+    } else {
+        voter.delegate
+    };
+
+    let delegate = delegate.unwrap_or_default();
+    require_keys_neq!(delegate, voter.voter_authority, VsrError::InvalidDelegate);
+
+    let token_owner_record = voter.load_voting_owner_token_owner_record(
+        &ctx.accounts.token_owner_record.to_account_info(),
+        registrar,
+    )?;
+    let revoking = delegate == Pubkey::default() && voter.delegate != Pubkey::default();
+    if revoking {
+        voter.delegate_revoked = true;
+    }
+    let released = token_owner_record.map_or(Ok(()), |record| {
+        voter.assert_token_owner_record_released(&record)
+    });
+    match released {
+        Ok(()) => {
+            voter.delegate = delegate;
+            voter.delegate_revoked = false;
+        }
+        Err(_) if revoking => {
+            msg!("Delegate revocation is pending until its votes are relinquished");
+        }
+        Err(err) => return Err(err),
+    }
+
+    // The record must be updated before it can be used again, so no weight
+    // carries over to the new voting owner within the same slot.
+    let record = &mut ctx.accounts.voter_weight_record;
+    record.governing_token_owner = voter.voter_weight_record_owner();
+    record.voter_weight = 0;
+    record.voter_weight_expiry = Some(0);
+
+    msg!(
+        "Voting owner changed from {} to {}",
+        old_record_owner,
+        record.governing_token_owner
+    );
+    if old_delegate != delegate {
+        emit!(DelegateSetEvent {
+            registrar: ctx.accounts.registrar.key(),
+            voter: ctx.accounts.voter.key(),
+            voter_authority: voter.voter_authority,
+            old_delegate: (old_delegate != Pubkey::default()).then_some(old_delegate),
+            new_delegate: (delegate != Pubkey::default()).then_some(delegate),
+        });
+    }
+
+    Ok(())
+}
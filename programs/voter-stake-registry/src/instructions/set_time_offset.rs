@@ -6,7 +6,11 @@ use std::str::FromStr;
 #[derive(Accounts)]
 #[instruction(time_offset: i64)]
 pub struct SetTimeOffset<'info> {
-    #[account(mut, has_one = realm_authority)]
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm_authority == realm_authority.key() @ ErrorCode::ConstraintHasOne,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
     pub realm_authority: Signer<'info>,
 }
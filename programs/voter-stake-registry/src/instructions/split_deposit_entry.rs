@@ -0,0 +1,106 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(Accounts)]
+pub struct SplitDepositEntry<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    // checking the PDA address it just an extra precaution,
+    // the other constraints must be exhaustive
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"voter".as_ref(), voter_authority.key().as_ref()],
+        bump = voter.load()?.voter_bump,
+        has_one = voter_authority,
+        has_one = registrar)]
+    pub voter: AccountLoader<'info, Voter>,
+    pub voter_authority: Signer<'info>,
+}
+
+/// Moves `amount` deposited tokens from the source deposit entry into the
+/// first free deposit entry, which gets an identical lockup.
+///
+/// The source entry's currently locked tokens are divided in the same
+/// proportion, so both entries keep vesting at the same rate relative to
+/// their size. The new entry's share is rounded down: the remainder stays
+/// locked in the source entry, so the total locked amount doesn't change.
+///
+/// Use internal_transfer_locked to move tokens to an existing entry instead.
+pub fn split_deposit_entry(
+    ctx: Context<SplitDepositEntry>,
+    source_deposit_entry_index: u8,
+    amount: u64,
+) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
+    let curr_ts = registrar.clock_unix_timestamp();
+
+    let source = voter.active_deposit_mut(source_deposit_entry_index)?;
+    let source_before = DepositEntryState::from(&*source);
+
+    source.assert_tokens_movable()?;
+
+    let deposited = source.amount_deposited_native;
+    require!(
+        amount > 0 && amount < deposited,
+        VsrError::InvalidSplitAmount
+    );
+
+    // Only divide what is still locked.
+    source.resolve_vesting(curr_ts)?;
+    let locked_amount = u64::try_from(
+        (source.amount_initially_locked_native as u128)
+            .checked_mul(amount as u128)
+            .unwrap()
+            / deposited as u128,
+    )
+    .unwrap();
+
+    let mut target_entry = *source;
+    target_entry.amount_deposited_native = amount;
+    target_entry.amount_initially_locked_native = locked_amount;
+    source.amount_deposited_native = deposited.checked_sub(amount).unwrap();
+    source.amount_initially_locked_native = source
+        .amount_initially_locked_native
+        .checked_sub(locked_amount)
+        .unwrap();
+    let source_after = DepositEntryState::from(&*source);
+
+    let target_deposit_entry_index = voter
+        .deposits()
+        .position(|d_entry| !d_entry.is_used)
+        .ok_or(VsrError::DepositEntryFull)?;
+    *voter.deposit_mut(target_deposit_entry_index).unwrap() = target_entry;
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
+    msg!(
+        "Split amount {} ({} locked) from deposit index {} into deposit index {}",
+        amount,
+        locked_amount,
+        source_deposit_entry_index,
+        target_deposit_entry_index,
+    );
+
+    emit!(DepositEntrySplitEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        amount,
+        source_deposit_entry_index,
+        source_before,
+        source_after,
+        target_deposit_entry_index: target_deposit_entry_index as u8,
+        target: DepositEntryState::from(&target_entry),
+        voter_weight: voter.weight(registrar)?,
+    });
+
+    Ok(())
+}
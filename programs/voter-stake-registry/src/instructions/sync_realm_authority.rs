@@ -0,0 +1,51 @@
+use crate::error::*;
+use crate::events::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use spl_governance::state::realm;
+
+#[derive(Accounts)]
+pub struct SyncRealmAuthority<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+        constraint = registrar.load()?.realm == realm.key() @ ErrorCode::ConstraintHasOne,
+    )]
+    pub registrar: AccountLoader<'info, Registrar>,
+
+    /// CHECK: realm is validated in the instruction:
+    /// - realm is owned by the registrar's governance_program_id
+    /// - realm is the registrar's realm
+    pub realm: UncheckedAccount<'info>,
+}
+
+/// Copies the realm's current authority to the registrar.
+///
+/// The registrar's realm_authority is set in create_registrar and would go
+/// stale when the realm authority changes in spl-governance, for example when
+/// it is handed to a governance after bootstrapping the DAO.
+///
+/// Anyone may call this. If the realm has no authority anymore, the registrar's
+/// realm_authority is cleared and instructions that need it can't be used.
+pub fn sync_realm_authority(ctx: Context<SyncRealmAuthority>) -> Result<()> {
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let realm = realm::get_realm_data(
+        &registrar.governance_program_id,
+        &ctx.accounts.realm.to_account_info(),
+    )?;
+
+    let realm_authority = realm.authority.unwrap_or_default();
+    msg!(
+        "Changed realm authority from {} to {}",
+        registrar.realm_authority,
+        realm_authority
+    );
+    emit!(RealmAuthoritySyncedEvent {
+        registrar: ctx.accounts.registrar.key(),
+        old_realm_authority: registrar.realm_authority,
+        new_realm_authority: realm_authority,
+    });
+    registrar.realm_authority = realm_authority;
+
+    Ok(())
+}
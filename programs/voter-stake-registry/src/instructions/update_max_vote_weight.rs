@@ -1,28 +1,43 @@
+use crate::error::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 // Remaining accounts should all the token mints that have registered
-// exchange rates.
+// exchange rates. They are not needed in the Deposited max vote weight mode.
 #[derive(Accounts)]
 pub struct UpdateMaxVoteWeight<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
-    // TODO: SPL governance has not yet implemented this.
-    /// CHECK: TODO. Will be implemented when max_vote_weight_record is rolled out.
-    pub max_vote_weight_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [registrar.key().as_ref(), b"max-voter-weight-record".as_ref()],
+        bump,
+        constraint = max_vote_weight_record.realm == registrar.load()?.realm,
+        constraint = max_vote_weight_record.governing_token_mint == registrar.load()?.realm_governing_token_mint,
+    )]
+    pub max_vote_weight_record: Account<'info, MaxVoterWeightRecord>,
 }
 
 /// Calculates the max vote weight for the registry. This is a function
 /// of the total supply of all exchange rate mints, converted into a
 /// common currency with a common number of decimals.
 ///
+/// In the Deposited max vote weight mode, only the deposited tokens of each
+/// mint count instead of the total supply, see MaxVoteWeightMode.
+///
+/// The result is written into the registrar's `MaxVoterWeightRecord` to be
+/// used by the SPL governance program. Since mint supplies can change, the
+/// record expires at the current slot and this "revise" instruction must be
+/// called immediately before it is used, in the same transaction.
+///
 /// Note that this method is only safe to use if the cumulative supply for
 /// all tokens fits into a u64 *after* converting into common decimals, as
 /// defined by the registrar's `rate_decimal` field.
 pub fn update_max_vote_weight(ctx: Context<UpdateMaxVoteWeight>) -> Result<()> {
     let registrar = &ctx.accounts.registrar.load()?;
-    let _max_vote_weight = registrar.max_vote_weight(ctx.remaining_accounts)?;
-    // TODO: SPL governance has not yet implemented this feature.
-    //       When it has, probably need to write the result into an account,
-    //       similar to VoterWeightRecord.
+    let record = &mut ctx.accounts.max_vote_weight_record;
+    record.max_voter_weight = registrar.max_vote_weight_for_mode(ctx.remaining_accounts)?;
+    record.max_voter_weight_expiry = Some(Clock::get()?.slot);
     Ok(())
 }
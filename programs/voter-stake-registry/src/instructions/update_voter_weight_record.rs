@@ -1,8 +1,10 @@
+use crate::error::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
 pub struct UpdateVoterWeightRecord<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -18,7 +20,7 @@ pub struct UpdateVoterWeightRecord<'info> {
         seeds = [registrar.key().as_ref(), b"voter-weight-record".as_ref(), voter.load()?.voter_authority.key().as_ref()],
         bump = voter.load()?.voter_weight_record_bump,
         constraint = voter_weight_record.realm == registrar.load()?.realm,
-        constraint = voter_weight_record.governing_token_owner == voter.load()?.voter_authority,
+        constraint = voter_weight_record.governing_token_owner == voter.load()?.voter_weight_record_owner(),
         constraint = voter_weight_record.governing_token_mint == registrar.load()?.realm_governing_token_mint,
     )]
     pub voter_weight_record: Account<'info, VoterWeightRecord>,
@@ -32,9 +34,12 @@ pub struct UpdateVoterWeightRecord<'info> {
 ///
 /// This "revise" instruction must be called immediately before voting, in
 /// the same transaction.
+///
+/// If the voter has a delegate, the record is usable with the delegate's
+/// token owner record, see set_delegate.
 pub fn update_voter_weight_record(ctx: Context<UpdateVoterWeightRecord>) -> Result<()> {
     let registrar = &ctx.accounts.registrar.load()?;
-    let voter = ctx.accounts.voter.load()?;
+    let voter = VoterRef::load(ctx.accounts.voter.as_ref())?;
     let record = &mut ctx.accounts.voter_weight_record;
     record.voter_weight = voter.weight(registrar)?;
     record.voter_weight_expiry = Some(Clock::get()?.slot);
@@ -0,0 +1,78 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+// Remaining accounts must be pairs of a voter and its voter weight record,
+// the records are writable.
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecords<'info> {
+    #[account(constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize)]
+    pub registrar: AccountLoader<'info, Registrar>,
+}
+
+/// Like update_voter_weight_record, but for any number of voters at once.
+///
+/// Each voter and voter weight record pair is checked like the accounts of
+/// update_voter_weight_record. The records expire at the current slot like
+/// there, so a batch can also precede votes in the same transaction.
+///
+/// Anyone may call this. The number of pairs is only limited by the
+/// transaction size and the compute budget.
+pub fn update_voter_weight_records<'key, 'accounts, 'remaining, 'info>(
+    ctx: Context<'key, 'accounts, 'remaining, 'info, UpdateVoterWeightRecords<'info>>,
+) -> Result<()> {
+    let registrar = &ctx.accounts.registrar.load()?;
+    let registrar_key = ctx.accounts.registrar.key();
+    let slot = Clock::get()?.slot;
+
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(2),
+        VsrError::InvalidVoterWeightRecordPairs
+    );
+    for accounts in ctx.remaining_accounts.chunks_exact(2) {
+        let (voter_info, record_info) = (&accounts[0], &accounts[1]);
+
+        // Checks the owner and discriminator
+        AccountLoader::<Voter>::try_from(voter_info)?;
+        let voter = VoterRef::load(voter_info)?;
+        require_keys_eq!(voter.registrar, registrar_key, ErrorCode::ConstraintHasOne);
+        let voter_address = Pubkey::create_program_address(voter_seeds!(voter), &crate::ID)
+            .map_err(|_| error!(ErrorCode::ConstraintSeeds))?;
+        require_keys_eq!(voter_address, voter_info.key(), ErrorCode::ConstraintSeeds);
+
+        require!(record_info.is_writable, ErrorCode::ConstraintMut);
+        let mut record = Account::<VoterWeightRecord>::try_from(record_info)?;
+        let record_address = Pubkey::create_program_address(
+            &[
+                registrar_key.as_ref(),
+                b"voter-weight-record".as_ref(),
+                voter.voter_authority.as_ref(),
+                &[voter.voter_weight_record_bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::ConstraintSeeds))?;
+        require_keys_eq!(
+            record_address,
+            record_info.key(),
+            ErrorCode::ConstraintSeeds
+        );
+        require!(
+            record.realm == registrar.realm
+                && record.governing_token_owner == voter.voter_weight_record_owner()
+                && record.governing_token_mint == registrar.realm_governing_token_mint,
+            ErrorCode::ConstraintRaw
+        );
+
+        record.voter_weight = voter.weight(registrar)?;
+        record.voter_weight_expiry = Some(slot);
+        record.exit(&crate::ID)?;
+    }
+
+    msg!(
+        "Updated {} voter weight records",
+        ctx.remaining_accounts.len() / 2
+    );
+
+    Ok(())
+}
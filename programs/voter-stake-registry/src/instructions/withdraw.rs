@@ -1,10 +1,15 @@
 use crate::error::*;
+use crate::events::*;
 use crate::state::*;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        constraint = has_current_registrar_layout(&registrar) @ VsrError::InvalidRegistrarSize,
+    )]
     pub registrar: AccountLoader<'info, Registrar>,
 
     // checking the PDA address it just an extra precaution,
@@ -19,15 +24,13 @@ pub struct Withdraw<'info> {
     pub voter: AccountLoader<'info, Voter>,
     pub voter_authority: Signer<'info>,
 
-    /// The token_owner_record for the voter_authority. This is needed
-    /// to be able to forbid withdraws while the voter is engaged with
-    /// a vote or has an open proposal.
+    /// The token_owner_record of the voter's current voting owner, see
+    /// set_delegate. This is needed to be able to forbid withdraws while the
+    /// voter is engaged with a vote or has an open proposal.
     ///
     /// CHECK: token_owner_record is validated in the instruction:
-    /// - owned by registrar.governance_program_id
-    /// - for the registrar.realm
-    /// - for the registrar.realm_governing_token_mint
-    /// - governing_token_owner is voter_authority
+    /// - the token owner record address for the current voting owner
+    /// - if it exists, owned by registrar.governance_program_id
     pub token_owner_record: UncheckedAccount<'info>,
 
     /// Withdraws must update the voter weight record, to prevent a stale
@@ -37,33 +40,40 @@ pub struct Withdraw<'info> {
         seeds = [registrar.key().as_ref(), b"voter-weight-record".as_ref(), voter_authority.key().as_ref()],
         bump = voter.load()?.voter_weight_record_bump,
         constraint = voter_weight_record.realm == registrar.load()?.realm,
-        constraint = voter_weight_record.governing_token_owner == voter.load()?.voter_authority,
+        constraint = voter_weight_record.governing_token_owner == voter.load()?.voter_weight_record_owner(),
         constraint = voter_weight_record.governing_token_mint == registrar.load()?.realm_governing_token_mint,
     )]
     pub voter_weight_record: Account<'info, VoterWeightRecord>,
 
-    #[account(
-        mut,
-        associated_token::authority = voter,
-        associated_token::mint = destination.mint,
-    )]
-    pub vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: validated in the instruction:
+    /// - the associated token account of voter for mint
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
 
+    /// CHECK: validated in the instruction:
+    /// - a token account for mint
     #[account(mut)]
-    pub destination: Box<Account<'info, TokenAccount>>,
+    pub destination: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: validated in the instruction:
+    /// - a mint of token_program that is configured on the registrar
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: must be the spl-token or token-2022 program
+    pub token_program: UncheckedAccount<'info>,
 }
 
 impl<'info> Withdraw<'info> {
-    pub fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
-        let program = self.token_program.to_account_info();
-        let accounts = token::Transfer {
-            from: self.vault.to_account_info(),
-            to: self.destination.to_account_info(),
-            authority: self.voter.to_account_info(),
-        };
-        CpiContext::new(program, accounts)
+    pub fn transfer(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        token_interface::transfer_checked(
+            &self.token_program,
+            &self.vault,
+            &self.mint,
+            &self.destination,
+            &self.voter.to_account_info(),
+            signer_seeds,
+            amount,
+        )
     }
 }
 
@@ -74,30 +84,45 @@ impl<'info> Withdraw<'info> {
 /// `amount` is in units of the native currency being withdrawn.
 pub fn withdraw(ctx: Context<Withdraw>, deposit_entry_index: u8, amount: u64) -> Result<()> {
     {
+        let token_program = ctx.accounts.token_program.key();
+        let mint = ctx.accounts.mint.key();
+        token_interface::check_token_program(&ctx.accounts.token_program)?;
+        token_interface::load_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.voter.key(),
+            &mint,
+            &token_program,
+        )?;
+        token_interface::load_token_account_for_mint(
+            &ctx.accounts.destination,
+            &mint,
+            &token_program,
+        )?;
+
         // Transfer the tokens to withdraw.
         let voter = &mut ctx.accounts.voter.load()?;
         let voter_seeds = voter_seeds!(voter);
-        token::transfer(
-            ctx.accounts.transfer_ctx().with_signer(&[voter_seeds]),
-            amount,
-        )?;
+        ctx.accounts.transfer(&[voter_seeds], amount)?;
     }
 
     // Load the accounts.
-    let registrar = &ctx.accounts.registrar.load()?;
-    let voter = &mut ctx.accounts.voter.load_mut()?;
+    let registrar = &mut ctx.accounts.registrar.load_mut()?;
+    let voter = &mut VoterRefMut::load(ctx.accounts.voter.as_ref())?;
+    registrar.count_voter(voter);
+    let totals_before = voter.voting_mint_totals();
 
     // Get the exchange rate for the token being withdrawn.
-    let mint_idx = registrar.voting_mint_config_index(ctx.accounts.destination.mint)?;
+    let mint_idx = registrar.voting_mint_config_index(ctx.accounts.mint.key())?;
 
     // Governance may forbid withdraws, for example when engaged in a vote.
-    // Not applicable for tokens that don't contribute to voting power.
-    if registrar.voting_mints[mint_idx].grants_vote_weight() {
-        let token_owner_record = voter.load_token_owner_record(
+    // Not applicable for tokens that don't contribute to voting power, unless
+    // they did before their mint was removed.
+    let mint_config = &registrar.voting_mints[mint_idx];
+    if mint_config.grants_vote_weight() || mint_config.deprecated {
+        voter.assert_voting_owner_can_withdraw(
             &ctx.accounts.token_owner_record.to_account_info(),
             registrar,
         )?;
-        token_owner_record.assert_can_withdraw_governing_tokens()?;
     }
 
     // Get the deposit being withdrawn from.
@@ -115,6 +140,7 @@ pub fn withdraw(ctx: Context<Withdraw>, deposit_entry_index: u8, amount: u64) ->
     );
 
     // Bookkeeping for withdrawn funds.
+    let before = DepositEntryState::from(&*deposit_entry);
     require_gte!(
         deposit_entry.amount_deposited_native,
         amount,
@@ -124,6 +150,7 @@ pub fn withdraw(ctx: Context<Withdraw>, deposit_entry_index: u8, amount: u64) ->
         .amount_deposited_native
         .checked_sub(amount)
         .unwrap();
+    registrar.voting_mints[mint_idx].sub_deposited(amount)?;
 
     msg!(
         "Withdrew amount {} at deposit index {} with lockup kind {:?} and {} seconds left",
@@ -133,10 +160,25 @@ pub fn withdraw(ctx: Context<Withdraw>, deposit_entry_index: u8, amount: u64) ->
         deposit_entry.lockup.seconds_left(curr_ts),
     );
 
+    let after = DepositEntryState::from(&*deposit_entry);
+    registrar.update_voting_mint_totals(&totals_before, &voter.voting_mint_totals());
+
     // Update the voter weight record
+    let voter_weight = voter.weight(registrar)?;
     let record = &mut ctx.accounts.voter_weight_record;
-    record.voter_weight = voter.weight(registrar)?;
+    record.voter_weight = voter_weight;
     record.voter_weight_expiry = Some(Clock::get()?.slot);
 
+    emit!(WithdrawEvent {
+        registrar: ctx.accounts.registrar.key(),
+        voter: ctx.accounts.voter.key(),
+        deposit_entry_index,
+        destination: ctx.accounts.destination.key(),
+        amount,
+        before,
+        after,
+        voter_weight,
+    });
+
     Ok(())
 }
@@ -9,6 +9,7 @@ pub mod events;
 mod governance;
 mod instructions;
 pub mod state;
+mod token_interface;
 
 #[macro_use]
 extern crate static_assertions;
@@ -68,6 +69,7 @@ pub mod voter_stake_registry {
         instructions::create_registrar(ctx, registrar_bump)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn configure_voting_mint(
         ctx: Context<ConfigureVotingMint>,
         idx: u16,
@@ -88,6 +90,72 @@ pub mod voter_stake_registry {
         )
     }
 
+    pub fn configure_deposit_limits(
+        ctx: Context<ConfigureDepositLimits>,
+        idx: u16,
+        min_deposit_native: u64,
+        max_voter_deposited_native: u64,
+        max_deposited_native: u64,
+    ) -> Result<()> {
+        instructions::configure_deposit_limits(
+            ctx,
+            idx,
+            min_deposit_native,
+            max_voter_deposited_native,
+            max_deposited_native,
+        )
+    }
+
+    pub fn configure_lockup_curve(
+        ctx: Context<ConfigureLockupCurve>,
+        idx: u16,
+        kind: LockupCurveKind,
+        points: Vec<LockupCurvePoint>,
+    ) -> Result<()> {
+        instructions::configure_lockup_curve(ctx, idx, kind, points)
+    }
+
+    pub fn configure_lockup_limits(
+        ctx: Context<ConfigureLockupLimits>,
+        idx: u16,
+        allowed_lockup_kinds: u16,
+        max_lockup_days: u16,
+    ) -> Result<()> {
+        instructions::configure_lockup_limits(ctx, idx, allowed_lockup_kinds, max_lockup_days)
+    }
+
+    pub fn configure_max_vote_weight_mode(
+        ctx: Context<ConfigureMaxVoteWeightMode>,
+        mode: MaxVoteWeightMode,
+    ) -> Result<()> {
+        instructions::configure_max_vote_weight_mode(ctx, mode)
+    }
+
+    pub fn sync_realm_authority(ctx: Context<SyncRealmAuthority>) -> Result<()> {
+        instructions::sync_realm_authority(ctx)
+    }
+
+    pub fn remove_voting_mint(ctx: Context<RemoveVotingMint>, idx: u16) -> Result<()> {
+        instructions::remove_voting_mint(ctx, idx)
+    }
+
+    pub fn resize_registrar(ctx: Context<ResizeRegistrar>) -> Result<()> {
+        instructions::resize_registrar(ctx)
+    }
+
+    pub fn backfill_voting_mint_totals<'key, 'accounts, 'remaining, 'info>(
+        ctx: Context<'key, 'accounts, 'remaining, 'info, BackfillVotingMintTotals<'info>>,
+    ) -> Result<()> {
+        instructions::backfill_voting_mint_totals(ctx)
+    }
+
+    pub fn finish_voting_mint_backfill(
+        ctx: Context<FinishVotingMintBackfill>,
+        idx: u16,
+    ) -> Result<()> {
+        instructions::finish_voting_mint_backfill(ctx, idx)
+    }
+
     pub fn create_voter(
         ctx: Context<CreateVoter>,
         voter_bump: u8,
@@ -96,6 +164,27 @@ pub mod voter_stake_registry {
         instructions::create_voter(ctx, voter_bump, voter_weight_record_bump)
     }
 
+    pub fn resize_voter(ctx: Context<ResizeVoter>, deposit_entry_count: u8) -> Result<()> {
+        instructions::resize_voter(ctx, deposit_entry_count)
+    }
+
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+        instructions::set_delegate(ctx, delegate)
+    }
+
+    pub fn propose_voter_transfer(
+        ctx: Context<ProposeVoterTransfer>,
+        new_voter_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::propose_voter_transfer(ctx, new_voter_authority)
+    }
+
+    pub fn accept_voter_transfer<'key, 'accounts, 'remaining, 'info>(
+        ctx: Context<'key, 'accounts, 'remaining, 'info, AcceptVoterTransfer<'info>>,
+    ) -> Result<()> {
+        instructions::accept_voter_transfer(ctx)
+    }
+
     pub fn create_deposit_entry(
         ctx: Context<CreateDepositEntry>,
         deposit_entry_index: u8,
@@ -114,10 +203,34 @@ pub mod voter_stake_registry {
         )
     }
 
+    pub fn create_deposit_entry_with_cliff(
+        ctx: Context<CreateDepositEntry>,
+        deposit_entry_index: u8,
+        kind: LockupKind,
+        start_ts: Option<u64>,
+        periods: u32,
+        cliff_periods: u32,
+        allow_clawback: bool,
+    ) -> Result<()> {
+        instructions::create_deposit_entry_with_cliff(
+            ctx,
+            deposit_entry_index,
+            kind,
+            start_ts,
+            periods,
+            cliff_periods,
+            allow_clawback,
+        )
+    }
+
     pub fn deposit(ctx: Context<Deposit>, deposit_entry_index: u8, amount: u64) -> Result<()> {
         instructions::deposit(ctx, deposit_entry_index, amount)
     }
 
+    pub fn deposit_unlocked(ctx: Context<DepositUnlocked>, amount: u64) -> Result<()> {
+        instructions::deposit_unlocked(ctx, amount)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>, deposit_entry_index: u8, amount: u64) -> Result<()> {
         instructions::withdraw(ctx, deposit_entry_index, amount)
     }
@@ -145,10 +258,43 @@ pub mod voter_stake_registry {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn grant_with_cliff(
+        ctx: Context<Grant>,
+        voter_bump: u8,
+        voter_weight_record_bump: u8,
+        kind: LockupKind,
+        start_ts: Option<u64>,
+        periods: u32,
+        cliff_periods: u32,
+        allow_clawback: bool,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::grant_with_cliff(
+            ctx,
+            voter_bump,
+            voter_weight_record_bump,
+            kind,
+            start_ts,
+            periods,
+            cliff_periods,
+            allow_clawback,
+            amount,
+        )
+    }
+
     pub fn clawback(ctx: Context<Clawback>, deposit_entry_index: u8) -> Result<()> {
         instructions::clawback(ctx, deposit_entry_index)
     }
 
+    pub fn partial_clawback(
+        ctx: Context<Clawback>,
+        deposit_entry_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::partial_clawback(ctx, deposit_entry_index, amount)
+    }
+
     pub fn close_deposit_entry(
         ctx: Context<CloseDepositEntry>,
         deposit_entry_index: u8,
@@ -179,6 +325,21 @@ pub mod voter_stake_registry {
         )
     }
 
+    pub fn split_deposit_entry(
+        ctx: Context<SplitDepositEntry>,
+        source_deposit_entry_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::split_deposit_entry(ctx, source_deposit_entry_index, amount)
+    }
+
+    pub fn merge_deposit_entries(
+        ctx: Context<MergeDepositEntries>,
+        deposit_entry_indexes: Vec<u8>,
+    ) -> Result<()> {
+        instructions::merge_deposit_entries(ctx, deposit_entry_indexes)
+    }
+
     pub fn internal_transfer_unlocked(
         ctx: Context<InternalTransferUnlocked>,
         source_deposit_entry_index: u8,
@@ -197,6 +358,16 @@ pub mod voter_stake_registry {
         instructions::update_voter_weight_record(ctx)
     }
 
+    pub fn update_voter_weight_records<'key, 'accounts, 'remaining, 'info>(
+        ctx: Context<'key, 'accounts, 'remaining, 'info, UpdateVoterWeightRecords<'info>>,
+    ) -> Result<()> {
+        instructions::update_voter_weight_records(ctx)
+    }
+
+    pub fn create_max_voter_weight_record(ctx: Context<CreateMaxVoterWeightRecord>) -> Result<()> {
+        instructions::create_max_voter_weight_record(ctx)
+    }
+
     pub fn update_max_vote_weight(ctx: Context<UpdateMaxVoteWeight>) -> Result<()> {
         instructions::update_max_vote_weight(ctx)
     }
@@ -207,6 +378,10 @@ pub mod voter_stake_registry {
         instructions::close_voter(ctx)
     }
 
+    pub fn log_registrar_info(ctx: Context<LogRegistrarInfo>) -> Result<()> {
+        instructions::log_registrar_info(ctx)
+    }
+
     pub fn log_voter_info(
         ctx: Context<LogVoterInfo>,
         deposit_entry_begin: u8,
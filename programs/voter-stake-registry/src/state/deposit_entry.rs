@@ -1,6 +1,8 @@
 use crate::error::*;
 use crate::state::lockup::{Lockup, LockupKind};
+use crate::state::lockup_curve::*;
 use crate::state::voting_mint_config::VotingMintConfig;
+use anchor_lang::__private::bytemuck::{Pod, Zeroable};
 use anchor_lang::prelude::*;
 use std::cmp::min;
 use std::convert::TryFrom;
@@ -43,6 +45,10 @@ pub struct DepositEntry {
 const_assert!(std::mem::size_of::<DepositEntry>() == 32 + 2 * 8 + 3 + 29);
 const_assert!(std::mem::size_of::<DepositEntry>() % 8 == 0);
 
+// Needed to view the extra deposit entries of grown voter accounts, see VoterAccount.
+unsafe impl Zeroable for DepositEntry {}
+unsafe impl Pod for DepositEntry {}
+
 impl DepositEntry {
     /// # Voting Power Caclulation
     ///
@@ -61,6 +67,9 @@ impl DepositEntry {
     ///   - the VotingMintConfig providing the values for
     ///     baseline_vote_weight, max_extra_lockup_vote_weight, lockup_saturation_secs
     ///
+    /// Voting mints can replace the linear lockup_duration_factor with a
+    /// piecewise linear or stepped curve, see LockupCurveKind.
+    ///
     /// Linear vesting schedules can be thought of as a sequence of cliff-
     /// locked tokens and have the matching voting weight.
     ///
@@ -80,8 +89,14 @@ impl DepositEntry {
     ///
     /// ## Linear Vesting Lockup
     ///
-    /// Daily/monthly linear vesting can be calculated with series sum, see
-    /// voting_power_linear_vesting() below.
+    /// Daily/weekly/monthly/quarterly linear vesting can be calculated with
+    /// series sum, see voting_power_linear_vesting() below and
+    /// LockupCurve::factor_sum() for other lockup curves.
+    ///
+    /// ## Cliff Then Monthly Vesting Lockup
+    ///
+    /// Like monthly vesting, except that the vesting cliffs of all months
+    /// up to the end of the initial cliff are merged into that single cliff.
     ///
     pub fn voting_power(&self, voting_mint_config: &VotingMintConfig, curr_ts: i64) -> Result<u64> {
         let baseline_vote_weight =
@@ -91,7 +106,7 @@ impl DepositEntry {
         let locked_vote_weight = self.voting_power_locked(
             curr_ts,
             max_locked_vote_weight,
-            voting_mint_config.lockup_saturation_secs,
+            &voting_mint_config.lockup_curve(),
         )?;
         require_gte!(
             max_locked_vote_weight,
@@ -108,29 +123,30 @@ impl DepositEntry {
         &self,
         curr_ts: i64,
         max_locked_vote_weight: u64,
-        lockup_saturation_secs: u64,
+        curve: &LockupCurve,
     ) -> Result<u64> {
         if self.lockup.expired(curr_ts) || max_locked_vote_weight == 0 {
             return Ok(0);
         }
         match self.lockup.kind {
             LockupKind::None => Ok(0),
-            LockupKind::Daily => self.voting_power_linear_vesting(
-                curr_ts,
-                max_locked_vote_weight,
-                lockup_saturation_secs,
-            ),
-            LockupKind::Monthly => self.voting_power_linear_vesting(
-                curr_ts,
-                max_locked_vote_weight,
-                lockup_saturation_secs,
-            ),
-            LockupKind::Cliff => {
-                self.voting_power_cliff(curr_ts, max_locked_vote_weight, lockup_saturation_secs)
+            LockupKind::Daily => {
+                self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, curve)
+            }
+            LockupKind::Weekly => {
+                self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, curve)
+            }
+            LockupKind::Monthly => {
+                self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, curve)
             }
-            LockupKind::Constant => {
-                self.voting_power_cliff(curr_ts, max_locked_vote_weight, lockup_saturation_secs)
+            LockupKind::Quarterly => {
+                self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, curve)
             }
+            LockupKind::CliffMonthly => {
+                self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, curve)
+            }
+            LockupKind::Cliff => self.voting_power_cliff(curr_ts, max_locked_vote_weight, curve),
+            LockupKind::Constant => self.voting_power_cliff(curr_ts, max_locked_vote_weight, curve),
         }
     }
 
@@ -143,7 +159,7 @@ impl DepositEntry {
         curr_ts: i64,
         at_ts: i64,
         max_locked_vote_weight: u64,
-        lockup_saturation_secs: u64,
+        curve: &LockupCurve,
     ) -> Result<u64> {
         let mut altered = *self;
 
@@ -159,7 +175,7 @@ impl DepositEntry {
         // Other lockup types don't need changes, because the user
         // cannot reduce their lockup strength.
 
-        altered.voting_power_locked(at_ts, max_locked_vote_weight, lockup_saturation_secs)
+        altered.voting_power_locked(at_ts, max_locked_vote_weight, curve)
     }
 
     /// Vote power contribution from funds with linear vesting.
@@ -167,8 +183,14 @@ impl DepositEntry {
         &self,
         curr_ts: i64,
         max_locked_vote_weight: u64,
-        lockup_saturation_secs: u64,
+        curve: &LockupCurve,
     ) -> Result<u64> {
+        if curve.kind != LockupCurveKind::Linear {
+            let factor = curve.factor(self.lockup.seconds_left(curr_ts));
+            return Ok(apply_factor_sum(max_locked_vote_weight, factor as u128, 1));
+        }
+
+        let lockup_saturation_secs = curve.lockup_saturation_secs;
         let remaining = min(self.lockup.seconds_left(curr_ts), lockup_saturation_secs);
         Ok(u64::try_from(
             (max_locked_vote_weight as u128)
@@ -185,8 +207,9 @@ impl DepositEntry {
         &self,
         curr_ts: i64,
         max_locked_vote_weight: u64,
-        lockup_saturation_secs: u64,
+        curve: &LockupCurve,
     ) -> Result<u64> {
+        let lockup_saturation_secs = curve.lockup_saturation_secs;
         let periods_left = self.lockup.periods_left(curr_ts)?;
         let periods_total = self.lockup.periods_total()?;
         let period_secs = self.lockup.kind.period_secs() as u64;
@@ -235,50 +258,60 @@ impl DepositEntry {
         //                      = q * (q - 1) / 2
         //
 
-        let secs_to_closest_cliff = self
-            .lockup
-            .seconds_left(curr_ts)
+        // Before the end of a CliffMonthly lockup's cliff, the first
+        // cliff_periods vesting cliffs all unlock together at its end, and the
+        // others follow in period_secs steps.
+        let cliff_periods_left = self.lockup.cliff_periods_left(curr_ts);
+        let vesting_periods_left = periods_left.checked_sub(cliff_periods_left).unwrap();
+        let seconds_left = self.lockup.seconds_left(curr_ts);
+        let secs_to_first_vesting = seconds_left
             .checked_sub(
                 period_secs
-                    .checked_mul(periods_left.saturating_sub(1))
+                    .checked_mul(vesting_periods_left.saturating_sub(1))
                     .unwrap(),
             )
             .unwrap();
+        let secs_to_cliff_end =
+            seconds_left.saturating_sub(period_secs.checked_mul(vesting_periods_left).unwrap());
+        let secs_to_closest_cliff = if cliff_periods_left > 0 {
+            secs_to_cliff_end
+        } else {
+            secs_to_first_vesting
+        };
 
         if secs_to_closest_cliff >= lockup_saturation_secs {
             return Ok(max_locked_vote_weight);
         }
 
+        // Other curves sum up the factors of the vesting cliffs segment by
+        // segment, generalizing the computation below.
+        if curve.kind != LockupCurveKind::Linear {
+            let cliff_factor_sum =
+                cliff_periods_left as u128 * curve.factor(secs_to_cliff_end) as u128;
+            let factor_sum =
+                curve.factor_sum(secs_to_first_vesting, period_secs, vesting_periods_left);
+            return Ok(apply_factor_sum(
+                max_locked_vote_weight,
+                cliff_factor_sum + factor_sum,
+                periods_total,
+            ));
+        }
+
+        // The merged vesting cliffs at the end of the cliff contribute
+        // cliff_periods_left times their lockup secs.
+        let cliff_lockup_secs =
+            cliff_periods_left as u128 * min(secs_to_cliff_end, lockup_saturation_secs) as u128;
+
         // In the example above, periods_total was 5.
         let denominator = periods_total.checked_mul(lockup_saturation_secs).unwrap();
 
-        let lockup_saturation_periods = (lockup_saturation_secs
-            .saturating_sub(secs_to_closest_cliff)
-            .checked_add(period_secs)
-            .unwrap())
-        .checked_div(period_secs)
-        .unwrap();
-        let q = min(lockup_saturation_periods, periods_left);
-        let r = periods_left.saturating_sub(q);
-
-        // Sum of the full periods left for all remaining vesting cliffs.
-        //
-        // Examples:
-        // - if there are 3 periods left, meaning three vesting cliffs in the future:
-        //   one has only a fractional period left and contributes 0
-        //   the next has one full period left
-        //   and the next has two full periods left
-        //   so sums to 3 = 3 * 2 / 2
-        // - if there's only one period left, the sum is 0
-        let sum_full_periods = q.checked_mul(q.saturating_sub(1)).unwrap() / 2;
-
-        // Total number of seconds left over all periods_left remaining vesting cliffs
-        let lockup_secs_fractional = q.checked_mul(secs_to_closest_cliff).unwrap();
-        let lockup_secs_full = sum_full_periods.checked_mul(period_secs).unwrap();
-        let lockup_secs_saturated = r.checked_mul(lockup_saturation_secs).unwrap();
-        let lockup_secs = lockup_secs_fractional as u128
-            + lockup_secs_full as u128
-            + lockup_secs_saturated as u128;
+        let lockup_secs = cliff_lockup_secs
+            + linear_vesting_lockup_secs(
+                secs_to_first_vesting,
+                period_secs,
+                vesting_periods_left,
+                lockup_saturation_secs,
+            );
 
         Ok(u64::try_from(
             (max_locked_vote_weight as u128)
@@ -299,7 +332,10 @@ impl DepositEntry {
         match self.lockup.kind {
             LockupKind::None => Ok(self.amount_initially_locked_native),
             LockupKind::Daily => self.vested_linearly(curr_ts),
+            LockupKind::Weekly => self.vested_linearly(curr_ts),
             LockupKind::Monthly => self.vested_linearly(curr_ts),
+            LockupKind::Quarterly => self.vested_linearly(curr_ts),
+            LockupKind::CliffMonthly => self.vested_linearly(curr_ts),
             LockupKind::Cliff => Ok(0),
             LockupKind::Constant => Ok(0),
         }
@@ -376,11 +412,64 @@ impl DepositEntry {
         require_eq!(self.vested(curr_ts)?, 0, VsrError::InternalProgramError);
         Ok(())
     }
+
+    /// Checks that tokens may be moved from this entry to other entries.
+    ///
+    /// Allowing that for clawback-enabled deposits could be used to avoid
+    /// clawback by making proposal instructions target the wrong entry index.
+    pub fn assert_tokens_movable(&self) -> Result<()> {
+        require!(
+            !self.allow_clawback,
+            VsrError::InvalidChangeToClawbackDepositEntry
+        );
+        Ok(())
+    }
+}
+
+/// Sum of the lockup seconds of `periods_left` vesting cliffs, the first being
+/// `secs_to_closest_cliff` away and the others `period_secs` apart, each capped
+/// at `lockup_saturation_secs`. See voting_power_linear_vesting().
+fn linear_vesting_lockup_secs(
+    secs_to_closest_cliff: u64,
+    period_secs: u64,
+    periods_left: u64,
+    lockup_saturation_secs: u64,
+) -> u128 {
+    if secs_to_closest_cliff >= lockup_saturation_secs {
+        return periods_left as u128 * lockup_saturation_secs as u128;
+    }
+
+    let lockup_saturation_periods = (lockup_saturation_secs
+        .saturating_sub(secs_to_closest_cliff)
+        .checked_add(period_secs)
+        .unwrap())
+    .checked_div(period_secs)
+    .unwrap();
+    let q = min(lockup_saturation_periods, periods_left);
+    let r = periods_left.saturating_sub(q);
+
+    // Sum of the full periods left for all remaining vesting cliffs.
+    //
+    // Examples:
+    // - if there are 3 periods left, meaning three vesting cliffs in the future:
+    //   one has only a fractional period left and contributes 0
+    //   the next has one full period left
+    //   and the next has two full periods left
+    //   so sums to 3 = 3 * 2 / 2
+    // - if there's only one period left, the sum is 0
+    let sum_full_periods = q.checked_mul(q.saturating_sub(1)).unwrap() / 2;
+
+    // Total number of seconds left over all periods_left remaining vesting cliffs
+    let lockup_secs_fractional = q.checked_mul(secs_to_closest_cliff).unwrap();
+    let lockup_secs_full = sum_full_periods.checked_mul(period_secs).unwrap();
+    let lockup_secs_saturated = r.checked_mul(lockup_saturation_secs).unwrap();
+    lockup_secs_fractional as u128 + lockup_secs_full as u128 + lockup_secs_saturated as u128
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::lockup::SECS_PER_MONTH;
     use crate::LockupKind::{Constant, Daily};
 
     #[test]
@@ -447,6 +536,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn resolve_vesting_with_cliff() -> Result<()> {
+        // 2 month cliff, then 2 more months of vesting
+        let mut deposit = DepositEntry {
+            amount_deposited_native: 40,
+            amount_initially_locked_native: 40,
+            lockup: Lockup::new_with_cliff(LockupKind::CliffMonthly, 1000, 1000, 2, 2).unwrap(),
+            is_used: true,
+            allow_clawback: false,
+            voting_mint_config_idx: 0,
+            reserved: [0; 29],
+        };
+        let month = deposit.lockup.kind.period_secs() as i64;
+        let amount_initially_locked =
+            |deposit: &DepositEntry| deposit.amount_initially_locked_native;
+        assert_eq!(deposit.lockup.end_ts(), 1000 + 4 * month);
+        assert_eq!(deposit.lockup.cliff_end_ts(), 1000 + 2 * month);
+
+        // nothing vests during the cliff
+        let mut time = 1001 + month;
+        assert_eq!(deposit.vested(time).unwrap(), 0);
+        assert_eq!(deposit.amount_locked(time), 40);
+        assert_eq!(deposit.lockup.periods_left(time).unwrap(), 4);
+        deposit.resolve_vesting(time).unwrap(); // no effect
+        assert_eq!(deposit.lockup.cliff_periods(), 2);
+        assert_eq!(deposit.lockup.start_ts(), 1000);
+
+        // the cliff vests two months' worth at once
+        time = 1000 + 2 * month;
+        assert_eq!(deposit.vested(time).unwrap(), 20);
+        assert_eq!(deposit.lockup.periods_left(time).unwrap(), 2);
+        deposit.resolve_vesting(time).unwrap();
+        assert_eq!(deposit.vested(time).unwrap(), 0);
+        assert_eq!(deposit.amount_unlocked(time), 20);
+        assert_eq!(deposit.lockup.cliff_periods(), 0);
+        assert_eq!(deposit.lockup.cliff_end_ts(), time);
+        assert_eq!(deposit.lockup.periods_total().unwrap(), 2);
+        assert_eq!(amount_initially_locked(&deposit), 20);
+
+        // and monthly vesting afterwards
+        time = 1000 + 3 * month;
+        assert_eq!(deposit.vested(time).unwrap(), 10);
+        assert_eq!(deposit.lockup.periods_left(time).unwrap(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn cliff_vesting_voting_power() -> Result<()> {
+        let month = SECS_PER_MONTH as i64;
+        let saturation = LockupCurve::linear(6 * SECS_PER_MONTH);
+        let deposit = |cliff_periods: u32, periods: u32| DepositEntry {
+            amount_deposited_native: 12_000,
+            amount_initially_locked_native: 12_000,
+            lockup: Lockup::new_with_cliff(LockupKind::CliffMonthly, 0, 0, cliff_periods, periods)
+                .unwrap(),
+            is_used: true,
+            allow_clawback: false,
+            voting_mint_config_idx: 0,
+            reserved: [0; 29],
+        };
+        let power = |d: &DepositEntry, ts: i64| d.voting_power_locked(ts, 12_000, &saturation);
+
+        // Without a cliff, it's regular monthly vesting.
+        let mut monthly = deposit(0, 12);
+        monthly.lockup.kind = LockupKind::Monthly;
+        for ts in [0, month / 2, 5 * month, 11 * month + 1] {
+            assert_eq!(power(&deposit(0, 12), ts)?, power(&monthly, ts)?);
+        }
+
+        // A cliff as long as the whole lockup is a plain cliff lockup.
+        let mut cliff = deposit(0, 12);
+        cliff.lockup.kind = LockupKind::Cliff;
+        for ts in [0, month / 2, 8 * month, 11 * month + 1] {
+            assert_eq!(power(&deposit(12, 0), ts)?, power(&cliff, ts)?);
+        }
+
+        // 3 month cliff, 9 vesting months, 1000 tokens each: at 1 month the
+        // cliff holds 3000 tokens for 2 more months, the rest as monthly vesting
+        // with the first unlock 3 months away and saturating after 6 months.
+        let lockup_months = 2 * 3000 + (3 + 4 + 5 + 6 * 6) * 1000;
+        assert_eq!(power(&deposit(3, 9), month)?, lockup_months / 6);
+
+        // After the cliff, it's monthly vesting of the rest.
+        let mut monthly = deposit(0, 12);
+        monthly.lockup.kind = LockupKind::Monthly;
+        for ts in [3 * month, 4 * month + 7, 11 * month + 1] {
+            assert_eq!(power(&deposit(3, 9), ts)?, power(&monthly, ts)?);
+        }
+
+        Ok(())
+    }
+
     #[test]
     pub fn far_future_lockup_start_test() -> Result<()> {
         // Check that voting power stays correct even if the lockup is very far in the
@@ -461,7 +643,9 @@ mod tests {
                 start_ts: lockup_start,
                 end_ts: lockup_start + 2 * day,
                 kind: Daily,
-                reserved: [0; 15],
+                cliff_periods: 0,
+                reserved1: [0; 3],
+                reserved: [0; 8],
             },
             is_used: true,
             allow_clawback: false,
@@ -475,8 +659,16 @@ mod tests {
             max_extra_lockup_vote_weight_scaled_factor: 1_000_000_000, // 1x
             lockup_saturation_secs: saturation as u64,
             digit_shift: 0,
-            reserved1: [0; 7],
-            reserved2: [0; 7],
+            deprecated: false,
+            deposited_native_tracked: true,
+            lockup_curve_kind: LockupCurveKind::Linear,
+            lockup_curve_point_count: 0,
+            reserved1: 0,
+            max_lockup_days: 0,
+            deposited_native: 0,
+            lockup_curve_points: Default::default(),
+            min_deposit_native: 0,
+            max_voter_deposited_native: 0,
         };
 
         let baseline_vote_weight =
@@ -533,7 +725,9 @@ mod tests {
                 start_ts: start,
                 end_ts: start + 5 * day,
                 kind: Constant,
-                reserved: [0; 15],
+                cliff_periods: 0,
+                reserved1: [0; 3],
+                reserved: [0; 8],
             },
             is_used: true,
             allow_clawback: false,
@@ -547,7 +741,7 @@ mod tests {
                     start + curr_offset,
                     start + at_offset,
                     100,
-                    saturation,
+                    &LockupCurve::linear(saturation),
                 )
                 .unwrap()
         };
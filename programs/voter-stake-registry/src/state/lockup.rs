@@ -11,14 +11,20 @@ vote_weight_record!(crate::ID);
 /// Seconds in one day.
 pub const SECS_PER_DAY: u64 = 86_400;
 
+/// Seconds in one week.
+pub const SECS_PER_WEEK: u64 = 7 * SECS_PER_DAY;
+
 /// Seconds in one month.
 pub const SECS_PER_MONTH: u64 = 365 * SECS_PER_DAY / 12;
 
+/// Seconds in one quarter.
+pub const SECS_PER_QUARTER: u64 = 3 * SECS_PER_MONTH;
+
 /// Maximum acceptable number of lockup periods.
 ///
 /// In the linear vesting voting power computation, a factor like
 /// `periods^2 * period_secs` is used. With the current setting
-/// that would be 36500^2 * SECS_PER_MONTH << 2^64.
+/// that would be 73000^2 * SECS_PER_QUARTER << 2^64.
 ///
 /// This setting limits the maximum lockup duration for lockup methods
 /// with daily periods to 200 years.
@@ -43,10 +49,17 @@ pub struct Lockup {
     /// Type of lockup.
     pub kind: LockupKind,
 
+    // Empty bytes for future upgrades, keeping cliff_periods aligned.
+    pub reserved1: [u8; 3],
+
+    /// Number of periods after start_ts before anything vests, for
+    /// CliffMonthly lockups. Zero for all other kinds.
+    pub(crate) cliff_periods: u32,
+
     // Empty bytes for future upgrades.
-    pub reserved: [u8; 15],
+    pub reserved: [u8; 8],
 }
-const_assert!(std::mem::size_of::<Lockup>() == 2 * 8 + 1 + 15);
+const_assert!(std::mem::size_of::<Lockup>() == 2 * 8 + 1 + 3 + 4 + 8);
 const_assert!(std::mem::size_of::<Lockup>() % 8 == 0);
 
 impl Default for Lockup {
@@ -55,7 +68,9 @@ impl Default for Lockup {
             kind: LockupKind::None,
             start_ts: 0,
             end_ts: 0,
-            reserved: [0; 15],
+            cliff_periods: 0,
+            reserved1: [0; 3],
+            reserved: [0; 8],
         }
     }
 }
@@ -83,10 +98,91 @@ impl Lockup {
                         .unwrap(),
                 )
                 .unwrap(),
-            reserved: [0; 15],
+            cliff_periods: 0,
+            reserved1: [0; 3],
+            reserved: [0; 8],
         })
     }
 
+    /// Create lockup for a given period, with a cliff of `cliff_periods` before it.
+    ///
+    /// Nothing vests for the first `cliff_periods`; at the cliff the share of
+    /// all periods up to it vests at once. Afterwards `periods` more periods
+    /// vest one by one. Cliffs are only allowed for CliffMonthly lockups.
+    pub fn new_with_cliff(
+        kind: LockupKind,
+        curr_ts: i64,
+        start_ts: i64,
+        cliff_periods: u32,
+        periods: u32,
+    ) -> Result<Self> {
+        require!(
+            cliff_periods == 0 || kind == LockupKind::CliffMonthly,
+            VsrError::InvalidLockupCliff
+        );
+        let total_periods = cliff_periods
+            .checked_add(periods)
+            .ok_or_else(|| error!(VsrError::InvalidLockupPeriod))?;
+        let mut lockup = Self::new_from_periods(kind, curr_ts, start_ts, total_periods)?;
+        lockup.cliff_periods = cliff_periods;
+        Ok(lockup)
+    }
+
+    /// Start of the lockup.
+    pub fn start_ts(&self) -> i64 {
+        self.start_ts
+    }
+
+    /// End of the lockup. For constant lockups this doesn't move forward
+    /// as time passes, see seconds_left().
+    pub fn end_ts(&self) -> i64 {
+        self.end_ts
+    }
+
+    /// Number of periods in the cliff before anything vests.
+    pub fn cliff_periods(&self) -> u32 {
+        self.cliff_periods
+    }
+
+    /// End of the cliff, when the first tokens vest. Same as start_ts for
+    /// lockups without a cliff.
+    pub fn cliff_end_ts(&self) -> i64 {
+        self.start_ts
+            .checked_add(
+                i64::try_from(
+                    (self.cliff_periods as u64)
+                        .checked_mul(self.kind.period_secs())
+                        .unwrap(),
+                )
+                .unwrap(),
+            )
+            .unwrap()
+    }
+
+    /// Number of periods that vest together at the end of the cliff,
+    /// zero once the cliff has passed.
+    pub fn cliff_periods_left(&self, curr_ts: i64) -> u64 {
+        if curr_ts < self.cliff_end_ts() {
+            self.cliff_periods as u64
+        } else {
+            0
+        }
+    }
+
+    /// Checks that moving locked tokens from this lockup to `target` can't
+    /// make them vest before the end of this lockup's cliff.
+    pub fn require_cliff_kept(&self, target: &Lockup, curr_ts: i64) -> Result<()> {
+        if self.cliff_periods_left(curr_ts) == 0 || !target.kind.is_vesting() {
+            return Ok(());
+        }
+        require_gte!(
+            target.cliff_end_ts(),
+            self.cliff_end_ts(),
+            VsrError::InvalidLockupCliff
+        );
+        Ok(())
+    }
+
     /// True when the lockup is finished.
     pub fn expired(&self, curr_ts: i64) -> bool {
         self.seconds_left(curr_ts) == 0
@@ -106,13 +202,14 @@ impl Lockup {
     }
 
     /// Returns the number of periods left on the lockup.
-    /// Returns 0 after lockup has expired and periods_total before start_ts.
+    /// Returns 0 after lockup has expired and periods_total before start_ts,
+    /// or before the end of the cliff.
     pub fn periods_left(&self, curr_ts: i64) -> Result<u64> {
         let period_secs = self.kind.period_secs();
         if period_secs == 0 {
             return Ok(0);
         }
-        if curr_ts < self.start_ts {
+        if curr_ts < self.cliff_end_ts() {
             return self.periods_total();
         }
         Ok(self
@@ -124,7 +221,8 @@ impl Lockup {
     }
 
     /// Returns the current period in the vesting schedule.
-    /// Will report periods_total() after lockup has expired and 0 before start_ts.
+    /// Will report periods_total() after lockup has expired and 0 before start_ts
+    /// or the end of the cliff.
     pub fn period_current(&self, curr_ts: i64) -> Result<u64> {
         Ok(self
             .periods_total()?
@@ -152,6 +250,10 @@ impl Lockup {
             .start_ts
             .checked_add(i64::try_from(periods.checked_mul(period_secs).unwrap()).unwrap())
             .unwrap();
+        // Periods only pass once the cliff has, so it's over if any did.
+        self.cliff_periods = self
+            .cliff_periods
+            .saturating_sub(u32::try_from(periods).unwrap());
         require_gte!(self.end_ts, self.start_ts, VsrError::InternalProgramError);
         require_eq!(
             self.period_current(curr_ts)?,
@@ -180,6 +282,20 @@ pub enum LockupKind {
     /// Lock up permanently. The number of days specified becomes the minimum
     /// unlock period when the deposit (or a part of it) is changed to Cliff.
     Constant,
+
+    /// Lock up for a number of weeks, where a linear fraction vests each week.
+    Weekly,
+
+    /// Lock up for a number of quarters, where a linear fraction vests each quarter.
+    Quarterly,
+
+    /// Lock up for a cliff of a number of months, then a number of months where
+    /// a linear fraction vests each month. At the cliff, the fraction for all
+    /// months up to it vests at once.
+    ///
+    /// For example a 12 month cliff and 36 vesting months vests 12/48 of the
+    /// tokens after a year and 1/48 each month after that.
+    CliffMonthly,
 }
 
 impl LockupKind {
@@ -194,17 +310,33 @@ impl LockupKind {
             LockupKind::Monthly => SECS_PER_MONTH,
             LockupKind::Cliff => SECS_PER_DAY, // arbitrary choice
             LockupKind::Constant => SECS_PER_DAY, // arbitrary choice
+            LockupKind::Weekly => SECS_PER_WEEK,
+            LockupKind::Quarterly => SECS_PER_QUARTER,
+            LockupKind::CliffMonthly => SECS_PER_MONTH,
         }
     }
 
+    /// The bit of this kind in Registrar::allowed_lockup_kinds
+    pub fn mask(&self) -> u16 {
+        1 << (*self as u16)
+    }
+
     /// Lockups cannot decrease in strictness
+    ///
+    /// Vesting lockups with longer periods are stricter, because their tokens
+    /// vest later. CliffMonthly lockups may have no cliff left and vest
+    /// monthly, so they rank below Quarterly. A pending cliff is protected
+    /// separately, see Lockup::require_cliff_kept().
     pub fn strictness(&self) -> u8 {
         match self {
             LockupKind::None => 0,
             LockupKind::Daily => 1,
-            LockupKind::Monthly => 2,
-            LockupKind::Cliff => 3, // can freely move between Cliff and Constant
-            LockupKind::Constant => 3,
+            LockupKind::Weekly => 2,
+            LockupKind::Monthly => 3,
+            LockupKind::CliffMonthly => 4,
+            LockupKind::Quarterly => 5,
+            LockupKind::Cliff => 6, // can freely move between Cliff and Constant
+            LockupKind::Constant => 6,
         }
     }
 
@@ -215,6 +347,9 @@ impl LockupKind {
             LockupKind::Monthly => true,
             LockupKind::Cliff => false,
             LockupKind::Constant => false,
+            LockupKind::Weekly => true,
+            LockupKind::Quarterly => true,
+            LockupKind::CliffMonthly => true,
         }
     }
 }
@@ -223,6 +358,7 @@ impl LockupKind {
 mod tests {
     use super::*;
     use crate::state::deposit_entry::DepositEntry;
+    use crate::state::lockup_curve::LockupCurve;
 
     // intentionally not a multiple of a day
     const MAX_SECS_LOCKED: u64 = 365 * 24 * 60 * 60 + 7 * 60 * 60;
@@ -252,6 +388,114 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn period_computations_weekly_quarterly() -> Result<()> {
+        for (kind, period) in [
+            (LockupKind::Weekly, SECS_PER_WEEK as i64),
+            (LockupKind::Quarterly, SECS_PER_QUARTER as i64),
+        ] {
+            let lockup = Lockup::new_from_periods(kind, 1000, 1000, 4)?;
+            assert_eq!(lockup.end_ts(), 1000 + 4 * period);
+            assert_eq!(lockup.periods_total()?, 4);
+            assert_eq!(lockup.periods_left(999)?, 4);
+            assert_eq!(lockup.period_current(1000 + period - 1)?, 0);
+            assert_eq!(lockup.periods_left(1000 + period - 1)?, 4);
+            assert_eq!(lockup.period_current(1000 + period)?, 1);
+            assert_eq!(lockup.periods_left(1000 + period)?, 3);
+            assert_eq!(lockup.period_current(1000 + 4 * period - 1)?, 3);
+            assert_eq!(lockup.periods_left(1000 + 4 * period - 1)?, 1);
+            assert_eq!(lockup.period_current(1000 + 4 * period)?, 4);
+            assert_eq!(lockup.periods_left(1000 + 4 * period)?, 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn period_computations_cliff_monthly() -> Result<()> {
+        let month = SECS_PER_MONTH as i64;
+        let lockup = Lockup::new_with_cliff(LockupKind::CliffMonthly, 1000, 1000, 3, 2)?;
+        assert_eq!(lockup.end_ts(), 1000 + 5 * month);
+        assert_eq!(lockup.cliff_end_ts(), 1000 + 3 * month);
+        assert_eq!(lockup.periods_total()?, 5);
+        // nothing vests before the end of the cliff
+        assert_eq!(lockup.periods_left(999)?, 5);
+        assert_eq!(lockup.cliff_periods_left(999), 3);
+        assert_eq!(lockup.periods_left(1000 + 2 * month)?, 5);
+        assert_eq!(lockup.period_current(1000 + 3 * month - 1)?, 0);
+        assert_eq!(lockup.periods_left(1000 + 3 * month - 1)?, 5);
+        assert_eq!(lockup.cliff_periods_left(1000 + 3 * month - 1), 3);
+        // then the cliff's periods all at once, and monthly afterwards
+        assert_eq!(lockup.period_current(1000 + 3 * month)?, 3);
+        assert_eq!(lockup.periods_left(1000 + 3 * month)?, 2);
+        assert_eq!(lockup.cliff_periods_left(1000 + 3 * month), 0);
+        assert_eq!(lockup.periods_left(1000 + 4 * month)?, 1);
+        assert_eq!(lockup.periods_left(1000 + 5 * month)?, 0);
+
+        assert!(Lockup::new_with_cliff(LockupKind::Monthly, 1000, 1000, 3, 2).is_err());
+        assert!(Lockup::new_with_cliff(LockupKind::Monthly, 1000, 1000, 0, 2).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    pub fn cliff_kept() -> Result<()> {
+        let month = SECS_PER_MONTH as i64;
+        let source = Lockup::new_with_cliff(LockupKind::CliffMonthly, 0, 0, 12, 36)?;
+        let later_cliff = Lockup::new_with_cliff(LockupKind::CliffMonthly, 0, 0, 13, 36)?;
+        let earlier_cliff = Lockup::new_with_cliff(LockupKind::CliffMonthly, 0, 0, 11, 48)?;
+        let quarterly = Lockup::new_from_periods(LockupKind::Quarterly, 0, 0, 20)?;
+        let cliff = Lockup::new_from_periods(LockupKind::Cliff, 0, 0, 365)?;
+
+        assert!(source.require_cliff_kept(&later_cliff, 0).is_ok());
+        assert!(source.require_cliff_kept(&cliff, 0).is_ok());
+        assert!(source.require_cliff_kept(&earlier_cliff, 0).is_err());
+        assert!(source.require_cliff_kept(&quarterly, 0).is_err());
+        // once the cliff has passed, there's nothing to keep
+        assert!(source.require_cliff_kept(&quarterly, 12 * month).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    pub fn strictness_ordering() {
+        let ordered = [
+            LockupKind::None,
+            LockupKind::Daily,
+            LockupKind::Weekly,
+            LockupKind::Monthly,
+            LockupKind::CliffMonthly,
+            LockupKind::Quarterly,
+            LockupKind::Cliff,
+        ];
+        for pair in ordered.windows(2) {
+            assert!(pair[0].strictness() < pair[1].strictness());
+        }
+        assert_eq!(
+            LockupKind::Cliff.strictness(),
+            LockupKind::Constant.strictness()
+        );
+        assert!(LockupKind::Weekly.is_vesting());
+        assert!(LockupKind::Quarterly.is_vesting());
+        assert!(LockupKind::CliffMonthly.is_vesting());
+    }
+
+    #[test]
+    pub fn kind_masks_are_distinct() {
+        let kinds = [
+            LockupKind::None,
+            LockupKind::Daily,
+            LockupKind::Monthly,
+            LockupKind::Cliff,
+            LockupKind::Constant,
+            LockupKind::Weekly,
+            LockupKind::Quarterly,
+            LockupKind::CliffMonthly,
+        ];
+        let all = kinds.iter().fold(0u16, |mask, kind| {
+            assert_eq!(mask & kind.mask(), 0);
+            mask | kind.mask()
+        });
+        assert_eq!(all, 0xff);
+    }
+
     #[test]
     pub fn days_left_start() -> Result<()> {
         run_test_days_left(TestDaysLeft {
@@ -378,6 +622,66 @@ mod tests {
         })
     }
 
+    #[test]
+    pub fn weeks_left_start() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Weekly,
+            expected_periods_left: 10,
+            periods_total: 10.0,
+            curr_period: 0.,
+        })
+    }
+
+    #[test]
+    pub fn weeks_left_one_and_a_half() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Weekly,
+            expected_periods_left: 9,
+            periods_total: 10.0,
+            curr_period: 1.5,
+        })
+    }
+
+    #[test]
+    pub fn weeks_left_eleven() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Weekly,
+            expected_periods_left: 0,
+            periods_total: 10.0,
+            curr_period: 11.,
+        })
+    }
+
+    #[test]
+    pub fn quarters_left_start() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Quarterly,
+            expected_periods_left: 8,
+            periods_total: 8.0,
+            curr_period: 0.,
+        })
+    }
+
+    #[test]
+    pub fn quarters_left_one_half() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Quarterly,
+            expected_periods_left: 8,
+            periods_total: 8.0,
+            curr_period: 0.5,
+        })
+    }
+
+    #[test]
+    pub fn quarters_left_seven_and_a_half() -> Result<()> {
+        run_test_periods_left(TestPeriodsLeft {
+            kind: LockupKind::Quarterly,
+            expected_periods_left: 1,
+            periods_total: 8.0,
+            curr_period: 7.5,
+        })
+    }
+
     #[test]
     pub fn voting_power_cliff_warmup() -> Result<()> {
         // 10 tokens with 6 decimals.
@@ -731,6 +1035,123 @@ mod tests {
         })
     }
 
+    #[test]
+    pub fn voting_power_weekly_warmup() -> Result<()> {
+        // 10 tokens with 6 decimals.
+        let amount_deposited = 10 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, -3.5, 10, 7.0),
+            amount_deposited,
+            days_total: 70.0,
+            curr_day: -3.5,
+            kind: LockupKind::Weekly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_weekly_start() -> Result<()> {
+        // 10 tokens with 6 decimals.
+        let amount_deposited = 10 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 0.0, 10, 7.0),
+            amount_deposited,
+            days_total: 70.0,
+            curr_day: 0.0,
+            kind: LockupKind::Weekly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_weekly_ten_and_a_half_days() -> Result<()> {
+        // 10 tokens with 6 decimals.
+        let amount_deposited = 10 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 10.5, 10, 7.0),
+            amount_deposited,
+            days_total: 70.0,
+            curr_day: 10.5,
+            kind: LockupKind::Weekly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_weekly_last_week() -> Result<()> {
+        // 10 tokens with 6 decimals.
+        let amount_deposited = 10 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 65.0, 10, 7.0),
+            amount_deposited,
+            days_total: 70.0,
+            curr_day: 65.0,
+            kind: LockupKind::Weekly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_weekly_end() -> Result<()> {
+        // 10 tokens with 6 decimals.
+        let amount_deposited = 10 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: 0,
+            amount_deposited,
+            days_total: 70.0,
+            curr_day: 70.0,
+            kind: LockupKind::Weekly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_quarterly_start() -> Result<()> {
+        // 8 tokens with 6 decimals, vesting over two years.
+        let amount_deposited = 8 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 0.0, 8, 91.25),
+            amount_deposited,
+            days_total: 8.0 * 91.25,
+            curr_day: 0.0,
+            kind: LockupKind::Quarterly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_quarterly_warmup() -> Result<()> {
+        // 8 tokens with 6 decimals, vesting over two years.
+        let amount_deposited = 8 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, -10.0, 8, 91.25),
+            amount_deposited,
+            days_total: 8.0 * 91.25,
+            curr_day: -10.0,
+            kind: LockupKind::Quarterly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_quarterly_one_and_a_half() -> Result<()> {
+        // 8 tokens with 6 decimals, vesting over two years.
+        let amount_deposited = 8 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 136.875, 8, 91.25),
+            amount_deposited,
+            days_total: 8.0 * 91.25,
+            curr_day: 136.875,
+            kind: LockupKind::Quarterly,
+        })
+    }
+
+    #[test]
+    pub fn voting_power_quarterly_last_quarter() -> Result<()> {
+        // 8 tokens with 6 decimals, vesting over two years.
+        let amount_deposited = 8 * 1_000_000;
+        run_test_voting_power(TestVotingPower {
+            expected_voting_power: locked_vesting_power(amount_deposited, 700.0, 8, 91.25),
+            amount_deposited,
+            days_total: 8.0 * 91.25,
+            curr_day: 700.0,
+            kind: LockupKind::Quarterly,
+        })
+    }
+
     struct TestDaysLeft {
         expected_days_left: u64,
         days_total: f64,
@@ -743,6 +1164,13 @@ mod tests {
         curr_month: f64,
     }
 
+    struct TestPeriodsLeft {
+        kind: LockupKind,
+        expected_periods_left: u64,
+        periods_total: f64,
+        curr_period: f64,
+    }
+
     struct TestVotingPower {
         amount_deposited: u64,
         days_total: f64,
@@ -759,7 +1187,9 @@ mod tests {
             kind: LockupKind::Cliff,
             start_ts,
             end_ts,
-            reserved: [0u8; 15],
+            cliff_periods: 0,
+            reserved1: [0u8; 3],
+            reserved: [0u8; 8],
         };
         let days_left = l.periods_left(curr_ts)?;
         assert_eq!(days_left, t.expected_days_left);
@@ -774,13 +1204,33 @@ mod tests {
             kind: LockupKind::Monthly,
             start_ts,
             end_ts,
-            reserved: [0u8; 15],
+            cliff_periods: 0,
+            reserved1: [0u8; 3],
+            reserved: [0u8; 8],
         };
         let months_left = l.periods_left(curr_ts)?;
         assert_eq!(months_left, t.expected_months_left);
         Ok(())
     }
 
+    fn run_test_periods_left(t: TestPeriodsLeft) -> Result<()> {
+        let start_ts = 1634929833;
+        let period_secs = t.kind.period_secs() as f64;
+        let end_ts = start_ts + (period_secs * t.periods_total).round() as i64;
+        let curr_ts = start_ts + (period_secs * t.curr_period).round() as i64;
+        let l = Lockup {
+            kind: t.kind,
+            start_ts,
+            end_ts,
+            cliff_periods: 0,
+            reserved1: [0u8; 3],
+            reserved: [0u8; 8],
+        };
+        let periods_left = l.periods_left(curr_ts)?;
+        assert_eq!(periods_left, t.expected_periods_left);
+        Ok(())
+    }
+
     fn run_test_voting_power(t: TestVotingPower) -> Result<()> {
         let start_ts = 1634929833;
         let end_ts = start_ts + days_to_secs(t.days_total);
@@ -794,12 +1244,18 @@ mod tests {
                 start_ts,
                 end_ts,
                 kind: t.kind,
-                reserved: [0u8; 15],
+                cliff_periods: 0,
+                reserved1: [0u8; 3],
+                reserved: [0u8; 8],
             },
             reserved: [0; 29],
         };
         let curr_ts = start_ts + days_to_secs(t.curr_day);
-        let power = d.voting_power_locked(curr_ts, t.amount_deposited, MAX_SECS_LOCKED)?;
+        let power = d.voting_power_locked(
+            curr_ts,
+            t.amount_deposited,
+            &LockupCurve::linear(MAX_SECS_LOCKED),
+        )?;
         assert_eq!(power, t.expected_voting_power);
         Ok(())
     }
@@ -821,16 +1277,24 @@ mod tests {
     // day - the current day in the lockup period
     // total_days - the number of days locked up
     fn locked_daily_power(amount: u64, day: f64, total_days: u64) -> u64 {
-        if day >= total_days as f64 {
+        locked_vesting_power(amount, day, total_days, 1.0)
+    }
+
+    // Like locked_daily_power, for vesting periods of period_days each.
+    //
+    // total_periods - the number of vesting periods locked up
+    fn locked_vesting_power(amount: u64, day: f64, total_periods: u64, period_days: f64) -> u64 {
+        let total_days = total_periods as f64 * period_days;
+        if day >= total_days {
             return 0;
         }
-        let days_remaining = total_days - day.floor() as u64;
+        let periods_remaining = total_periods - (day / period_days).floor() as u64;
         let mut total = 0f64;
-        for k in 0..days_remaining {
-            // We have 'days_remaining' remaining cliff-locked deposits of
-            // amount / total_days each.
-            let remaining_days = total_days as f64 - day - k as f64;
-            total += locked_cliff_power_float(amount / total_days, remaining_days);
+        for k in 0..periods_remaining {
+            // We have 'periods_remaining' remaining cliff-locked deposits of
+            // amount / total_periods each.
+            let remaining_days = total_days - day - k as f64 * period_days;
+            total += locked_cliff_power_float(amount / total_periods, remaining_days);
         }
         // the test code uses floats to compute the voting power; avoid
         // getting incurrect expected results due to floating point rounding
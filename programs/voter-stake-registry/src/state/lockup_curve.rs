@@ -0,0 +1,490 @@
+use crate::error::*;
+use crate::state::voting_mint_config::SCALED_FACTOR_BASE;
+use anchor_lang::prelude::*;
+use std::cmp::min;
+use std::convert::TryFrom;
+
+/// Maximum number of breakpoints or tiers of a lockup curve.
+pub const MAX_LOCKUP_CURVE_POINTS: usize = 4;
+
+/// How the extra lockup vote weight grows with the remaining lockup time.
+///
+/// All curves start at zero extra vote weight for no lockup and reach the full
+/// max_extra_lockup_vote_weight at lockup_saturation_secs.
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LockupCurveKind {
+    /// The extra vote weight grows linearly up to lockup_saturation_secs.
+    #[default]
+    Linear,
+
+    /// The extra vote weight is interpolated linearly between the curve points,
+    /// starting from zero at no lockup.
+    ///
+    /// Allows for example a steep ramp for short lockups and a flat tail.
+    PiecewiseLinear,
+
+    /// The extra vote weight is the factor of the longest tier whose lockup
+    /// duration has been reached, or zero below the first tier.
+    Step,
+}
+
+/// A breakpoint or tier of a lockup curve.
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq, Eq)]
+pub struct LockupCurvePoint {
+    /// Remaining lockup time in seconds.
+    pub lockup_secs: u32,
+
+    /// Fraction of max_extra_lockup_vote_weight granted at lockup_secs.
+    ///
+    /// In 1/SCALED_FACTOR_BASE units.
+    pub scaled_factor: u32,
+}
+const_assert!(std::mem::size_of::<LockupCurvePoint>() == 2 * 4);
+
+/// A validated lockup curve, see VotingMintConfig::lockup_curve().
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockupCurve {
+    pub kind: LockupCurveKind,
+
+    /// Remaining lockup time at which the full extra vote weight is reached.
+    pub lockup_saturation_secs: u64,
+
+    points: [LockupCurvePoint; MAX_LOCKUP_CURVE_POINTS],
+    point_count: usize,
+}
+
+/// Part of a lockup curve on which the factor is affine in the lockup time.
+#[derive(Default, Clone, Copy)]
+struct CurveSegment {
+    start_secs: u64,
+    /// None for the last segment, which is unbounded
+    end_secs: Option<u64>,
+    start_factor: u64,
+    end_factor: u64,
+}
+
+/// The segments of a lockup curve: one ending at each point and an unbounded
+/// last one.
+#[derive(Default)]
+struct CurveSegments {
+    segments: [CurveSegment; MAX_LOCKUP_CURVE_POINTS + 1],
+    count: usize,
+}
+
+impl CurveSegments {
+    fn push(&mut self, segment: CurveSegment) {
+        self.segments[self.count] = segment;
+        self.count += 1;
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &CurveSegment> {
+        self.segments[..self.count].iter()
+    }
+}
+
+impl CurveSegment {
+    fn contains(&self, lockup_secs: u64) -> bool {
+        lockup_secs >= self.start_secs
+            && !matches!(self.end_secs, Some(end_secs) if lockup_secs >= end_secs)
+    }
+
+    fn factor(&self, lockup_secs: u64) -> u64 {
+        match self.end_secs {
+            Some(end_secs) if self.end_factor != self.start_factor => {
+                let increase = (self.end_factor - self.start_factor) as u128
+                    * (lockup_secs - self.start_secs) as u128
+                    / (end_secs - self.start_secs) as u128;
+                self.start_factor + u64::try_from(increase).unwrap()
+            }
+            _ => self.start_factor,
+        }
+    }
+
+    /// Sum of the factors at `first_lockup_secs + p * period_secs` for all
+    /// 0 <= p < count where that lockup time falls into this segment.
+    ///
+    /// The sum over the matching p is an arithmetic series and can be done in
+    /// closed form. Rounds down once.
+    fn factor_sum(&self, first_lockup_secs: u64, period_secs: u64, count: u64) -> u128 {
+        let first_index_from = |secs: u64| {
+            min(
+                count,
+                secs.saturating_sub(first_lockup_secs)
+                    .checked_add(period_secs - 1)
+                    .unwrap()
+                    / period_secs,
+            )
+        };
+        let p_begin = first_index_from(self.start_secs);
+        let p_end = self.end_secs.map_or(count, first_index_from);
+        if p_end <= p_begin {
+            return 0;
+        }
+        let k = (p_end - p_begin) as u128;
+
+        let mut sum = k * self.start_factor as u128;
+        if let Some(end_secs) = self.end_secs {
+            if self.end_factor != self.start_factor {
+                // \sum_p (secs_p - start_secs) for the k lockup times in this segment
+                let first_offset =
+                    (first_lockup_secs + p_begin * period_secs - self.start_secs) as u128;
+                let offsets = k * first_offset + period_secs as u128 * (k * (k - 1) / 2);
+                sum += (self.end_factor - self.start_factor) as u128 * offsets
+                    / (end_secs - self.start_secs) as u128;
+            }
+        }
+        sum
+    }
+}
+
+impl LockupCurve {
+    /// The default curve, reaching the full extra vote weight linearly at
+    /// `lockup_saturation_secs`.
+    pub fn linear(lockup_saturation_secs: u64) -> Self {
+        Self {
+            kind: LockupCurveKind::Linear,
+            lockup_saturation_secs,
+            points: [LockupCurvePoint::default(); MAX_LOCKUP_CURVE_POINTS],
+            point_count: 0,
+        }
+    }
+
+    /// Creates a curve and checks that it is well formed:
+    /// - linear curves have no points and a positive saturation
+    /// - other curves have 1 to MAX_LOCKUP_CURVE_POINTS points with strictly
+    ///   increasing lockup_secs and non-decreasing factors
+    /// - the last point grants the full extra vote weight at lockup_saturation_secs
+    pub fn new(
+        kind: LockupCurveKind,
+        lockup_saturation_secs: u64,
+        points: &[LockupCurvePoint],
+    ) -> Result<Self> {
+        require_gt!(
+            lockup_saturation_secs,
+            0,
+            VsrError::LockupSaturationMustBePositive
+        );
+        if kind == LockupCurveKind::Linear {
+            require!(points.is_empty(), VsrError::InvalidLockupCurve);
+            return Ok(Self::linear(lockup_saturation_secs));
+        }
+
+        require!(!points.is_empty(), VsrError::InvalidLockupCurve);
+        require_gte!(
+            MAX_LOCKUP_CURVE_POINTS,
+            points.len(),
+            VsrError::InvalidLockupCurve
+        );
+        let mut prev = LockupCurvePoint::default();
+        for point in points {
+            require_gt!(
+                point.lockup_secs,
+                prev.lockup_secs,
+                VsrError::InvalidLockupCurve
+            );
+            require_gte!(
+                point.scaled_factor,
+                prev.scaled_factor,
+                VsrError::InvalidLockupCurve
+            );
+            prev = *point;
+        }
+        require_eq!(
+            prev.scaled_factor as u64,
+            SCALED_FACTOR_BASE,
+            VsrError::InvalidLockupCurve
+        );
+        require_eq!(
+            prev.lockup_secs as u64,
+            lockup_saturation_secs,
+            VsrError::InvalidLockupCurve
+        );
+
+        Ok(Self::new_unchecked(kind, lockup_saturation_secs, points))
+    }
+
+    /// Creates a curve without the checks of new(), for curves that were
+    /// validated when they were stored.
+    pub(crate) fn new_unchecked(
+        kind: LockupCurveKind,
+        lockup_saturation_secs: u64,
+        points: &[LockupCurvePoint],
+    ) -> Self {
+        let mut curve = Self::linear(lockup_saturation_secs);
+        curve.kind = kind;
+        curve.points[..points.len()].copy_from_slice(points);
+        curve.point_count = points.len();
+        curve
+    }
+
+    /// The breakpoints or tiers of the curve, empty for linear curves.
+    pub fn points(&self) -> &[LockupCurvePoint] {
+        &self.points[..self.point_count]
+    }
+
+    /// Splits the curve into parts on which the factor is affine.
+    ///
+    /// Linear curves are handled like piecewise linear curves with a single
+    /// point at lockup_saturation_secs.
+    fn segments(&self) -> CurveSegments {
+        let linear_point = (self.kind == LockupCurveKind::Linear)
+            .then_some((self.lockup_saturation_secs, SCALED_FACTOR_BASE));
+        let points = linear_point.into_iter().chain(
+            self.points()
+                .iter()
+                .map(|p| (p.lockup_secs as u64, p.scaled_factor as u64)),
+        );
+        let mut segments = CurveSegments::default();
+        let mut prev = (0u64, 0u64);
+        for (secs, factor) in points {
+            segments.push(CurveSegment {
+                start_secs: prev.0,
+                end_secs: Some(secs),
+                start_factor: prev.1,
+                end_factor: match self.kind {
+                    LockupCurveKind::Step => prev.1,
+                    _ => factor,
+                },
+            });
+            prev = (secs, factor);
+        }
+        segments.push(CurveSegment {
+            start_secs: prev.0,
+            end_secs: None,
+            start_factor: prev.1,
+            end_factor: prev.1,
+        });
+        segments
+    }
+
+    /// Fraction of the max extra lockup vote weight for a lockup with
+    /// `lockup_secs` remaining.
+    ///
+    /// In 1/SCALED_FACTOR_BASE units.
+    pub fn factor(&self, lockup_secs: u64) -> u64 {
+        self.segments()
+            .iter()
+            .find(|segment| segment.contains(lockup_secs))
+            .unwrap()
+            .factor(lockup_secs)
+    }
+
+    /// Sum of the factors for `count` lockups, the first having `first_lockup_secs`
+    /// remaining and each further one `period_secs` more.
+    ///
+    /// That's the lockup factor of a vesting deposit with `count` vesting cliffs
+    /// left, when multiplied by 1/count. Rounds down once per curve segment.
+    ///
+    /// In 1/SCALED_FACTOR_BASE units.
+    pub fn factor_sum(&self, first_lockup_secs: u64, period_secs: u64, count: u64) -> u128 {
+        self.segments()
+            .iter()
+            .map(|segment| segment.factor_sum(first_lockup_secs, period_secs, count))
+            .sum()
+    }
+}
+
+/// Applies a sum of `count` factors, in 1/SCALED_FACTOR_BASE units, to
+/// `count` equal parts of `max_locked_vote_weight`.
+pub fn apply_factor_sum(max_locked_vote_weight: u64, factor_sum: u128, count: u64) -> u64 {
+    u64::try_from(
+        (max_locked_vote_weight as u128)
+            .checked_mul(factor_sum)
+            .unwrap()
+            .checked_div(
+                (count as u128)
+                    .checked_mul(SCALED_FACTOR_BASE as u128)
+                    .unwrap(),
+            )
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::deposit_entry::DepositEntry;
+    use crate::state::lockup::{Lockup, LockupKind, SECS_PER_DAY, SECS_PER_MONTH};
+
+    const YEAR: u32 = 365 * SECS_PER_DAY as u32;
+
+    fn point(lockup_secs: u32, percent: u32) -> LockupCurvePoint {
+        LockupCurvePoint {
+            lockup_secs,
+            scaled_factor: percent * (SCALED_FACTOR_BASE / 100) as u32,
+        }
+    }
+
+    fn ramp_curve() -> LockupCurve {
+        // steep ramp to 60% for the first quarter year, flat tail afterwards
+        LockupCurve::new(
+            LockupCurveKind::PiecewiseLinear,
+            2 * YEAR as u64,
+            &[point(YEAR / 4, 60), point(YEAR, 90), point(2 * YEAR, 100)],
+        )
+        .unwrap()
+    }
+
+    fn tier_curve() -> LockupCurve {
+        LockupCurve::new(
+            LockupCurveKind::Step,
+            4 * YEAR as u64,
+            &[point(YEAR, 25), point(2 * YEAR, 50), point(4 * YEAR, 100)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    pub fn validation() {
+        let ok = |kind, saturation, points: &[LockupCurvePoint]| {
+            LockupCurve::new(kind, saturation, points).is_ok()
+        };
+        assert!(ok(LockupCurveKind::Linear, 100, &[]));
+        assert!(!ok(LockupCurveKind::Linear, 0, &[]));
+        assert!(!ok(LockupCurveKind::Linear, 100, &[point(100, 100)]));
+        assert!(ok(LockupCurveKind::Step, 100, &[point(100, 100)]));
+        assert!(!ok(LockupCurveKind::Step, 100, &[]));
+        // saturation must match the last point
+        assert!(!ok(LockupCurveKind::Step, 200, &[point(100, 100)]));
+        // the last point must grant the full extra vote weight
+        assert!(!ok(LockupCurveKind::Step, 100, &[point(100, 50)]));
+        assert!(ok(
+            LockupCurveKind::PiecewiseLinear,
+            100,
+            &[point(50, 50), point(60, 50), point(100, 100)]
+        ));
+        // factors may not decrease, lockup times must increase
+        assert!(!ok(
+            LockupCurveKind::PiecewiseLinear,
+            100,
+            &[point(50, 50), point(60, 40), point(100, 100)]
+        ));
+        assert!(!ok(
+            LockupCurveKind::PiecewiseLinear,
+            100,
+            &[point(50, 50), point(50, 60), point(100, 100)]
+        ));
+        assert!(!ok(
+            LockupCurveKind::PiecewiseLinear,
+            100,
+            &[point(0, 50), point(100, 100)]
+        ));
+        assert!(!ok(
+            LockupCurveKind::PiecewiseLinear,
+            100,
+            &[
+                point(10, 10),
+                point(20, 20),
+                point(30, 30),
+                point(40, 40),
+                point(100, 100)
+            ]
+        ));
+    }
+
+    #[test]
+    pub fn factors() {
+        let base = SCALED_FACTOR_BASE;
+
+        let linear = LockupCurve::linear(1000);
+        assert_eq!(linear.factor(0), 0);
+        assert_eq!(linear.factor(250), base / 4);
+        assert_eq!(linear.factor(1000), base);
+        assert_eq!(linear.factor(5000), base);
+
+        let ramp = ramp_curve();
+        let quarter = YEAR as u64 / 4;
+        assert_eq!(ramp.factor(0), 0);
+        assert_eq!(ramp.factor(quarter / 2), 3 * base / 10);
+        assert_eq!(ramp.factor(quarter), 6 * base / 10);
+        assert_eq!(ramp.factor(2 * quarter), 7 * base / 10);
+        assert_eq!(ramp.factor(YEAR as u64), 9 * base / 10);
+        assert_eq!(ramp.factor(3 * YEAR as u64 / 2), 95 * base / 100);
+        assert_eq!(ramp.factor(2 * YEAR as u64), base);
+        assert_eq!(ramp.factor(10 * YEAR as u64), base);
+
+        let tiers = tier_curve();
+        assert_eq!(tiers.factor(0), 0);
+        assert_eq!(tiers.factor(YEAR as u64 - 1), 0);
+        assert_eq!(tiers.factor(YEAR as u64), base / 4);
+        assert_eq!(tiers.factor(2 * YEAR as u64 - 1), base / 4);
+        assert_eq!(tiers.factor(2 * YEAR as u64), base / 2);
+        assert_eq!(tiers.factor(4 * YEAR as u64 - 1), base / 2);
+        assert_eq!(tiers.factor(4 * YEAR as u64), base);
+        assert_eq!(tiers.factor(5 * YEAR as u64), base);
+    }
+
+    #[test]
+    pub fn factor_sums() {
+        // the closed form sum rounds once per segment, the iterative sum once per term
+        let check = |curve: &LockupCurve, first: u64, period: u64, count: u64| {
+            let iterative: u128 = (0..count)
+                .map(|p| curve.factor(first + p * period) as u128)
+                .sum();
+            let closed_form = curve.factor_sum(first, period, count);
+            assert!(closed_form >= iterative);
+            assert!(closed_form - iterative <= count as u128);
+            if curve.kind == LockupCurveKind::Step {
+                assert_eq!(closed_form, iterative);
+            }
+        };
+        let curves = [LockupCurve::linear(YEAR as u64), ramp_curve(), tier_curve()];
+        for curve in curves.iter() {
+            for (first, period, count) in [
+                (1, SECS_PER_DAY, 1),
+                (SECS_PER_DAY, SECS_PER_DAY, 10),
+                (12_345, SECS_PER_DAY, 800),
+                (SECS_PER_MONTH / 3, SECS_PER_MONTH, 60),
+                (3 * YEAR as u64, SECS_PER_MONTH, 36),
+                (10 * YEAR as u64, SECS_PER_DAY, 5),
+            ] {
+                check(curve, first, period, count);
+            }
+        }
+    }
+
+    #[test]
+    pub fn vesting_matches_cliffs() -> Result<()> {
+        // A monthly vesting deposit has the voting power of one cliff deposit
+        // per vesting period.
+        let start = 1_000_000_000;
+        let months = 60;
+        let amount = 60_000_000;
+        let curve = tier_curve();
+        let deposit = |kind, periods| DepositEntry {
+            amount_deposited_native: amount / months * periods,
+            amount_initially_locked_native: amount / months * periods,
+            lockup: Lockup::new_from_periods(kind, start, start, periods as u32).unwrap(),
+            is_used: true,
+            allow_clawback: false,
+            voting_mint_config_idx: 0,
+            reserved: [0; 29],
+        };
+        let vesting = deposit(LockupKind::Monthly, months);
+
+        for curr_ts in [
+            start - 100,
+            start,
+            start + 1000,
+            start + 14 * SECS_PER_MONTH as i64,
+        ] {
+            let expected: u64 = (1..=months)
+                .map(|month| {
+                    let mut cliff = deposit(LockupKind::Cliff, 0);
+                    cliff.lockup.end_ts = start + (month * SECS_PER_MONTH) as i64;
+                    cliff
+                        .voting_power_locked(curr_ts, amount / months, &curve)
+                        .unwrap()
+                })
+                .sum();
+            assert_eq!(
+                vesting.voting_power_locked(curr_ts, amount, &curve)?,
+                expected
+            );
+        }
+        Ok(())
+    }
+}
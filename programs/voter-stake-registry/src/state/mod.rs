@@ -1,11 +1,13 @@
 pub use deposit_entry::*;
 pub use lockup::*;
+pub use lockup_curve::*;
 pub use registrar::*;
 pub use voter::*;
 pub use voting_mint_config::*;
 
 mod deposit_entry;
 mod lockup;
+mod lockup_curve;
 mod registrar;
 mod voter;
 mod voting_mint_config;
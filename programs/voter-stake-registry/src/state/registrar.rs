@@ -1,7 +1,17 @@
 use crate::error::*;
+use crate::max_voter_weight_record;
+use crate::state::deposit_entry::DepositEntry;
+use crate::state::lockup::{Lockup, LockupKind};
+use crate::state::voter::{Voter, VoterAccount};
 use crate::state::voting_mint_config::VotingMintConfig;
+use crate::token_interface;
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use std::ops::{Deref, DerefMut};
+
+// Generate a MaxVoterWeightRecord Anchor wrapper, owned by the current program.
+// Like VoterWeightRecords, they are defined by the SPL governance program but
+// owned by this program.
+max_voter_weight_record!(crate::ID);
 
 /// Instance of a voting rights distributor.
 #[account(zero_copy)]
@@ -15,17 +25,209 @@ pub struct Registrar {
 
     /// Storage for voting mints and their configuration.
     /// The length should be adjusted for one's use case.
-    pub voting_mints: [VotingMintConfig; 4],
+    ///
+    /// Registrars created with fewer voting mints must be migrated with
+    /// resize_registrar before they can be used.
+    pub voting_mints: [VotingMintConfig; MAX_VOTING_MINTS],
 
     /// Debug only: time offset, to allow tests to move forward in time.
     pub time_offset: i64,
     pub bump: u8,
-    pub reserved2: [u8; 7],
-    pub reserved3: [u64; 11], // split because `Default` does not support [u8; 95]
+
+    /// How update_max_vote_weight computes the max vote weight.
+    pub max_vote_weight_mode: MaxVoteWeightMode,
+    pub reserved2: [u8; 6],
+
+    /// Maximum native tokens of each voting mint that may be deposited across
+    /// all voters, zero for no cap. Indexed like `voting_mints` and compared
+    /// to their deposited_native.
+    pub max_deposited_native: [u64; MAX_VOTING_MINTS],
+
+    /// The lockup kinds deposits of each voting mint may use, see
+    /// LockupKind::mask. Zero allows all kinds. Indexed like `voting_mints`.
+    pub allowed_lockup_kinds: [u16; MAX_VOTING_MINTS],
+
+    pub reserved3: [u64; 1],
+
+    /// Locked and clawback-enabled tokens of each voting mint across all
+    /// voters, indexed like `voting_mints`.
+    ///
+    /// Registrars created with fewer voting mints or without these totals
+    /// must be migrated with resize_registrar before they can be used.
+    pub voting_mint_totals: [VotingMintTotals; MAX_VOTING_MINTS],
+
+    /// Whether voting_mint_totals has been tracked since the mint was
+    /// configured. False for mints configured before the totals existed,
+    /// until their backfill is finished, see count_voter.
+    pub voting_mint_totals_tracked: [bool; MAX_VOTING_MINTS],
 }
-const_assert!(std::mem::size_of::<Registrar>() == 5 * 32 + 4 * 152 + 8 + 1 + 95);
+const_assert!(
+    std::mem::size_of::<Registrar>()
+        == 5 * 32
+            + MAX_VOTING_MINTS * 152
+            + 8
+            + 1
+            + 1
+            + 6
+            + MAX_VOTING_MINTS * 8
+            + MAX_VOTING_MINTS * 2
+            + 8
+            + MAX_VOTING_MINTS * (16 + 1)
+);
 const_assert!(std::mem::size_of::<Registrar>() % 8 == 0);
 
+/// Number of voting mints a registrar can hold.
+pub const MAX_VOTING_MINTS: usize = 8;
+
+/// How the max vote weight of a registrar is computed, see
+/// Registrar::max_vote_weight_for_mode().
+#[repr(u8)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MaxVoteWeightMode {
+    /// The full supply of every voting mint, at the max extra lockup vote
+    /// weight.
+    #[default]
+    MintSupply,
+
+    /// The tokens deposited of every voting mint, with the max extra lockup
+    /// vote weight for the locked ones.
+    ///
+    /// Needs deposits to be tracked for all voting mints that grant vote
+    /// weight, see finish_voting_mint_backfill for mints configured before.
+    Deposited,
+}
+
+/// Aggregate amounts of a voting mint, see Registrar::voting_mint_totals.
+///
+/// The voting mint's deposited total is VotingMintConfig::deposited_native.
+#[zero_copy]
+#[derive(Default)]
+pub struct VotingMintTotals {
+    /// Native tokens in deposit entries with a lockup, counting at most the
+    /// entry's amount_initially_locked_native.
+    ///
+    /// This is an upper bound for the locked tokens: tokens that vested and
+    /// expired lockups only stop counting when an instruction resolves the
+    /// deposit entry's vesting or its tokens are withdrawn.
+    pub locked_native: u64,
+
+    /// Native tokens deposited in deposit entries that allow clawback.
+    pub clawback_deposited_native: u64,
+}
+const_assert!(std::mem::size_of::<VotingMintTotals>() == 16);
+
+impl VotingMintTotals {
+    /// Adds a voter's share of the totals.
+    fn add(&mut self, other: &VotingMintTotals) {
+        self.locked_native = self.locked_native.checked_add(other.locked_native).unwrap();
+        self.clawback_deposited_native = self
+            .clawback_deposited_native
+            .checked_add(other.clawback_deposited_native)
+            .unwrap();
+    }
+
+    /// Adds a deposit entry's tokens to the totals.
+    pub fn add_deposit_entry(&mut self, d_entry: &DepositEntry) {
+        if d_entry.lockup.kind != LockupKind::None {
+            let locked = d_entry
+                .amount_initially_locked_native
+                .min(d_entry.amount_deposited_native);
+            self.locked_native = self.locked_native.checked_add(locked).unwrap();
+        }
+        if d_entry.allow_clawback {
+            self.clawback_deposited_native = self
+                .clawback_deposited_native
+                .checked_add(d_entry.amount_deposited_native)
+                .unwrap();
+        }
+    }
+
+    /// Replaces a voter's share of the totals, `before`, by `after`.
+    ///
+    /// Untracked totals may be lower than a voter's share, they saturate at
+    /// zero like VotingMintConfig::sub_deposited.
+    fn replace(&mut self, before: &VotingMintTotals, after: &VotingMintTotals, tracked: bool) {
+        let update = |total: u64, before: u64, after: u64| {
+            let total = total.checked_add(after).unwrap();
+            if tracked {
+                total.checked_sub(before).unwrap()
+            } else {
+                total.saturating_sub(before)
+            }
+        };
+        self.locked_native = update(
+            self.locked_native,
+            before.locked_native,
+            after.locked_native,
+        );
+        self.clawback_deposited_native = update(
+            self.clawback_deposited_native,
+            before.clawback_deposited_native,
+            after.clawback_deposited_native,
+        );
+    }
+}
+
+/// Bytes of a Registrar before `voting_mints`.
+const REGISTRAR_HEAD_LEN: usize = 5 * 32;
+/// Bytes of a Registrar after `voting_mints`.
+const REGISTRAR_TAIL_LEN: usize =
+    LEGACY_REGISTRAR_TAIL_LEN + MAX_VOTING_MINTS * (std::mem::size_of::<VotingMintTotals>() + 1);
+/// Bytes of a Registrar after `voting_mints`, in layouts without
+/// `voting_mint_totals`.
+const LEGACY_REGISTRAR_TAIL_LEN: usize = 8 + 1 + 95;
+const_assert!(
+    std::mem::size_of::<Registrar>()
+        == REGISTRAR_HEAD_LEN
+            + MAX_VOTING_MINTS * std::mem::size_of::<VotingMintConfig>()
+            + REGISTRAR_TAIL_LEN
+);
+
+/// Size of a registrar account with the current layout.
+pub const REGISTRAR_ACCOUNT_LEN: usize = 8 + std::mem::size_of::<Registrar>();
+
+/// Whether a registrar account has the current layout.
+///
+/// AccountLoader panics when loading registrars with an older, smaller layout,
+/// so instructions check this first. Such registrars must be migrated with
+/// resize_registrar.
+pub fn has_current_registrar_layout(registrar: &AccountLoader<Registrar>) -> bool {
+    registrar.as_ref().data_len() == REGISTRAR_ACCOUNT_LEN
+}
+
+/// Moves the fields after `voting_mints` of a registrar account with an
+/// older layout to where they are in the current layout, leaving the new
+/// voting mint configs and the voting mint totals zeroed.
+///
+/// Older layouts had fewer voting mints and no voting mint totals.
+///
+/// `data` is the full account data including the discriminator, already
+/// grown to the current size. `old_len` is the account size before that.
+pub fn migrate_registrar_data(data: &mut [u8], old_len: usize) -> Result<()> {
+    let new_len = REGISTRAR_ACCOUNT_LEN;
+    let config_len = std::mem::size_of::<VotingMintConfig>();
+    let fixed_len = 8 + REGISTRAR_HEAD_LEN + LEGACY_REGISTRAR_TAIL_LEN;
+    require_eq!(data.len(), new_len, VsrError::InvalidRegistrarSize);
+    if old_len == new_len {
+        return Ok(());
+    }
+    require_gt!(new_len, old_len, VsrError::InvalidRegistrarSize);
+    require_gte!(old_len, fixed_len, VsrError::InvalidRegistrarSize);
+    require_eq!(
+        (old_len - fixed_len) % config_len,
+        0,
+        VsrError::InvalidRegistrarSize
+    );
+
+    let old_tail_start = old_len - LEGACY_REGISTRAR_TAIL_LEN;
+    let new_tail_start = new_len - REGISTRAR_TAIL_LEN;
+    let new_totals_start = new_tail_start + LEGACY_REGISTRAR_TAIL_LEN;
+    data.copy_within(old_tail_start..old_len, new_tail_start);
+    data[old_tail_start..new_tail_start].fill(0);
+    data[new_totals_start..].fill(0);
+    Ok(())
+}
+
 impl Registrar {
     pub fn clock_unix_timestamp(&self) -> i64 {
         Clock::get()
@@ -42,18 +244,163 @@ impl Registrar {
             .ok_or_else(|| error!(VsrError::VotingMintNotFound))
     }
 
+    /// Checks the deposit limits set with configure_deposit_limits after
+    /// `amount` native tokens of voting mint `mint_idx` arrived in a voter's
+    /// vault and were added to the mint's deposited_native.
+    ///
+    /// `voter_deposited_native` is the voter's new total for the mint.
+    pub fn check_deposit_limits(
+        &self,
+        mint_idx: usize,
+        amount: u64,
+        voter_deposited_native: u64,
+    ) -> Result<()> {
+        let mint_config = &self.voting_mints[mint_idx];
+        require_gte!(
+            amount,
+            mint_config.min_deposit_native,
+            VsrError::DepositBelowMinimum
+        );
+        if mint_config.max_voter_deposited_native > 0 {
+            require_gte!(
+                mint_config.max_voter_deposited_native,
+                voter_deposited_native,
+                VsrError::VoterDepositCapExceeded
+            );
+        }
+        let max_deposited_native = self.max_deposited_native[mint_idx];
+        if max_deposited_native > 0 {
+            require_gte!(
+                max_deposited_native,
+                mint_config.deposited_native,
+                VsrError::RegistrarDepositCapExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks the lockup restrictions set with configure_lockup_limits for
+    /// tokens of the voting mint at `mint_idx` that get locked up with
+    /// `lockup` at `curr_ts`.
+    pub fn check_lockup(&self, mint_idx: usize, lockup: &Lockup, curr_ts: i64) -> Result<()> {
+        let allowed_lockup_kinds = self.allowed_lockup_kinds[mint_idx];
+        require!(
+            allowed_lockup_kinds == 0 || allowed_lockup_kinds & lockup.kind.mask() != 0,
+            VsrError::LockupKindNotAllowed
+        );
+        self.voting_mints[mint_idx].check_lockup(lockup, curr_ts)
+    }
+
+    /// Whether the deposited or voting mint totals of the voting mint at `idx`
+    /// are still being backfilled: true for mints that were configured before
+    /// they were tracked, until finish_voting_mint_backfill.
+    pub fn voting_mint_backfilling(&self, idx: usize) -> bool {
+        let config = &self.voting_mints[idx];
+        config.in_use()
+            && !(config.deposited_native_tracked && self.voting_mint_totals_tracked[idx])
+    }
+
+    /// Adds the voter's deposits to the untracked totals of voting mints that
+    /// are being backfilled, unless they were added already.
+    ///
+    /// Instructions that change a voter's deposits call this first, so the
+    /// totals of these mints only ever contain whole voters. Once all voters
+    /// with deposits are counted, with backfill_voting_mint_totals for those
+    /// that didn't change, the totals are exact.
+    pub fn count_voter<V, D>(&mut self, voter: &mut VoterAccount<V, D>)
+    where
+        V: DerefMut<Target = Voter>,
+        D: Deref<Target = [DepositEntry]>,
+    {
+        let voter_totals = voter.voting_mint_totals();
+        for (idx, voter_totals) in voter_totals.iter().enumerate() {
+            if !self.voting_mint_backfilling(idx) || voter.voting_mints_counted[idx] {
+                continue;
+            }
+            if !self.voting_mints[idx].deposited_native_tracked {
+                self.voting_mints[idx].add_deposited(voter.deposited_native(idx));
+            }
+            if !self.voting_mint_totals_tracked[idx] {
+                self.voting_mint_totals[idx].add(voter_totals);
+            }
+            voter.voting_mints_counted[idx] = true;
+        }
+    }
+
+    /// Book keeping for changes to a voter's deposit entries: `before` and
+    /// `after` are the voter's VoterAccount::voting_mint_totals around them.
+    pub fn update_voting_mint_totals(
+        &mut self,
+        before: &[VotingMintTotals; MAX_VOTING_MINTS],
+        after: &[VotingMintTotals; MAX_VOTING_MINTS],
+    ) {
+        for (idx, (before, after)) in before.iter().zip(after.iter()).enumerate() {
+            let tracked = self.voting_mint_totals_tracked[idx];
+            self.voting_mint_totals[idx].replace(before, after, tracked);
+        }
+    }
+
+    /// The max vote weight according to max_vote_weight_mode.
+    ///
+    /// `mint_accounts` must contain all voting mints for MintSupply and are
+    /// unused for Deposited.
+    pub fn max_vote_weight_for_mode(&self, mint_accounts: &[AccountInfo]) -> Result<u64> {
+        match self.max_vote_weight_mode {
+            MaxVoteWeightMode::MintSupply => self.max_vote_weight(mint_accounts),
+            MaxVoteWeightMode::Deposited => self.max_deposited_vote_weight(),
+        }
+    }
+
+    /// The max vote weight of the deposited tokens: their baseline vote weight
+    /// plus the max extra lockup vote weight for the locked ones.
+    ///
+    /// For mints without voting mint totals all deposited tokens are assumed
+    /// to be locked.
+    pub fn max_deposited_vote_weight(&self) -> Result<u64> {
+        self.voting_mints.iter().enumerate().try_fold(
+            0u64,
+            |mut sum, (idx, voting_mint_config)| -> Result<u64> {
+                // removed mints grant no vote weight
+                if !voting_mint_config.in_use() || voting_mint_config.deprecated {
+                    return Ok(sum);
+                }
+                require!(
+                    voting_mint_config.deposited_native_tracked,
+                    VsrError::VotingMintDepositsNotTracked
+                );
+                let deposited = voting_mint_config.deposited_native;
+                let locked = if self.voting_mint_totals_tracked[idx] {
+                    self.voting_mint_totals[idx].locked_native.min(deposited)
+                } else {
+                    deposited
+                };
+                sum = sum
+                    .checked_add(voting_mint_config.baseline_vote_weight(deposited)?)
+                    .ok_or_else(|| error!(VsrError::VoterWeightOverflow))?;
+                sum = sum
+                    .checked_add(voting_mint_config.max_extra_lockup_vote_weight(locked)?)
+                    .ok_or_else(|| error!(VsrError::VoterWeightOverflow))?;
+                Ok(sum)
+            },
+        )
+    }
+
+    /// The max vote weight of the full supply of all voting mints.
+    ///
+    /// `mint_accounts` must contain all voting mints.
     pub fn max_vote_weight(&self, mint_accounts: &[AccountInfo]) -> Result<u64> {
         self.voting_mints
             .iter()
             .try_fold(0u64, |mut sum, voting_mint_config| -> Result<u64> {
-                if !voting_mint_config.in_use() {
+                // removed mints grant no vote weight
+                if !voting_mint_config.in_use() || voting_mint_config.deprecated {
                     return Ok(sum);
                 }
                 let mint_account = mint_accounts
                     .iter()
                     .find(|a| a.key() == voting_mint_config.mint)
                     .ok_or_else(|| error!(VsrError::VotingMintNotFound))?;
-                let mint = Account::<Mint>::try_from(mint_account)?;
+                let mint = token_interface::load_mint(mint_account)?;
                 sum = sum
                     .checked_add(voting_mint_config.baseline_vote_weight(mint.supply)?)
                     .ok_or_else(|| error!(VsrError::VoterWeightOverflow))?;
@@ -78,3 +425,69 @@ macro_rules! registrar_seeds {
 }
 
 pub use registrar_seeds;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn migrate_registrar_data_from_four_mints() -> Result<()> {
+        let mut registrar = Registrar {
+            realm: Pubkey::new_unique(),
+            realm_authority: Pubkey::new_unique(),
+            time_offset: -7,
+            bump: 254,
+            ..Registrar::default()
+        };
+        for (i, config) in registrar.voting_mints.iter_mut().take(4).enumerate() {
+            config.mint = Pubkey::new_unique();
+            config.digit_shift = i as i8;
+        }
+        let mut expected = vec![0u8; 8];
+        expected.extend_from_slice(bytemuck::bytes_of(&registrar));
+
+        // the old layout only stored four voting mints and no totals
+        let config_len = std::mem::size_of::<VotingMintConfig>();
+        let old_mints_end = 8 + REGISTRAR_HEAD_LEN + 4 * config_len;
+        let tail_start = expected.len() - REGISTRAR_TAIL_LEN;
+        let mut data = expected[..old_mints_end].to_vec();
+        data.extend_from_slice(&expected[tail_start..tail_start + LEGACY_REGISTRAR_TAIL_LEN]);
+        let old_len = data.len();
+        data.resize(expected.len(), 0xff);
+
+        migrate_registrar_data(&mut data, old_len)?;
+        assert_eq!(data, expected);
+
+        // already migrated data stays untouched
+        migrate_registrar_data(&mut data, expected.len())?;
+        assert_eq!(data, expected);
+
+        // sizes that don't correspond to a layout are rejected
+        assert!(migrate_registrar_data(&mut data, old_len - 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    pub fn migrate_registrar_data_without_totals() -> Result<()> {
+        let mut registrar = Registrar {
+            realm: Pubkey::new_unique(),
+            time_offset: 3,
+            ..Registrar::default()
+        };
+        registrar.max_deposited_native[7] = 1000;
+        for config in registrar.voting_mints.iter_mut() {
+            config.mint = Pubkey::new_unique();
+        }
+        let mut expected = vec![0u8; 8];
+        expected.extend_from_slice(bytemuck::bytes_of(&registrar));
+
+        // all voting mints, but no totals
+        let old_len = expected.len() - MAX_VOTING_MINTS * (16 + 1);
+        let mut data = expected[..old_len].to_vec();
+        data.resize(expected.len(), 0xff);
+
+        migrate_registrar_data(&mut data, old_len)?;
+        assert_eq!(data, expected);
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 use crate::error::*;
 use crate::state::deposit_entry::DepositEntry;
-use crate::state::registrar::Registrar;
+use crate::state::registrar::{Registrar, VotingMintTotals, MAX_VOTING_MINTS};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as tx_instructions;
+use anchor_lang::Discriminator;
+use spl_governance::error::GovernanceError;
 use spl_governance::state::token_owner_record;
+use std::cell::{Ref, RefMut};
+use std::ops::{Deref, DerefMut};
 
 /// User account for minting voting rights.
 #[account(zero_copy)]
@@ -12,17 +17,191 @@ pub struct Voter {
     pub deposits: [DepositEntry; 32],
     pub voter_bump: u8,
     pub voter_weight_record_bump: u8,
-    pub reserved: [u8; 94],
+    /// Wallet that votes with this voter's weight instead of the voter
+    /// authority, see set_delegate. Pubkey::default() if unset.
+    pub delegate: Pubkey,
+    /// Wallet that may take over this voter with accept_voter_transfer,
+    /// see propose_voter_transfer. Pubkey::default() if unset.
+    pub pending_voter_authority: Pubkey,
+    /// Set while the voter authority revoked `delegate` but the delegate's
+    /// votes may still use this voter's weight, see set_delegate.
+    pub delegate_revoked: bool,
+    /// Whether this voter's deposits were added to the registrar's totals of
+    /// each voting mint while they are backfilled, see
+    /// Registrar::count_voter. Indexed like Registrar::voting_mints.
+    pub voting_mints_counted: [bool; MAX_VOTING_MINTS],
+    pub reserved: [u8; 21],
 }
-const_assert!(std::mem::size_of::<Voter>() == 2 * 32 + 32 * 80 + 2 + 94);
+const_assert!(
+    std::mem::size_of::<Voter>() == 2 * 32 + 32 * 80 + 2 + 32 + 32 + 1 + MAX_VOTING_MINTS + 21
+);
 const_assert!(std::mem::size_of::<Voter>() % 8 == 0);
 
-impl Voter {
+/// Size of a voter account that wasn't grown with resize_voter.
+pub const VOTER_ACCOUNT_LEN: usize = 8 + std::mem::size_of::<Voter>();
+
+/// Maximum number of deposit entries a voter account can be grown to.
+///
+/// Note that the vote weight computation iterates over all deposit entries,
+/// so voters with many entries need more compute.
+pub const MAX_DEPOSIT_ENTRIES: usize = 128;
+
+/// The number of extra deposit entries stored after the Voter in an account
+/// of `account_len` bytes.
+pub fn extra_deposit_entry_count(account_len: usize) -> Result<usize> {
+    let entry_len = std::mem::size_of::<DepositEntry>();
+    require_gte!(account_len, VOTER_ACCOUNT_LEN, VsrError::InvalidVoterSize);
+    let extra_len = account_len - VOTER_ACCOUNT_LEN;
+    require_eq!(extra_len % entry_len, 0, VsrError::InvalidVoterSize);
+    Ok(extra_len / entry_len)
+}
+
+/// A Voter together with the extra deposit entries that are stored after it
+/// in voter accounts that were grown with resize_voter.
+///
+/// The deposit entry index `i` refers to `voter.deposits[i]` for the first
+/// entries and to `extra_deposits[i - voter.deposits.len()]` for the rest.
+/// All code that works with deposit entries should go through this type,
+/// so that it sees the extra deposit entries.
+#[derive(Clone)]
+pub struct VoterAccount<V, D> {
+    pub voter: V,
+    pub extra_deposits: D,
+}
+
+pub type VoterRef<'a> = VoterAccount<Ref<'a, Voter>, Ref<'a, [DepositEntry]>>;
+pub type VoterRefMut<'a> = VoterAccount<RefMut<'a, Voter>, RefMut<'a, [DepositEntry]>>;
+/// An owned copy of a voter account, see try_from_account_data.
+pub type VoterAccountData = VoterAccount<Box<Voter>, Vec<DepositEntry>>;
+
+impl<'a> VoterRef<'a> {
+    /// Borrows the voter and its extra deposit entries from the account data.
+    ///
+    /// The account owner and discriminator must have been checked already,
+    /// like AccountLoader does.
+    pub fn load(account_info: &'a AccountInfo) -> Result<Self> {
+        let data = account_info.try_borrow_data()?;
+        extra_deposit_entry_count(data.len())?;
+        let (voter, extra_deposits) = Ref::map_split(data, |data| {
+            let (voter, extra) = data.split_at(VOTER_ACCOUNT_LEN);
+            (
+                bytemuck::from_bytes(&voter[8..]),
+                bytemuck::cast_slice(extra),
+            )
+        });
+        Ok(Self {
+            voter,
+            extra_deposits,
+        })
+    }
+}
+
+impl<'a> VoterRefMut<'a> {
+    /// Mutably borrows the voter and its extra deposit entries from the account data.
+    ///
+    /// The account owner and discriminator must have been checked already,
+    /// like AccountLoader does.
+    pub fn load(account_info: &'a AccountInfo) -> Result<Self> {
+        require!(account_info.is_writable, ErrorCode::AccountNotMutable);
+        let data = account_info.try_borrow_mut_data()?;
+        extra_deposit_entry_count(data.len())?;
+        let (voter, extra_deposits) = RefMut::map_split(data, |data| {
+            let (voter, extra) = data.split_at_mut(VOTER_ACCOUNT_LEN);
+            (
+                bytemuck::from_bytes_mut(&mut voter[8..]),
+                bytemuck::cast_slice_mut(extra),
+            )
+        });
+        Ok(Self {
+            voter,
+            extra_deposits,
+        })
+    }
+}
+
+impl VoterAccountData {
+    /// Copies a voter and its extra deposit entries out of the full account
+    /// data, including the discriminator. Useful off-chain.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == Voter::discriminator(),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let extra_count = extra_deposit_entry_count(data.len())?;
+        let entry_len = std::mem::size_of::<DepositEntry>();
+        Ok(Self {
+            voter: Box::new(bytemuck::pod_read_unaligned(&data[8..VOTER_ACCOUNT_LEN])),
+            extra_deposits: (0..extra_count)
+                .map(|i| {
+                    let start = VOTER_ACCOUNT_LEN + i * entry_len;
+                    bytemuck::pod_read_unaligned(&data[start..start + entry_len])
+                })
+                .collect(),
+        })
+    }
+}
+
+impl<V: Deref<Target = Voter>, D: Deref<Target = [DepositEntry]>> Deref for VoterAccount<V, D> {
+    type Target = Voter;
+
+    fn deref(&self) -> &Voter {
+        &self.voter
+    }
+}
+
+impl<V: DerefMut<Target = Voter>, D: Deref<Target = [DepositEntry]>> DerefMut
+    for VoterAccount<V, D>
+{
+    fn deref_mut(&mut self) -> &mut Voter {
+        &mut self.voter
+    }
+}
+
+impl<V: Deref<Target = Voter>, D: Deref<Target = [DepositEntry]>> VoterAccount<V, D> {
+    /// Number of deposit entries, including unused ones
+    pub fn deposit_entry_count(&self) -> usize {
+        self.voter.deposits.len() + self.extra_deposits.len()
+    }
+
+    /// All deposit entries, including unused ones, in index order
+    pub fn deposits(&self) -> impl Iterator<Item = &DepositEntry> {
+        self.voter.deposits.iter().chain(self.extra_deposits.iter())
+    }
+
+    pub fn deposit(&self, index: usize) -> Option<&DepositEntry> {
+        let fixed_len = self.voter.deposits.len();
+        if index < fixed_len {
+            Some(&self.voter.deposits[index])
+        } else {
+            self.extra_deposits.get(index - fixed_len)
+        }
+    }
+
+    /// Native tokens of a voting mint deposited across all deposit entries
+    pub fn deposited_native(&self, voting_mint_config_idx: usize) -> u64 {
+        self.deposits()
+            .filter(|d| d.is_used && d.voting_mint_config_idx as usize == voting_mint_config_idx)
+            .map(|d| d.amount_deposited_native)
+            .sum()
+    }
+
+    /// The voter's share of Registrar::voting_mint_totals
+    pub fn voting_mint_totals(&self) -> [VotingMintTotals; MAX_VOTING_MINTS] {
+        let mut totals = [VotingMintTotals::default(); MAX_VOTING_MINTS];
+        for d_entry in self.deposits().filter(|d| d.is_used) {
+            totals[d_entry.voting_mint_config_idx as usize].add_deposit_entry(d_entry);
+        }
+        totals
+    }
+
     /// The full vote weight available to the voter
     pub fn weight(&self, registrar: &Registrar) -> Result<u64> {
-        let curr_ts = registrar.clock_unix_timestamp();
-        self.deposits
-            .iter()
+        self.weight_at(registrar, registrar.clock_unix_timestamp())
+    }
+
+    /// The full vote weight available to the voter at `curr_ts`
+    pub fn weight_at(&self, registrar: &Registrar, curr_ts: i64) -> Result<u64> {
+        self.deposits()
             .filter(|d| d.is_used)
             .try_fold(0u64, |sum, d| {
                 d.voting_power(
@@ -35,8 +214,7 @@ impl Voter {
 
     /// The vote weight available to the voter when ignoring any lockup effects
     pub fn weight_baseline(&self, registrar: &Registrar) -> Result<u64> {
-        self.deposits
-            .iter()
+        self.deposits()
             .filter(|d| d.is_used)
             .try_fold(0u64, |sum, d| {
                 registrar.voting_mints[d.voting_mint_config_idx as usize]
@@ -54,8 +232,7 @@ impl Voter {
         at_ts: i64,
     ) -> Result<u64> {
         require_gte!(at_ts, curr_ts, VsrError::InvalidTimestampArguments);
-        self.deposits
-            .iter()
+        self.deposits()
             .filter(|d| d.is_used)
             .try_fold(0u64, |sum, d| {
                 let mint_config = &registrar.voting_mints[d.voting_mint_config_idx as usize];
@@ -65,24 +242,84 @@ impl Voter {
                     curr_ts,
                     at_ts,
                     max_locked_vote_weight,
-                    mint_config.lockup_saturation_secs,
+                    &mint_config.lockup_curve(),
                 )?;
                 Ok(sum.checked_add(amount).unwrap())
             })
     }
+}
+
+impl<V: DerefMut<Target = Voter>, D: DerefMut<Target = [DepositEntry]>> VoterAccount<V, D> {
+    /// All deposit entries, including unused ones, in index order
+    pub fn deposits_mut(&mut self) -> impl Iterator<Item = &mut DepositEntry> {
+        self.voter
+            .deposits
+            .iter_mut()
+            .chain(self.extra_deposits.iter_mut())
+    }
+
+    pub fn deposit_mut(&mut self, index: usize) -> Option<&mut DepositEntry> {
+        let fixed_len = self.voter.deposits.len();
+        if index < fixed_len {
+            Some(&mut self.voter.deposits[index])
+        } else {
+            self.extra_deposits.get_mut(index - fixed_len)
+        }
+    }
 
     pub fn active_deposit_mut(&mut self, index: u8) -> Result<&mut DepositEntry> {
         let index = index as usize;
         require_gt!(
-            self.deposits.len(),
+            self.deposit_entry_count(),
             index,
             VsrError::OutOfBoundsDepositEntryIndex
         );
-        let d = &mut self.deposits[index];
+        let d = self.deposit_mut(index).unwrap();
         require!(d.is_used, VsrError::UnusedDepositEntryIndex);
         Ok(d)
     }
+}
+
+impl Voter {
+    /// Checks that the current instruction isn't executed through CPI, given
+    /// the instructions sysvar.
+    ///
+    /// Creating and transferring voters is forbidden from CPI. The goal is to
+    /// make automation impossible that weakens some of the limitations
+    /// intentionally imposed on locked tokens, like programs controlling voters
+    /// trading them.
+    pub fn assert_not_cpi(instructions: &AccountInfo, program_id: &Pubkey) -> Result<()> {
+        let current_index = tx_instructions::load_current_index_checked(instructions)? as usize;
+        let current_ixn =
+            tx_instructions::load_instruction_at_checked(current_index, instructions)?;
+        require_keys_eq!(current_ixn.program_id, *program_id, VsrError::ForbiddenCpi);
+        Ok(())
+    }
+
+    /// The wallet whose token owner record votes with this voter's weight:
+    /// the delegate if one is set, otherwise the voter authority.
+    ///
+    /// A revoked delegate stays the voting owner until its revocation
+    /// completes, since its votes may still use the weight.
+    pub fn voting_owner(&self) -> Pubkey {
+        if self.delegate == Pubkey::default() {
+            self.voter_authority
+        } else {
+            self.delegate
+        }
+    }
+
+    /// The `governing_token_owner` of the voter weight record: the voting
+    /// owner, or nobody while a delegate revocation is pending.
+    pub fn voter_weight_record_owner(&self) -> Pubkey {
+        if self.delegate_revoked {
+            Pubkey::default()
+        } else {
+            self.voting_owner()
+        }
+    }
 
+    /// Loads the token owner record of the voting owner, see voting_owner().
     pub fn load_token_owner_record(
         &self,
         account_info: &AccountInfo,
@@ -96,11 +333,70 @@ impl Voter {
         )?;
         require_keys_eq!(
             record.governing_token_owner,
-            self.voter_authority,
+            self.voting_owner(),
             VsrError::InvalidTokenOwnerRecord
         );
         Ok(record)
     }
+
+    /// Checks that the voting owner's token owner record has no outstanding
+    /// votes or proposals, which would otherwise keep using this voter's weight.
+    ///
+    /// While a delegate revocation is pending only the revoked delegate's
+    /// votes count: its proposals don't use the weight, and anyone can
+    /// relinquish its votes once voting on them has ended. That way an
+    /// uncooperative delegate can't keep the voter's tokens locked.
+    pub fn assert_token_owner_record_released(
+        &self,
+        record: &token_owner_record::TokenOwnerRecordV2,
+    ) -> Result<()> {
+        if !self.delegate_revoked {
+            record.assert_can_withdraw_governing_tokens()?;
+        } else if record.unrelinquished_votes_count > 0 {
+            return Err(ProgramError::from(
+                GovernanceError::AllVotesMustBeRelinquishedToWithdrawGoverningTokens,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Loads the voting owner's token owner record, or None if it doesn't
+    /// exist yet at its expected address: its owner can't have voted.
+    pub fn load_voting_owner_token_owner_record(
+        &self,
+        account_info: &AccountInfo,
+        registrar: &Registrar,
+    ) -> Result<Option<token_owner_record::TokenOwnerRecordV2>> {
+        require_keys_eq!(
+            *account_info.key,
+            token_owner_record::get_token_owner_record_address(
+                &registrar.governance_program_id,
+                &registrar.realm,
+                &registrar.realm_governing_token_mint,
+                &self.voting_owner(),
+            ),
+            VsrError::InvalidTokenOwnerRecord
+        );
+        if account_info.data_is_empty() {
+            return Ok(None);
+        }
+        self.load_token_owner_record(account_info, registrar)
+            .map(Some)
+    }
+
+    /// Checks that the voting owner's token owner record doesn't use this
+    /// voter's weight anymore, see assert_token_owner_record_released.
+    pub fn assert_voting_owner_can_withdraw(
+        &self,
+        account_info: &AccountInfo,
+        registrar: &Registrar,
+    ) -> Result<()> {
+        if let Some(record) = self.load_voting_owner_token_owner_record(account_info, registrar)? {
+            self.assert_token_owner_record_released(&record)?;
+        }
+        Ok(())
+    }
 }
 
 #[macro_export]
@@ -116,3 +412,66 @@ macro_rules! voter_seeds {
 }
 
 pub use voter_seeds;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::__private::bytemuck::Zeroable;
+
+    #[test]
+    pub fn extra_deposit_entry_counts() {
+        let entry_len = std::mem::size_of::<DepositEntry>();
+        assert_eq!(extra_deposit_entry_count(VOTER_ACCOUNT_LEN).unwrap(), 0);
+        assert_eq!(
+            extra_deposit_entry_count(VOTER_ACCOUNT_LEN + 3 * entry_len).unwrap(),
+            3
+        );
+        assert!(extra_deposit_entry_count(VOTER_ACCOUNT_LEN - 8).is_err());
+        assert!(extra_deposit_entry_count(VOTER_ACCOUNT_LEN + 8).is_err());
+    }
+
+    #[test]
+    pub fn voter_account_with_extra_deposits() -> Result<()> {
+        let entry_len = std::mem::size_of::<DepositEntry>();
+        let mut voter = Voter::zeroed();
+        voter.deposits[31].is_used = true;
+        voter.deposits[31].amount_deposited_native = 31;
+
+        // u64 storage keeps the data aligned, like the runtime does
+        let mut storage = vec![0u64; (VOTER_ACCOUNT_LEN + 2 * entry_len) / 8];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut storage);
+        data[..8].copy_from_slice(&Voter::discriminator());
+        data[8..VOTER_ACCOUNT_LEN].copy_from_slice(bytemuck::bytes_of(&voter));
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let account_info =
+            AccountInfo::new(&key, false, true, &mut lamports, data, &crate::ID, false, 0);
+        {
+            let mut voter = VoterRefMut::load(&account_info)?;
+            assert_eq!(voter.deposit_entry_count(), 34);
+            assert!(voter.deposit_mut(34).is_none());
+            let d = voter.deposit_mut(33).unwrap();
+            d.is_used = true;
+            d.amount_deposited_native = 33;
+            assert_eq!(voter.active_deposit_mut(33)?.amount_deposited_native, 33);
+            assert!(voter.active_deposit_mut(32).is_err());
+            assert!(voter.active_deposit_mut(34).is_err());
+        }
+
+        let voter = VoterAccountData::try_from_account_data(&account_info.try_borrow_data()?)?;
+        let used: Vec<u64> = voter
+            .deposits()
+            .filter(|d| d.is_used)
+            .map(|d| d.amount_deposited_native)
+            .collect();
+        assert_eq!(used, vec![31, 33]);
+        assert_eq!(voter.extra_deposits[1].amount_deposited_native, 33);
+
+        // the weight counts the extra entries too
+        let mut registrar = Registrar::default();
+        registrar.voting_mints[0].baseline_vote_weight_scaled_factor = 1_000_000_000;
+        assert_eq!(voter.weight_baseline(&registrar)?, 31 + 33);
+        Ok(())
+    }
+}
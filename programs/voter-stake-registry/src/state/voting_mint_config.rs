@@ -1,16 +1,17 @@
 use crate::error::*;
-use anchor_lang::__private::bytemuck::Zeroable;
+use crate::state::lockup::{Lockup, SECS_PER_DAY};
+use crate::state::lockup_curve::*;
 use anchor_lang::prelude::*;
 use std::convert::TryFrom;
 
-const SCALED_FACTOR_BASE: u64 = 1_000_000_000;
+pub(crate) const SCALED_FACTOR_BASE: u64 = 1_000_000_000;
 
 /// Exchange rate for an asset that can be used to mint voting rights.
 ///
 /// See documentation of configure_voting_mint for details on how
 /// native token amounts convert to vote weight.
 #[zero_copy]
-#[derive(Default)]
+#[derive(AnchorSerialize, AnchorDeserialize, Default)]
 pub struct VotingMintConfig {
     /// Mint for this entry.
     pub mint: Pubkey,
@@ -27,7 +28,8 @@ pub struct VotingMintConfig {
     ///
     /// This is the extra votes gained for lockups lasting lockup_saturation_secs or
     /// longer. Shorter lockups receive only a fraction of the maximum extra vote weight,
-    /// based on lockup_time divided by lockup_saturation_secs.
+    /// based on lockup_time divided by lockup_saturation_secs, or on the lockup curve
+    /// if lockup_curve_kind isn't Linear.
     ///
     /// In 1/SCALED_FACTOR_BASE units.
     pub max_extra_lockup_vote_weight_scaled_factor: u64,
@@ -38,11 +40,51 @@ pub struct VotingMintConfig {
     /// Number of digits to shift native amounts, applying a 10^digit_shift factor.
     pub digit_shift: i8,
 
-    // Empty bytes for future upgrades.
-    pub reserved1: [u8; 7],
-    pub reserved2: [u64; 7], // split because `Default` does not support [u8; 63]
+    /// Set by remove_voting_mint. Deprecated mints grant no vote weight and
+    /// accept no new deposits, but existing deposits can still be withdrawn.
+    pub deprecated: bool,
+
+    /// Whether deposited_native has been tracked since the mint was configured.
+    /// False for mints that were configured before the field existed.
+    pub deposited_native_tracked: bool,
+
+    /// Shape of the extra vote weight over the lockup time, set by
+    /// configure_lockup_curve.
+    pub lockup_curve_kind: LockupCurveKind,
+
+    /// Number of used entries in lockup_curve_points, zero for linear curves.
+    pub lockup_curve_point_count: u8,
+
+    /// The lockup kinds deposits of this mint may use are stored in
+    /// Registrar::allowed_lockup_kinds.
+    pub reserved1: u8,
+
+    /// Maximum number of days tokens of this mint may be locked up for, from
+    /// when they get locked. Zero for no maximum.
+    pub max_lockup_days: u16,
+
+    /// Total native tokens of this mint in the vaults of the registrar's voters.
+    ///
+    /// Only accurate if deposited_native_tracked is set.
+    pub deposited_native: u64,
+
+    /// Breakpoints or tiers of the lockup curve, see LockupCurveKind.
+    pub lockup_curve_points: [LockupCurvePoint; MAX_LOCKUP_CURVE_POINTS],
+
+    /// Smallest number of native tokens deposit and grant accept, zero for
+    /// no minimum.
+    pub min_deposit_native: u64,
+
+    /// Maximum native tokens of this mint a single voter may have deposited,
+    /// zero for no cap.
+    ///
+    /// The registrar-wide cap is Registrar::max_deposited_native.
+    pub max_voter_deposited_native: u64,
 }
-const_assert!(std::mem::size_of::<VotingMintConfig>() == 2 * 32 + 3 * 8 + 1 + 63);
+const_assert!(
+    std::mem::size_of::<VotingMintConfig>()
+        == 2 * 32 + 6 * 8 + 1 + 2 + 2 + 1 + 2 + MAX_LOCKUP_CURVE_POINTS * 8
+);
 const_assert!(std::mem::size_of::<VotingMintConfig>() % 8 == 0);
 
 impl VotingMintConfig {
@@ -94,11 +136,47 @@ impl VotingMintConfig {
         )
     }
 
+    /// The lockup curve that scales max_extra_lockup_vote_weight with the
+    /// remaining lockup time.
+    pub fn lockup_curve(&self) -> LockupCurve {
+        match self.lockup_curve_kind {
+            LockupCurveKind::Linear => LockupCurve::linear(self.lockup_saturation_secs),
+            // other curves were validated by configure_lockup_curve
+            kind => {
+                let points = self.lockup_curve_points;
+                LockupCurve::new_unchecked(
+                    kind,
+                    self.lockup_saturation_secs,
+                    &points[..self.lockup_curve_point_count as usize],
+                )
+            }
+        }
+    }
+
+    /// Stores a lockup curve, also updating lockup_saturation_secs.
+    pub fn set_lockup_curve(&mut self, curve: &LockupCurve) {
+        let mut points = [LockupCurvePoint::default(); MAX_LOCKUP_CURVE_POINTS];
+        points[..curve.points().len()].copy_from_slice(curve.points());
+        self.lockup_curve_kind = curve.kind;
+        self.lockup_curve_point_count = curve.points().len() as u8;
+        self.lockup_curve_points = points;
+        self.lockup_saturation_secs = curve.lockup_saturation_secs;
+    }
+
     /// Whether this voting mint is configured.
     pub fn in_use(&self) -> bool {
         self.mint != Pubkey::default()
     }
 
+    /// Whether the mint was removed with remove_voting_mint and all its
+    /// tokens were withdrawn since, so its index can be reused.
+    ///
+    /// Empty deposit entries may still refer to the index, they become
+    /// entries of the next mint configured there.
+    pub fn removed_without_deposits(&self) -> bool {
+        self.deprecated && self.deposited_native_tracked && self.deposited_native == 0
+    }
+
     /// Do tokens of this mint contribute to voting weight?
     ///
     /// DAOs may configure mints without any vote weight contributions if they
@@ -108,6 +186,36 @@ impl VotingMintConfig {
         self.baseline_vote_weight_scaled_factor > 0
             || self.max_extra_lockup_vote_weight_scaled_factor > 0
     }
-}
 
-unsafe impl Zeroable for VotingMintConfig {}
+    /// Checks the lockup length restriction set with configure_lockup_limits for
+    /// tokens of this mint that get locked up with `lockup` at `curr_ts`.
+    ///
+    /// Use Registrar::check_lockup, which also checks the lockup kind.
+    pub fn check_lockup(&self, lockup: &Lockup, curr_ts: i64) -> Result<()> {
+        if self.max_lockup_days > 0 {
+            require_gte!(
+                self.max_lockup_days as u64 * SECS_PER_DAY,
+                lockup.seconds_left(curr_ts),
+                VsrError::LockupTooLong
+            );
+        }
+        Ok(())
+    }
+
+    /// Book keeping for `amount` native tokens arriving in a voter's vault.
+    pub fn add_deposited(&mut self, amount: u64) {
+        self.deposited_native = self.deposited_native.checked_add(amount).unwrap();
+    }
+
+    /// Book keeping for `amount` native tokens leaving a voter's vault.
+    pub fn sub_deposited(&mut self, amount: u64) -> Result<()> {
+        self.deposited_native = if self.deposited_native_tracked {
+            self.deposited_native
+                .checked_sub(amount)
+                .ok_or_else(|| error!(VsrError::InternalProgramError))?
+        } else {
+            self.deposited_native.saturating_sub(amount)
+        };
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account as TokenAccount, Mint};
+
+/// Voting mints may belong to either the spl-token or the token-2022 program.
+///
+/// Anchor's typed token accounts only support spl-token, which is why the
+/// instructions take unchecked accounts and validate them with the helpers below.
+pub fn is_token_program(key: &Pubkey) -> bool {
+    *key == anchor_spl::token::ID || *key == spl_token_2022::ID
+}
+
+pub fn check_token_program(token_program: &AccountInfo) -> Result<()> {
+    require!(
+        is_token_program(token_program.key),
+        VsrError::InvalidTokenProgram
+    );
+    Ok(())
+}
+
+/// Loads a mint owned by either token program. Mint extensions are skipped.
+pub fn load_mint(account_info: &AccountInfo) -> Result<Mint> {
+    require!(
+        is_token_program(account_info.owner),
+        VsrError::InvalidTokenProgram
+    );
+    let data = account_info.try_borrow_data()?;
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base)
+}
+
+/// Loads a token account owned by either token program. Account extensions are skipped.
+pub fn load_token_account(account_info: &AccountInfo) -> Result<TokenAccount> {
+    require!(
+        is_token_program(account_info.owner),
+        VsrError::InvalidTokenProgram
+    );
+    let data = account_info.try_borrow_data()?;
+    Ok(StateWithExtensions::<TokenAccount>::unpack(&data)?.base)
+}
+
+/// Loads a token account and checks that it's owned by the token program and
+/// holds tokens of `mint`.
+pub fn load_token_account_for_mint(
+    account_info: &AccountInfo,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<TokenAccount> {
+    require_keys_eq!(
+        *account_info.owner,
+        *token_program,
+        VsrError::InvalidTokenProgram
+    );
+    let token_account = load_token_account(account_info)?;
+    require_keys_eq!(token_account.mint, *mint, VsrError::InvalidMint);
+    Ok(token_account)
+}
+
+/// The address of the voter's vault for `mint`: the associated token account,
+/// which depends on the token program.
+pub fn vault_address(voter: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(voter, mint, token_program)
+}
+
+/// Loads the voter's vault for `mint` and checks that it is the associated
+/// token account owned by `voter`.
+pub fn load_vault(
+    vault: &AccountInfo,
+    voter: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<TokenAccount> {
+    require_keys_eq!(
+        *vault.key,
+        vault_address(voter, mint, token_program),
+        VsrError::InvalidVault
+    );
+    let vault = load_token_account_for_mint(vault, mint, token_program)?;
+    require_keys_eq!(vault.owner, *voter, VsrError::InvalidVault);
+    Ok(vault)
+}
+
+/// Creates the voter's vault for `mint` if it doesn't exist yet.
+pub fn create_vault_if_needed<'info>(
+    payer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    voter: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if !vault.data_is_empty() {
+        return Ok(());
+    }
+    let ix = spl_associated_token_account::instruction::create_associated_token_account(
+        payer.key,
+        voter.key,
+        mint.key,
+        token_program.key,
+    );
+    invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            vault.clone(),
+            voter.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
+        &[],
+    )
+    .map_err(Into::into)
+}
+
+/// Transfers `amount` native tokens with `transfer_checked`, which is supported
+/// by both token programs and required for token-2022 mints with transfer fees.
+///
+/// Note that the amount that arrives at `to` may be less than `amount` when the
+/// mint charges transfer fees.
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let decimals = load_mint(mint)?.decimals;
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            from.clone(),
+            mint.clone(),
+            to.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Transfers `amount` native tokens into `vault` with transfer_checked and
+/// returns how many arrived there.
+///
+/// Token-2022 mints may charge a transfer fee, so only the returned amount
+/// should be credited to the depositor.
+pub fn transfer_to_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let vault_before = load_token_account(vault)?.amount;
+    transfer_checked(token_program, from, mint, vault, authority, &[], amount)?;
+    let vault_after = load_token_account(vault)?.amount;
+    Ok(vault_after.checked_sub(vault_before).unwrap())
+}
+
+/// Closes an empty token account, sending its lamports to `destination`.
+pub fn close_account<'info>(
+    token_program: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = spl_token_2022::instruction::close_account(
+        token_program.key,
+        account.key,
+        destination.key,
+        authority.key,
+        &[],
+    )?;
+    invoke_signed(
+        &ix,
+        &[
+            account.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -21,11 +21,12 @@ pub struct RegistrarCookie {
     pub mint: MintCookie,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct VotingMintConfigCookie {
     pub mint: MintCookie,
 }
 
+#[derive(Debug)]
 pub struct VoterCookie {
     pub address: Pubkey,
     pub authority: Pubkey,
@@ -95,7 +96,7 @@ impl AddinCookie {
         &self,
         registrar: &RegistrarCookie,
         authority: &Keypair,
-        _payer: &Keypair,
+        payer: &Keypair,
         index: u16,
         mint: &MintCookie,
         digit_shift: i8,
@@ -105,6 +106,38 @@ impl AddinCookie {
         grant_authority: Option<Pubkey>,
         other_mints: Option<&[Pubkey]>,
     ) -> VotingMintConfigCookie {
+        self.try_configure_voting_mint(
+            registrar,
+            authority,
+            payer,
+            index,
+            mint,
+            digit_shift,
+            baseline_vote_weight_scaled_factor,
+            max_extra_lockup_vote_weight_scaled_factor,
+            lockup_saturation_secs,
+            grant_authority,
+            other_mints,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[allow(dead_code)]
+    pub async fn try_configure_voting_mint(
+        &self,
+        registrar: &RegistrarCookie,
+        authority: &Keypair,
+        _payer: &Keypair,
+        index: u16,
+        mint: &MintCookie,
+        digit_shift: i8,
+        baseline_vote_weight_scaled_factor: f64,
+        max_extra_lockup_vote_weight_scaled_factor: f64,
+        lockup_saturation_secs: u64,
+        grant_authority: Option<Pubkey>,
+        other_mints: Option<&[Pubkey]>,
+    ) -> std::result::Result<VotingMintConfigCookie, BanksClientError> {
         let deposit_mint = mint.pubkey.unwrap();
 
         let data = anchor_lang::InstructionData::data(
@@ -150,52 +183,67 @@ impl AddinCookie {
 
         self.solana
             .process_transaction(&instructions, Some(&[&signer2]))
-            .await
-            .unwrap();
+            .await?;
 
-        VotingMintConfigCookie { mint: mint.clone() }
+        Ok(VotingMintConfigCookie { mint: mint.clone() })
     }
 
-    pub async fn create_voter(
+    #[allow(dead_code)]
+    pub async fn configure_max_vote_weight_mode(
         &self,
         registrar: &RegistrarCookie,
-        token_owner_record: &TokenOwnerRecordCookie,
         authority: &Keypair,
-        payer: &Keypair,
-    ) -> VoterCookie {
-        let (voter, voter_bump) = Pubkey::find_program_address(
-            &[
-                &registrar.address.to_bytes(),
-                b"voter".as_ref(),
-                &authority.pubkey().to_bytes(),
-            ],
-            &self.program_id,
+        mode: voter_stake_registry::state::MaxVoteWeightMode,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::ConfigureMaxVoteWeightMode { mode },
         );
-        let (voter_weight_record, voter_weight_record_bump) = Pubkey::find_program_address(
-            &[
-                &registrar.address.to_bytes(),
-                b"voter-weight-record".as_ref(),
-                &authority.pubkey().to_bytes(),
-            ],
-            &self.program_id,
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::ConfigureMaxVoteWeightMode {
+                registrar: registrar.address,
+                realm_authority: authority.pubkey(),
+            },
+            None,
         );
 
-        let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::CreateVoter {
-                voter_bump,
-                voter_weight_record_bump,
-            });
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn configure_deposit_limits(
+        &self,
+        registrar: &RegistrarCookie,
+        authority: &Keypair,
+        index: u16,
+        min_deposit_native: u64,
+        max_voter_deposited_native: u64,
+        max_deposited_native: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::ConfigureDepositLimits {
+                idx: index,
+                min_deposit_native,
+                max_voter_deposited_native,
+                max_deposited_native,
+            },
+        );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::CreateVoter {
-                voter,
-                voter_weight_record,
+            &voter_stake_registry::accounts::ConfigureDepositLimits {
                 registrar: registrar.address,
-                voter_authority: authority.pubkey(),
-                payer: payer.pubkey(),
-                system_program: solana_sdk::system_program::id(),
-                rent: solana_program::sysvar::rent::id(),
-                instructions: solana_program::sysvar::instructions::id(),
+                realm_authority: authority.pubkey(),
             },
             None,
         );
@@ -207,58 +255,34 @@ impl AddinCookie {
         }];
 
         // clone the secrets
-        let signer1 = Keypair::from_base58_string(&payer.to_base58_string());
-        let signer2 = Keypair::from_base58_string(&authority.to_base58_string());
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
 
         self.solana
-            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .process_transaction(&instructions, Some(&[&signer]))
             .await
-            .unwrap();
-
-        VoterCookie {
-            address: voter,
-            authority: authority.pubkey(),
-            voter_weight_record,
-            token_owner_record: token_owner_record.address,
-        }
     }
 
-    pub async fn create_deposit_entry(
+    #[allow(dead_code)]
+    pub async fn configure_lockup_limits(
         &self,
         registrar: &RegistrarCookie,
-        voter: &VoterCookie,
-        voter_authority: &Keypair,
-        voting_mint: &VotingMintConfigCookie,
-        deposit_entry_index: u8,
-        lockup_kind: voter_stake_registry::state::LockupKind,
-        start_ts: Option<u64>,
-        periods: u32,
-        allow_clawback: bool,
+        authority: &Keypair,
+        index: u16,
+        allowed_lockup_kinds: u16,
+        max_lockup_days: u16,
     ) -> std::result::Result<(), BanksClientError> {
-        let vault = voter.vault_address(&voting_mint);
-
         let data = anchor_lang::InstructionData::data(
-            &voter_stake_registry::instruction::CreateDepositEntry {
-                deposit_entry_index,
-                kind: lockup_kind,
-                start_ts,
-                periods,
-                allow_clawback,
+            &voter_stake_registry::instruction::ConfigureLockupLimits {
+                idx: index,
+                allowed_lockup_kinds,
+                max_lockup_days,
             },
         );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::CreateDepositEntry {
-                vault,
+            &voter_stake_registry::accounts::ConfigureLockupLimits {
                 registrar: registrar.address,
-                voter: voter.address,
-                voter_authority: voter_authority.pubkey(),
-                payer: voter_authority.pubkey(),
-                deposit_mint: voting_mint.mint.pubkey.unwrap(),
-                system_program: solana_sdk::system_program::id(),
-                token_program: spl_token::id(),
-                associated_token_program: spl_associated_token_account::id(),
-                rent: solana_program::sysvar::rent::id(),
+                realm_authority: authority.pubkey(),
             },
             None,
         );
@@ -270,40 +294,42 @@ impl AddinCookie {
         }];
 
         // clone the secrets
-        let signer1 = Keypair::from_base58_string(&voter_authority.to_base58_string());
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
 
         self.solana
-            .process_transaction(&instructions, Some(&[&signer1]))
+            .process_transaction(&instructions, Some(&[&signer]))
             .await
     }
 
     #[allow(dead_code)]
-    pub async fn deposit(
+    pub async fn configure_lockup_curve(
         &self,
         registrar: &RegistrarCookie,
-        voter: &VoterCookie,
-        voting_mint: &VotingMintConfigCookie,
         authority: &Keypair,
-        token_address: Pubkey,
-        deposit_entry_index: u8,
-        amount: u64,
+        index: u16,
+        kind: voter_stake_registry::state::LockupCurveKind,
+        points: &[(u32, f64)],
     ) -> std::result::Result<(), BanksClientError> {
-        let vault = voter.vault_address(&voting_mint);
-
-        let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Deposit {
-                deposit_entry_index,
-                amount,
-            });
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::ConfigureLockupCurve {
+                idx: index,
+                kind,
+                points: points
+                    .iter()
+                    .map(
+                        |&(lockup_secs, factor)| voter_stake_registry::state::LockupCurvePoint {
+                            lockup_secs,
+                            scaled_factor: (factor * 1e9) as u32,
+                        },
+                    )
+                    .collect(),
+            },
+        );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::Deposit {
+            &voter_stake_registry::accounts::ConfigureLockupCurve {
                 registrar: registrar.address,
-                voter: voter.address,
-                vault: vault,
-                deposit_token: token_address,
-                deposit_authority: authority.pubkey(),
-                token_program: spl_token::id(),
+                realm_authority: authority.pubkey(),
             },
             None,
         );
@@ -323,70 +349,50 @@ impl AddinCookie {
     }
 
     #[allow(dead_code)]
-    pub async fn grant(
+    pub async fn sync_realm_authority(
         &self,
         registrar: &RegistrarCookie,
-        voter_authority: Pubkey,
-        voting_mint: &VotingMintConfigCookie,
-        lockup_kind: voter_stake_registry::state::LockupKind,
-        start_ts: Option<u64>,
-        periods: u32,
-        allow_clawback: bool,
-        amount: u64,
-        deposit_token: Pubkey,
-        token_authority: &Keypair,
-        grant_authority: &Keypair,
-    ) -> std::result::Result<VoterCookie, BanksClientError> {
-        let (voter, voter_bump) = Pubkey::find_program_address(
-            &[
-                &registrar.address.to_bytes(),
-                b"voter".as_ref(),
-                &voter_authority.to_bytes(),
-            ],
-            &self.program_id,
+        realm: &GovernanceRealmCookie,
+    ) {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::SyncRealmAuthority {},
         );
-        let (voter_weight_record, voter_weight_record_bump) = Pubkey::find_program_address(
-            &[
-                &registrar.address.to_bytes(),
-                b"voter-weight-record".as_ref(),
-                &voter_authority.to_bytes(),
-            ],
-            &self.program_id,
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::SyncRealmAuthority {
+                registrar: registrar.address,
+                realm: realm.realm,
+            },
+            None,
         );
-        let voter_cookie = VoterCookie {
-            address: voter,
-            authority: voter_authority,
-            voter_weight_record,
-            token_owner_record: Pubkey::new_unique(), // don't have it
-        };
-        let vault = voter_cookie.vault_address(&voting_mint);
 
-        let data = anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Grant {
-            voter_bump,
-            voter_weight_record_bump,
-            kind: lockup_kind,
-            start_ts,
-            periods,
-            allow_clawback,
-            amount,
-        });
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        self.solana
+            .process_transaction(&instructions, None)
+            .await
+            .unwrap();
+    }
+
+    #[allow(dead_code)]
+    pub async fn remove_voting_mint(
+        &self,
+        registrar: &RegistrarCookie,
+        authority: &Keypair,
+        index: u16,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::RemoveVotingMint { idx: index },
+        );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::Grant {
+            &voter_stake_registry::accounts::RemoveVotingMint {
                 registrar: registrar.address,
-                voter,
-                voter_authority,
-                voter_weight_record,
-                vault,
-                deposit_token,
-                token_authority: token_authority.pubkey(),
-                grant_authority: grant_authority.pubkey(),
-                payer: token_authority.pubkey(),
-                deposit_mint: voting_mint.mint.pubkey.unwrap(),
-                system_program: solana_sdk::system_program::id(),
-                token_program: spl_token::id(),
-                associated_token_program: spl_associated_token_account::id(),
-                rent: solana_program::sysvar::rent::id(),
+                realm_authority: authority.pubkey(),
             },
             None,
         );
@@ -398,44 +404,901 @@ impl AddinCookie {
         }];
 
         // clone the secrets
-        let signer1 = Keypair::from_base58_string(&grant_authority.to_base58_string());
-        let signer2 = Keypair::from_base58_string(&token_authority.to_base58_string());
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
 
         self.solana
-            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
-            .await?;
-
-        Ok(voter_cookie)
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
     }
 
     #[allow(dead_code)]
-    pub async fn clawback(
+    pub async fn create_voter(
         &self,
         registrar: &RegistrarCookie,
-        voter: &VoterCookie,
-        voting_mint: &VotingMintConfigCookie,
-        realm_authority: &Keypair,
-        token_address: Pubkey,
-        deposit_entry_index: u8,
-    ) -> std::result::Result<(), BanksClientError> {
-        let vault = voter.vault_address(&voting_mint);
+        token_owner_record: &TokenOwnerRecordCookie,
+        authority: &Keypair,
+        payer: &Keypair,
+    ) -> VoterCookie {
+        let (voter, voter_bump) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter".as_ref(),
+                &authority.pubkey().to_bytes(),
+            ],
+            &self.program_id,
+        );
+        let (voter_weight_record, voter_weight_record_bump) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter-weight-record".as_ref(),
+                &authority.pubkey().to_bytes(),
+            ],
+            &self.program_id,
+        );
+
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::CreateVoter {
+                voter_bump,
+                voter_weight_record_bump,
+            });
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::CreateVoter {
+                voter,
+                voter_weight_record,
+                registrar: registrar.address,
+                voter_authority: authority.pubkey(),
+                payer: payer.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+                rent: solana_program::sysvar::rent::id(),
+                instructions: solana_program::sysvar::instructions::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&payer.to_base58_string());
+        let signer2 = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .await
+            .unwrap();
+
+        VoterCookie {
+            address: voter,
+            authority: authority.pubkey(),
+            voter_weight_record,
+            token_owner_record: token_owner_record.address,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn resize_voter(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        authority: &Keypair,
+        payer: &Keypair,
+        deposit_entry_count: u8,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::ResizeVoter {
+                deposit_entry_count,
+            });
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::ResizeVoter {
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_authority: authority.pubkey(),
+                payer: payer.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&payer.to_base58_string());
+        let signer2 = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn resize_registrar(
+        &self,
+        registrar: &RegistrarCookie,
+        payer: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::ResizeRegistrar {},
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::ResizeRegistrar {
+                registrar: registrar.address,
+                payer: payer.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&payer.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn backfill_voting_mint_totals(
+        &self,
+        registrar: &RegistrarCookie,
+        voters: &[&VoterCookie],
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::BackfillVotingMintTotals {},
+        );
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::BackfillVotingMintTotals {
+                registrar: registrar.address,
+            },
+            None,
+        );
+        for voter in voters {
+            accounts.push(anchor_lang::prelude::AccountMeta::new(voter.address, false));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        self.solana.process_transaction(&instructions, None).await
+    }
+
+    #[allow(dead_code)]
+    pub async fn finish_voting_mint_backfill(
+        &self,
+        registrar: &RegistrarCookie,
+        authority: &Keypair,
+        index: u16,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::FinishVotingMintBackfill { idx: index },
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::FinishVotingMintBackfill {
+                registrar: registrar.address,
+                realm_authority: authority.pubkey(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn create_deposit_entry(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voter_authority: &Keypair,
+        voting_mint: &VotingMintConfigCookie,
+        deposit_entry_index: u8,
+        lockup_kind: voter_stake_registry::state::LockupKind,
+        start_ts: Option<u64>,
+        periods: u32,
+        allow_clawback: bool,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(&voting_mint);
+
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::CreateDepositEntry {
+                deposit_entry_index,
+                kind: lockup_kind,
+                start_ts,
+                periods,
+                allow_clawback,
+            },
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::CreateDepositEntry {
+                vault,
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_authority: voter_authority.pubkey(),
+                payer: voter_authority.pubkey(),
+                deposit_mint: voting_mint.mint.pubkey.unwrap(),
+                system_program: solana_sdk::system_program::id(),
+                token_program: voting_mint.mint.token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                rent: solana_program::sysvar::rent::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&voter_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn deposit(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mint: &VotingMintConfigCookie,
+        authority: &Keypair,
+        token_address: Pubkey,
+        deposit_entry_index: u8,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(&voting_mint);
+
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Deposit {
+                deposit_entry_index,
+                amount,
+            });
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::Deposit {
+                registrar: registrar.address,
+                voter: voter.address,
+                vault,
+                deposit_token: token_address,
+                deposit_authority: authority.pubkey(),
+                deposit_mint: voting_mint.mint.pubkey.unwrap(),
+                token_program: voting_mint.mint.token_program,
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn deposit_unlocked(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mint: &VotingMintConfigCookie,
+        authority: &Keypair,
+        payer: &Keypair,
+        token_address: Pubkey,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(voting_mint);
+
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::DepositUnlocked { amount },
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::DepositUnlocked {
+                registrar: registrar.address,
+                voter: voter.address,
+                vault,
+                deposit_token: token_address,
+                deposit_authority: authority.pubkey(),
+                payer: payer.pubkey(),
+                deposit_mint: voting_mint.mint.pubkey.unwrap(),
+                system_program: solana_sdk::system_program::id(),
+                token_program: voting_mint.mint.token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                rent: solana_program::sysvar::rent::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&authority.to_base58_string());
+        let signer2 = Keypair::from_base58_string(&payer.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn grant(
+        &self,
+        registrar: &RegistrarCookie,
+        voter_authority: Pubkey,
+        voting_mint: &VotingMintConfigCookie,
+        lockup_kind: voter_stake_registry::state::LockupKind,
+        start_ts: Option<u64>,
+        periods: u32,
+        allow_clawback: bool,
+        amount: u64,
+        deposit_token: Pubkey,
+        token_authority: &Keypair,
+        grant_authority: &Keypair,
+    ) -> std::result::Result<VoterCookie, BanksClientError> {
+        self.grant_with_data(
+            registrar,
+            voter_authority,
+            voting_mint,
+            deposit_token,
+            token_authority,
+            grant_authority,
+            |voter_bump, voter_weight_record_bump| {
+                anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Grant {
+                    voter_bump,
+                    voter_weight_record_bump,
+                    kind: lockup_kind,
+                    start_ts,
+                    periods,
+                    allow_clawback,
+                    amount,
+                })
+            },
+        )
+        .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn grant_with_cliff(
+        &self,
+        registrar: &RegistrarCookie,
+        voter_authority: Pubkey,
+        voting_mint: &VotingMintConfigCookie,
+        lockup_kind: voter_stake_registry::state::LockupKind,
+        start_ts: Option<u64>,
+        cliff_periods: u32,
+        periods: u32,
+        allow_clawback: bool,
+        amount: u64,
+        deposit_token: Pubkey,
+        token_authority: &Keypair,
+        grant_authority: &Keypair,
+    ) -> std::result::Result<VoterCookie, BanksClientError> {
+        self.grant_with_data(
+            registrar,
+            voter_authority,
+            voting_mint,
+            deposit_token,
+            token_authority,
+            grant_authority,
+            |voter_bump, voter_weight_record_bump| {
+                anchor_lang::InstructionData::data(
+                    &voter_stake_registry::instruction::GrantWithCliff {
+                        voter_bump,
+                        voter_weight_record_bump,
+                        kind: lockup_kind,
+                        start_ts,
+                        periods,
+                        cliff_periods,
+                        allow_clawback,
+                        amount,
+                    },
+                )
+            },
+        )
+        .await
+    }
+
+    async fn grant_with_data(
+        &self,
+        registrar: &RegistrarCookie,
+        voter_authority: Pubkey,
+        voting_mint: &VotingMintConfigCookie,
+        deposit_token: Pubkey,
+        token_authority: &Keypair,
+        grant_authority: &Keypair,
+        data: impl FnOnce(u8, u8) -> Vec<u8>,
+    ) -> std::result::Result<VoterCookie, BanksClientError> {
+        let (voter, voter_bump) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter".as_ref(),
+                &voter_authority.to_bytes(),
+            ],
+            &self.program_id,
+        );
+        let (voter_weight_record, voter_weight_record_bump) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter-weight-record".as_ref(),
+                &voter_authority.to_bytes(),
+            ],
+            &self.program_id,
+        );
+        let voter_cookie = VoterCookie {
+            address: voter,
+            authority: voter_authority,
+            voter_weight_record,
+            token_owner_record: Pubkey::new_unique(), // don't have it
+        };
+        let vault = voter_cookie.vault_address(&voting_mint);
+
+        let data = data(voter_bump, voter_weight_record_bump);
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::Grant {
+                registrar: registrar.address,
+                voter,
+                voter_authority,
+                voter_weight_record,
+                vault,
+                deposit_token,
+                token_authority: token_authority.pubkey(),
+                grant_authority: grant_authority.pubkey(),
+                payer: token_authority.pubkey(),
+                deposit_mint: voting_mint.mint.pubkey.unwrap(),
+                system_program: solana_sdk::system_program::id(),
+                token_program: voting_mint.mint.token_program,
+                associated_token_program: spl_associated_token_account::id(),
+                rent: solana_program::sysvar::rent::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&grant_authority.to_base58_string());
+        let signer2 = Keypair::from_base58_string(&token_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .await?;
+
+        Ok(voter_cookie)
+    }
+
+    #[allow(dead_code)]
+    pub async fn clawback(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mint: &VotingMintConfigCookie,
+        realm_authority: &Keypair,
+        token_address: Pubkey,
+        deposit_entry_index: u8,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(&voting_mint);
+
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Clawback {
+                deposit_entry_index,
+            });
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::Clawback {
+                registrar: registrar.address,
+                voter: voter.address,
+                vault,
+                destination: token_address,
+                realm_authority: realm_authority.pubkey(),
+                mint: voting_mint.mint.pubkey.unwrap(),
+                token_program: voting_mint.mint.token_program,
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&realm_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn partial_clawback(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mint: &VotingMintConfigCookie,
+        realm_authority: &Keypair,
+        token_address: Pubkey,
+        deposit_entry_index: u8,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(voting_mint);
+
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::PartialClawback {
+                deposit_entry_index,
+                amount,
+            },
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::Clawback {
+                registrar: registrar.address,
+                voter: voter.address,
+                vault,
+                destination: token_address,
+                realm_authority: realm_authority.pubkey(),
+                mint: voting_mint.mint.pubkey.unwrap(),
+                token_program: voting_mint.mint.token_program,
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&realm_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn withdraw(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mint: &VotingMintConfigCookie,
+        authority: &Keypair,
+        token_address: Pubkey,
+        deposit_entry_index: u8,
+        amount: u64,
+    ) -> std::result::Result<(), BanksClientError> {
+        let vault = voter.vault_address(&voting_mint);
+
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Withdraw {
+                deposit_entry_index,
+                amount,
+            });
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::Withdraw {
+                registrar: registrar.address,
+                voter: voter.address,
+                token_owner_record: voter.token_owner_record,
+                voter_weight_record: voter.voter_weight_record,
+                vault,
+                destination: token_address,
+                voter_authority: authority.pubkey(),
+                mint: voting_mint.mint.pubkey.unwrap(),
+                token_program: voting_mint.mint.token_program,
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn close_voter(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mints: &[&VotingMintConfigCookie],
+        voter_authority: &Keypair,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::CloseVoter {});
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::CloseVoter {
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_authority: voter_authority.pubkey(),
+                sol_destination: voter_authority.pubkey(),
+            },
+            None,
+        );
+        for voting_mint in voting_mints {
+            accounts.push(anchor_lang::prelude::AccountMeta::new(
+                voter.vault_address(voting_mint),
+                false,
+            ));
+            accounts.push(anchor_lang::prelude::AccountMeta::new_readonly(
+                voting_mint.mint.token_program,
+                false,
+            ));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&voter_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    pub fn update_voter_weight_record_instruction(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+    ) -> Instruction {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::UpdateVoterWeightRecord {},
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::UpdateVoterWeightRecord {
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_weight_record: voter.voter_weight_record,
+                system_program: solana_sdk::system_program::id(),
+            },
+            None,
+        );
+
+        Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub async fn propose_voter_transfer(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        authority: &Keypair,
+        new_voter_authority: Option<Pubkey>,
+    ) -> std::result::Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::ProposeVoterTransfer {
+                new_voter_authority,
+            },
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::ProposeVoterTransfer {
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_authority: authority.pubkey(),
+                instructions: solana_program::sysvar::instructions::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+    }
+
+    #[allow(dead_code)]
+    pub async fn accept_voter_transfer(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+        voting_mints: &[&VotingMintConfigCookie],
+        new_token_owner_record: &TokenOwnerRecordCookie,
+        new_authority: &Keypair,
+        payer: &Keypair,
+    ) -> std::result::Result<VoterCookie, BanksClientError> {
+        let (new_voter, _) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter".as_ref(),
+                &new_authority.pubkey().to_bytes(),
+            ],
+            &self.program_id,
+        );
+        let (new_voter_weight_record, _) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"voter-weight-record".as_ref(),
+                &new_authority.pubkey().to_bytes(),
+            ],
+            &self.program_id,
+        );
+        let new_voter_cookie = VoterCookie {
+            address: new_voter,
+            authority: new_authority.pubkey(),
+            voter_weight_record: new_voter_weight_record,
+            token_owner_record: new_token_owner_record.address,
+        };
+
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::AcceptVoterTransfer {},
+        );
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::AcceptVoterTransfer {
+                registrar: registrar.address,
+                voter: voter.address,
+                voter_weight_record: voter.voter_weight_record,
+                token_owner_record: voter.token_owner_record,
+                new_voter,
+                new_voter_authority: new_authority.pubkey(),
+                new_voter_weight_record,
+                payer: payer.pubkey(),
+                sol_destination: new_authority.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+                associated_token_program: spl_associated_token_account::id(),
+                rent: solana_program::sysvar::rent::id(),
+                instructions: solana_program::sysvar::instructions::id(),
+            },
+            None,
+        );
+        for voting_mint in voting_mints {
+            accounts.push(anchor_lang::prelude::AccountMeta::new(
+                voter.vault_address(voting_mint),
+                false,
+            ));
+            accounts.push(anchor_lang::prelude::AccountMeta::new(
+                new_voter_cookie.vault_address(voting_mint),
+                false,
+            ));
+            accounts.push(anchor_lang::prelude::AccountMeta::new_readonly(
+                voting_mint.mint.pubkey.unwrap(),
+                false,
+            ));
+            accounts.push(anchor_lang::prelude::AccountMeta::new_readonly(
+                voting_mint.mint.token_program,
+                false,
+            ));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer1 = Keypair::from_base58_string(&payer.to_base58_string());
+        let signer2 = Keypair::from_base58_string(&new_authority.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer1, &signer2]))
+            .await?;
+
+        Ok(new_voter_cookie)
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_record(
+        &self,
+        registrar: &RegistrarCookie,
+        voter: &VoterCookie,
+    ) -> std::result::Result<voter_stake_registry::state::VoterWeightRecord, BanksClientError> {
+        let instructions = vec![self.update_voter_weight_record_instruction(registrar, voter)];
+
+        self.solana.process_transaction(&instructions, None).await?;
+
+        Ok(self
+            .solana
+            .get_account::<voter_stake_registry::state::VoterWeightRecord>(
+                voter.voter_weight_record,
+            )
+            .await)
+    }
 
-        let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Clawback {
-                deposit_entry_index,
-            });
+    #[allow(dead_code)]
+    pub async fn update_voter_weight_records(
+        &self,
+        registrar: &RegistrarCookie,
+        voters: &[&VoterCookie],
+    ) -> std::result::Result<Vec<voter_stake_registry::state::VoterWeightRecord>, BanksClientError>
+    {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::UpdateVoterWeightRecords {},
+        );
 
-        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::Clawback {
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::UpdateVoterWeightRecords {
                 registrar: registrar.address,
-                voter: voter.address,
-                vault,
-                destination: token_address,
-                realm_authority: realm_authority.pubkey(),
-                token_program: spl_token::id(),
             },
             None,
         );
+        for voter in voters {
+            accounts.push(anchor_lang::prelude::AccountMeta::new_readonly(
+                voter.address,
+                false,
+            ));
+            accounts.push(anchor_lang::prelude::AccountMeta::new(
+                voter.voter_weight_record,
+                false,
+            ));
+        }
 
         let instructions = vec![Instruction {
             program_id: self.program_id,
@@ -443,43 +1306,42 @@ impl AddinCookie {
             data,
         }];
 
-        // clone the secrets
-        let signer = Keypair::from_base58_string(&realm_authority.to_base58_string());
+        self.solana.process_transaction(&instructions, None).await?;
 
-        self.solana
-            .process_transaction(&instructions, Some(&[&signer]))
-            .await
+        let mut records = vec![];
+        for voter in voters {
+            records.push(
+                self.solana
+                    .get_account::<voter_stake_registry::state::VoterWeightRecord>(
+                        voter.voter_weight_record,
+                    )
+                    .await,
+            );
+        }
+        Ok(records)
     }
 
     #[allow(dead_code)]
-    pub async fn withdraw(
+    pub async fn set_delegate(
         &self,
         registrar: &RegistrarCookie,
         voter: &VoterCookie,
-        voting_mint: &VotingMintConfigCookie,
         authority: &Keypair,
-        token_address: Pubkey,
-        deposit_entry_index: u8,
-        amount: u64,
+        token_owner_record: Pubkey,
+        delegate: Option<Pubkey>,
     ) -> std::result::Result<(), BanksClientError> {
-        let vault = voter.vault_address(&voting_mint);
-
         let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::Withdraw {
-                deposit_entry_index,
-                amount,
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::SetDelegate {
+                delegate,
             });
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::Withdraw {
+            &voter_stake_registry::accounts::SetDelegate {
                 registrar: registrar.address,
                 voter: voter.address,
-                token_owner_record: voter.token_owner_record,
-                voter_weight_record: voter.voter_weight_record,
-                vault,
-                destination: token_address,
                 voter_authority: authority.pubkey(),
-                token_program: spl_token::id(),
+                token_owner_record,
+                voter_weight_record: voter.voter_weight_record,
             },
             None,
         );
@@ -499,29 +1361,25 @@ impl AddinCookie {
     }
 
     #[allow(dead_code)]
-    pub async fn close_voter(
+    pub async fn close_deposit_entry(
         &self,
-        registrar: &RegistrarCookie,
         voter: &VoterCookie,
-        voting_mint: &VotingMintConfigCookie,
-        voter_authority: &Keypair,
-    ) -> std::result::Result<(), BanksClientError> {
-        let vault = voter.vault_address(&voting_mint);
-
-        let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::CloseVoter {});
+        authority: &Keypair,
+        deposit_entry_index: u8,
+    ) -> Result<(), BanksClientError> {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::CloseDepositEntry {
+                deposit_entry_index,
+            },
+        );
 
-        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::CloseVoter {
-                registrar: registrar.address,
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::CloseDepositEntry {
                 voter: voter.address,
-                voter_authority: voter_authority.pubkey(),
-                sol_destination: voter_authority.pubkey(),
-                token_program: spl_token::id(),
+                voter_authority: authority.pubkey(),
             },
             None,
         );
-        accounts.push(anchor_lang::prelude::AccountMeta::new(vault, false));
 
         let instructions = vec![Instruction {
             program_id: self.program_id,
@@ -530,72 +1388,74 @@ impl AddinCookie {
         }];
 
         // clone the secrets
-        let signer = Keypair::from_base58_string(&voter_authority.to_base58_string());
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
 
         self.solana
             .process_transaction(&instructions, Some(&[&signer]))
             .await
     }
 
-    pub fn update_voter_weight_record_instruction(
+    #[allow(dead_code)]
+    pub async fn reset_lockup(
         &self,
         registrar: &RegistrarCookie,
         voter: &VoterCookie,
-    ) -> Instruction {
-        let data = anchor_lang::InstructionData::data(
-            &voter_stake_registry::instruction::UpdateVoterWeightRecord {},
-        );
+        authority: &Keypair,
+        deposit_entry_index: u8,
+        kind: voter_stake_registry::state::LockupKind,
+        periods: u32,
+    ) -> Result<(), BanksClientError> {
+        let data =
+            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::ResetLockup {
+                deposit_entry_index,
+                kind,
+                periods,
+            });
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::UpdateVoterWeightRecord {
+            &voter_stake_registry::accounts::ResetLockup {
                 registrar: registrar.address,
                 voter: voter.address,
-                voter_weight_record: voter.voter_weight_record,
-                system_program: solana_sdk::system_program::id(),
+                voter_authority: authority.pubkey(),
             },
             None,
         );
 
-        Instruction {
+        let instructions = vec![Instruction {
             program_id: self.program_id,
             accounts,
             data,
-        }
-    }
-
-    #[allow(dead_code)]
-    pub async fn update_voter_weight_record(
-        &self,
-        registrar: &RegistrarCookie,
-        voter: &VoterCookie,
-    ) -> std::result::Result<voter_stake_registry::state::VoterWeightRecord, BanksClientError> {
-        let instructions = vec![self.update_voter_weight_record_instruction(registrar, voter)];
+        }];
 
-        self.solana.process_transaction(&instructions, None).await?;
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
 
-        Ok(self
-            .solana
-            .get_account::<voter_stake_registry::state::VoterWeightRecord>(
-                voter.voter_weight_record,
-            )
-            .await)
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
     }
 
     #[allow(dead_code)]
-    pub async fn close_deposit_entry(
+    pub async fn internal_transfer_locked(
         &self,
+        registrar: &RegistrarCookie,
         voter: &VoterCookie,
         authority: &Keypair,
-        deposit_entry_index: u8,
+        source_deposit_entry_index: u8,
+        target_deposit_entry_index: u8,
+        amount: u64,
     ) -> Result<(), BanksClientError> {
         let data = anchor_lang::InstructionData::data(
-            &voter_stake_registry::instruction::CloseDepositEntry {
-                deposit_entry_index,
+            &voter_stake_registry::instruction::InternalTransferLocked {
+                source_deposit_entry_index,
+                target_deposit_entry_index,
+                amount,
             },
         );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::CloseDepositEntry {
+            &voter_stake_registry::accounts::InternalTransferLocked {
+                registrar: registrar.address,
                 voter: voter.address,
                 voter_authority: authority.pubkey(),
             },
@@ -617,24 +1477,23 @@ impl AddinCookie {
     }
 
     #[allow(dead_code)]
-    pub async fn reset_lockup(
+    pub async fn split_deposit_entry(
         &self,
         registrar: &RegistrarCookie,
         voter: &VoterCookie,
         authority: &Keypair,
-        deposit_entry_index: u8,
-        kind: voter_stake_registry::state::LockupKind,
-        periods: u32,
+        source_deposit_entry_index: u8,
+        amount: u64,
     ) -> Result<(), BanksClientError> {
-        let data =
-            anchor_lang::InstructionData::data(&voter_stake_registry::instruction::ResetLockup {
-                deposit_entry_index,
-                kind,
-                periods,
-            });
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::SplitDepositEntry {
+                source_deposit_entry_index,
+                amount,
+            },
+        );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::ResetLockup {
+            &voter_stake_registry::accounts::SplitDepositEntry {
                 registrar: registrar.address,
                 voter: voter.address,
                 voter_authority: authority.pubkey(),
@@ -657,25 +1516,21 @@ impl AddinCookie {
     }
 
     #[allow(dead_code)]
-    pub async fn internal_transfer_locked(
+    pub async fn merge_deposit_entries(
         &self,
         registrar: &RegistrarCookie,
         voter: &VoterCookie,
         authority: &Keypair,
-        source_deposit_entry_index: u8,
-        target_deposit_entry_index: u8,
-        amount: u64,
+        deposit_entry_indexes: Vec<u8>,
     ) -> Result<(), BanksClientError> {
         let data = anchor_lang::InstructionData::data(
-            &voter_stake_registry::instruction::InternalTransferLocked {
-                source_deposit_entry_index,
-                target_deposit_entry_index,
-                amount,
+            &voter_stake_registry::instruction::MergeDepositEntries {
+                deposit_entry_indexes,
             },
         );
 
         let accounts = anchor_lang::ToAccountMetas::to_account_metas(
-            &voter_stake_registry::accounts::InternalTransferLocked {
+            &voter_stake_registry::accounts::MergeDepositEntries {
                 registrar: registrar.address,
                 voter: voter.address,
                 voter_authority: authority.pubkey(),
@@ -771,6 +1626,117 @@ impl AddinCookie {
             .unwrap();
     }
 
+    #[allow(dead_code)]
+    pub async fn log_registrar_info(&self, registrar: &RegistrarCookie) {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::LogRegistrarInfo {},
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::LogRegistrarInfo {
+                registrar: registrar.address,
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        self.solana
+            .process_transaction(&instructions, None)
+            .await
+            .unwrap();
+    }
+
+    #[allow(dead_code)]
+    pub async fn create_max_voter_weight_record(
+        &self,
+        registrar: &RegistrarCookie,
+        payer: &Keypair,
+    ) -> Pubkey {
+        let (max_voter_weight_record, _) = Pubkey::find_program_address(
+            &[
+                &registrar.address.to_bytes(),
+                b"max-voter-weight-record".as_ref(),
+            ],
+            &self.program_id,
+        );
+
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::CreateMaxVoterWeightRecord {},
+        );
+
+        let accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::CreateMaxVoterWeightRecord {
+                registrar: registrar.address,
+                max_voter_weight_record,
+                payer: payer.pubkey(),
+                system_program: solana_sdk::system_program::id(),
+            },
+            None,
+        );
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&payer.to_base58_string());
+
+        self.solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+            .unwrap();
+
+        max_voter_weight_record
+    }
+
+    #[allow(dead_code)]
+    pub async fn update_max_vote_weight(
+        &self,
+        registrar: &RegistrarCookie,
+        max_voter_weight_record: Pubkey,
+        mints: &[Pubkey],
+    ) -> std::result::Result<voter_stake_registry::state::MaxVoterWeightRecord, BanksClientError>
+    {
+        let data = anchor_lang::InstructionData::data(
+            &voter_stake_registry::instruction::UpdateMaxVoteWeight {},
+        );
+
+        let mut accounts = anchor_lang::ToAccountMetas::to_account_metas(
+            &voter_stake_registry::accounts::UpdateMaxVoteWeight {
+                registrar: registrar.address,
+                max_vote_weight_record: max_voter_weight_record,
+            },
+            None,
+        );
+        for mint in mints {
+            accounts.push(anchor_lang::prelude::AccountMeta::new_readonly(
+                *mint, false,
+            ));
+        }
+
+        let instructions = vec![Instruction {
+            program_id: self.program_id,
+            accounts,
+            data,
+        }];
+
+        self.solana.process_transaction(&instructions, None).await?;
+
+        Ok(self
+            .solana
+            .get_account::<voter_stake_registry::state::MaxVoterWeightRecord>(
+                max_voter_weight_record,
+            )
+            .await)
+    }
+
     #[allow(dead_code)]
     pub async fn set_time_offset(
         &self,
@@ -810,25 +1776,36 @@ impl AddinCookie {
 impl VotingMintConfigCookie {
     #[allow(dead_code)]
     pub async fn vault_balance(&self, solana: &SolanaCookie, voter: &VoterCookie) -> u64 {
-        let vault = voter.vault_address(&self);
-        solana.get_account::<TokenAccount>(vault).await.amount
+        let vault = voter.vault_address(self);
+        solana.token_account_balance(vault).await
     }
 }
 
 impl VoterCookie {
     #[allow(dead_code)]
     pub async fn deposit_amount(&self, solana: &SolanaCookie, deposit_id: u8) -> u64 {
-        solana
-            .get_account::<voter_stake_registry::state::Voter>(self.address)
+        self.load(solana)
             .await
-            .deposits[deposit_id as usize]
+            .deposit(deposit_id as usize)
+            .unwrap()
             .amount_deposited_native
     }
 
+    /// The voter including the extra deposit entries of resized voters
+    #[allow(dead_code)]
+    pub async fn load(
+        &self,
+        solana: &SolanaCookie,
+    ) -> voter_stake_registry::state::VoterAccountData {
+        let data = solana.get_account_data(self.address).await;
+        voter_stake_registry::state::VoterAccountData::try_from_account_data(&data).unwrap()
+    }
+
     pub fn vault_address(&self, mint: &VotingMintConfigCookie) -> Pubkey {
-        spl_associated_token_account::get_associated_token_address(
+        spl_associated_token_account::get_associated_token_address_with_program_id(
             &self.address,
             &mint.mint.pubkey.unwrap(),
+            &mint.mint.token_program,
         )
     }
 }
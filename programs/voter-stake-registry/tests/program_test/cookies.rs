@@ -2,6 +2,7 @@ use crate::utils::*;
 use solana_program::pubkey::*;
 use solana_sdk::signature::Keypair;
 
+#[derive(Debug)]
 pub struct MintCookie {
     pub index: usize,
     pub decimals: u8,
@@ -10,6 +11,8 @@ pub struct MintCookie {
     pub quote_lot: f64,
     pub pubkey: Option<Pubkey>,
     pub authority: Keypair,
+    /// spl-token or token-2022
+    pub token_program: Pubkey,
 }
 
 impl Clone for MintCookie {
@@ -22,6 +25,7 @@ impl Clone for MintCookie {
             quote_lot: self.quote_lot,
             pubkey: self.pubkey.clone(),
             authority: clone_keypair(&self.authority),
+            token_program: self.token_program,
         }
     }
 }
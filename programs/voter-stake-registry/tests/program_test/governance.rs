@@ -24,6 +24,7 @@ pub struct GovernanceRealmCookie {
 }
 
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 pub struct TokenOwnerRecordCookie {
     pub address: Pubkey,
 }
@@ -105,6 +106,31 @@ impl GovernanceCookie {
 }
 
 impl GovernanceRealmCookie {
+    #[allow(dead_code)]
+    pub async fn set_realm_authority(&self, authority: &Keypair, new_authority: Option<&Pubkey>) {
+        let action = if new_authority.is_some() {
+            spl_governance::state::realm::SetRealmAuthorityAction::SetUnchecked
+        } else {
+            spl_governance::state::realm::SetRealmAuthorityAction::Remove
+        };
+        let instructions = vec![spl_governance::instruction::set_realm_authority(
+            &self.governance.program_id,
+            &self.realm,
+            &authority.pubkey(),
+            new_authority,
+            action,
+        )];
+
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.governance
+            .solana
+            .process_transaction(&instructions, Some(&[&signer]))
+            .await
+            .unwrap();
+    }
+
+    #[allow(dead_code)]
     pub async fn create_token_owner_record(
         &self,
         owner: Pubkey,
@@ -50,6 +50,11 @@ impl AddPacked for ProgramTest {
 pub struct ProgramOutput {
     pub logs: Vec<String>,
     pub data: Vec<String>,
+    /// Every log message of the transaction, like the logMessages of an rpc
+    /// getTransaction response
+    pub messages: Vec<String>,
+    pub signature: String,
+    pub message: solana_sdk::message::Message,
 }
 struct LoggerWrapper {
     inner: env_logger::Logger,
@@ -67,6 +72,7 @@ impl Log for LoggerWrapper {
             .starts_with("solana_runtime::message_processor")
         {
             let msg = record.args().to_string();
+            self.output.write().unwrap().messages.push(msg.clone());
             if let Some(data) = msg.strip_prefix("Program log: ") {
                 self.output.write().unwrap().logs.push(data.into());
             } else if let Some(data) = msg.strip_prefix("Program data: ") {
@@ -123,6 +129,13 @@ impl TestContext {
             processor!(spl_governance::processor::process_instruction),
         );
 
+        // not among the programs solana-program-test provides
+        test.add_program(
+            "spl_token_2022",
+            spl_token_2022::id(),
+            processor!(spl_token_2022::processor::Processor::process),
+        );
+
         // Setup the environment
 
         // Mints
@@ -135,6 +148,7 @@ impl TestContext {
                 quote_lot: 10 as f64,
                 pubkey: None, //Some(mngo_token::ID),
                 authority: Keypair::new(),
+                token_program: spl_token::id(),
             }, // symbol: "MNGO".to_string()
             MintCookie {
                 index: 1,
@@ -144,6 +158,7 @@ impl TestContext {
                 quote_lot: 0 as f64,
                 pubkey: None,
                 authority: Keypair::new(),
+                token_program: spl_token::id(),
             }, // symbol: "USDC".to_string()
         ];
         // Add mints in loop
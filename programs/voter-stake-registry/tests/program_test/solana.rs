@@ -2,7 +2,6 @@ use std::cell::RefCell;
 use std::sync::{Arc, RwLock};
 
 use anchor_lang::AccountDeserialize;
-use anchor_spl::token::TokenAccount;
 use solana_program::{program_pack::Pack, rent::*, system_instruction};
 use solana_program_test::*;
 use solana_sdk::{
@@ -13,6 +12,7 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use spl_token::*;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
 
 pub struct SolanaCookie {
     pub context: RefCell<ProgramTestContext>,
@@ -44,6 +44,11 @@ impl SolanaCookie {
         // let recent_blockhash = self.context.banks_client.get_recent_blockhash().await.unwrap();
 
         transaction.sign(&all_signers, context.last_blockhash);
+        {
+            let mut output = self.program_output.write().unwrap();
+            output.signature = transaction.signatures[0].to_string();
+            output.message = transaction.message.clone();
+        }
 
         context
             .banks_client
@@ -72,21 +77,33 @@ impl SolanaCookie {
             .unwrap();
     }
 
+    /// Creates a token account of the mint's token program, with space for the
+    /// account extensions the mint requires.
     #[allow(dead_code)]
     pub async fn create_token_account(&self, owner: &Pubkey, mint: Pubkey) -> Pubkey {
         let keypair = Keypair::new();
-        let rent = self.rent.minimum_balance(spl_token::state::Account::LEN);
+        let token_program = self.get_account_owner(mint).await;
+        let mint_data = self.get_account_data(mint).await;
+        let mint_extensions =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)
+                .unwrap()
+                .get_extension_types()
+                .unwrap();
+        let space = ExtensionType::get_account_len::<spl_token_2022::state::Account>(
+            &ExtensionType::get_required_init_account_extensions(&mint_extensions),
+        );
+        let rent = self.rent.minimum_balance(space);
 
         let instructions = [
             system_instruction::create_account(
                 &self.context.borrow().payer.pubkey(),
                 &keypair.pubkey(),
                 rent,
-                spl_token::state::Account::LEN as u64,
-                &spl_token::id(),
+                space as u64,
+                &token_program,
             ),
-            spl_token::instruction::initialize_account(
-                &spl_token::id(),
+            spl_token_2022::instruction::initialize_account3(
+                &token_program,
                 &keypair.pubkey(),
                 &mint,
                 owner,
@@ -100,11 +117,126 @@ impl SolanaCookie {
         return keypair.pubkey();
     }
 
+    #[allow(dead_code)]
+    pub async fn create_mint(&self, authority: &Pubkey, decimals: u8) -> Pubkey {
+        let keypair = Keypair::new();
+        let rent = self.rent.minimum_balance(spl_token::state::Mint::LEN);
+
+        let instructions = [
+            system_instruction::create_account(
+                &self.context.borrow().payer.pubkey(),
+                &keypair.pubkey(),
+                rent,
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &keypair.pubkey(),
+                authority,
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+
+        self.process_transaction(&instructions, Some(&[&keypair]))
+            .await
+            .unwrap();
+        keypair.pubkey()
+    }
+
+    /// Creates a token-2022 mint that charges `transfer_fee_basis_points` on
+    /// transfers, up to `maximum_fee` native tokens.
+    #[allow(dead_code)]
+    pub async fn create_mint_with_transfer_fee(
+        &self,
+        authority: &Pubkey,
+        decimals: u8,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Pubkey {
+        let keypair = Keypair::new();
+        let space = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ]);
+        let rent = self.rent.minimum_balance(space);
+
+        let instructions = [
+            system_instruction::create_account(
+                &self.context.borrow().payer.pubkey(),
+                &keypair.pubkey(),
+                rent,
+                space as u64,
+                &spl_token_2022::id(),
+            ),
+            spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &keypair.pubkey(),
+                Some(authority),
+                Some(authority),
+                transfer_fee_basis_points,
+                maximum_fee,
+            )
+            .unwrap(),
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                &keypair.pubkey(),
+                authority,
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+
+        self.process_transaction(&instructions, Some(&[&keypair]))
+            .await
+            .unwrap();
+        keypair.pubkey()
+    }
+
+    #[allow(dead_code)]
+    pub async fn mint_to(
+        &self,
+        mint: Pubkey,
+        authority: &Keypair,
+        destination: Pubkey,
+        amount: u64,
+    ) {
+        let token_program = self.get_account_owner(mint).await;
+        let instructions = [spl_token_2022::instruction::mint_to(
+            &token_program,
+            &mint,
+            &destination,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()];
+
+        // clone the secrets
+        let signer = Keypair::from_base58_string(&authority.to_base58_string());
+
+        self.process_transaction(&instructions, Some(&[&signer]))
+            .await
+            .unwrap();
+    }
+
+    #[allow(dead_code)]
+    pub async fn get_account_owner(&self, address: Pubkey) -> Pubkey {
+        let mut banks_client = self.context.borrow().banks_client.clone();
+        banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap()
+            .owner
+    }
+
     #[allow(dead_code)]
     pub async fn get_account_data(&self, address: Pubkey) -> Vec<u8> {
-        self.context
-            .borrow_mut()
-            .banks_client
+        let mut banks_client = self.context.borrow().banks_client.clone();
+        banks_client
             .get_account(address)
             .await
             .unwrap()
@@ -113,6 +245,19 @@ impl SolanaCookie {
             .to_vec()
     }
 
+    /// Replaces the data of an account, keeping its owner. Useful for setting up
+    /// accounts with older layouts.
+    #[allow(dead_code)]
+    pub async fn set_account_data(&self, address: Pubkey, data: &[u8]) {
+        let mut banks_client = self.context.borrow().banks_client.clone();
+        let mut account = banks_client.get_account(address).await.unwrap().unwrap();
+        account.data = data.to_vec();
+        account.lamports = self.rent.minimum_balance(data.len());
+        self.context
+            .borrow_mut()
+            .set_account(&address, &account.into());
+    }
+
     #[allow(dead_code)]
     pub async fn get_account<T: AccountDeserialize>(&self, address: Pubkey) -> T {
         let data = self.get_account_data(address).await;
@@ -122,7 +267,11 @@ impl SolanaCookie {
 
     #[allow(dead_code)]
     pub async fn token_account_balance(&self, address: Pubkey) -> u64 {
-        self.get_account::<TokenAccount>(address).await.amount
+        let data = self.get_account_data(address).await;
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+            .unwrap()
+            .base
+            .amount
     }
 
     #[allow(dead_code)]
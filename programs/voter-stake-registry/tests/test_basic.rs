@@ -175,7 +175,7 @@ async fn test_basic() -> Result<(), TransportError> {
         .await?;
     context
         .addin
-        .close_voter(&registrar, &voter, &mngo_voting_mint, &voter_authority)
+        .close_voter(&registrar, &voter, &[&mngo_voting_mint], &voter_authority)
         .await?;
     let lamports_after = context
         .solana
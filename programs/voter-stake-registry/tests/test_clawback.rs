@@ -1,4 +1,3 @@
-use anchor_spl::token::TokenAccount;
 use solana_program_test::*;
 use solana_sdk::{signer::Signer, transport::TransportError};
 
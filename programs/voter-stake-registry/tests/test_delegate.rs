@@ -0,0 +1,608 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+async fn approve_weight(context: &TestContext, proposal: &ProposalCookie) -> u64 {
+    let proposal_data = context.solana.get_account_data(proposal.address).await;
+    let mut data_slice: &[u8] = &proposal_data;
+    let proposal_state: spl_governance::state::proposal::ProposalV2 =
+        anchor_lang::AnchorDeserialize::deserialize(&mut data_slice).unwrap();
+    proposal_state.options[0].vote_weight
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_delegate() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let delegate = &context.users[2].key;
+    let voter2_authority = &context.users[3].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter2_mngo = context.users[3].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+    let delegate_token_owner_record = realm
+        .create_token_owner_record(delegate.pubkey(), &payer)
+        .await;
+    let token_owner_record2 = realm
+        .create_token_owner_record(voter2_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    let voter2 = addin
+        .create_voter(&registrar, &token_owner_record2, &voter2_authority, &payer)
+        .await;
+
+    // the voter as seen by the delegate, using the delegate's token owner record
+    let delegated_voter = VoterCookie {
+        address: voter.address,
+        authority: delegate.pubkey(),
+        voter_weight_record: voter.voter_weight_record,
+        token_owner_record: delegate_token_owner_record.address,
+    };
+
+    let mint_governance = realm
+        .create_mint_governance(
+            context.mints[0].pubkey.unwrap(),
+            &context.mints[0].authority,
+            &voter2,
+            &voter2_authority,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter2),
+        )
+        .await;
+
+    for (voter, authority, token_account) in [
+        (&voter, voter_authority, voter_mngo),
+        (&voter2, voter2_authority, voter2_mngo),
+    ] {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                voter,
+                authority,
+                &mngo_voting_mint,
+                0,
+                LockupKind::None,
+                None,
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                voter,
+                &mngo_voting_mint,
+                authority,
+                token_account,
+                0,
+                1000,
+            )
+            .await
+            .unwrap();
+    }
+
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            token_owner_record.address,
+            Some(voter_authority.pubkey()),
+        )
+        .await
+        .expect_err("can't delegate to the voter authority");
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            delegate_token_owner_record.address,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .expect_err("must pass the voter authority's token owner record");
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            token_owner_record.address,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.governing_token_owner, delegate.pubkey());
+    assert_eq!(vwr.voter_weight, 2 * 1000);
+
+    let proposal = realm
+        .create_proposal(
+            mint_governance.address,
+            &voter2_authority,
+            &voter2,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter2),
+        )
+        .await
+        .unwrap();
+
+    // the voter authority can no longer vote with the voter's weight
+    realm
+        .cast_vote(
+            mint_governance.address,
+            &proposal,
+            &voter,
+            &voter_authority,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter),
+        )
+        .await
+        .expect_err("voter weight record belongs to the delegate");
+
+    realm
+        .cast_vote(
+            mint_governance.address,
+            &proposal,
+            &delegated_voter,
+            &delegate,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter),
+        )
+        .await
+        .unwrap();
+    assert_eq!(approve_weight(&context, &proposal).await, 2 * 1000);
+
+    // the delegate's vote locks the deposit and the delegation
+    addin
+        .withdraw(
+            &registrar,
+            &delegated_voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_mngo,
+            0,
+            1,
+        )
+        .await
+        .expect_err("could not withdraw");
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            delegate_token_owner_record.address,
+            Some(voter2_authority.pubkey()),
+        )
+        .await
+        .expect_err("could not change the delegate");
+
+    realm
+        .relinquish_vote(
+            mint_governance.address,
+            &proposal,
+            delegate_token_owner_record.address,
+            &delegate,
+            payer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // the voter authority takes the weight back
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            delegate_token_owner_record.address,
+            None,
+        )
+        .await
+        .unwrap();
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.governing_token_owner, voter_authority.pubkey());
+    assert_eq!(vwr.voter_weight, 2 * 1000);
+
+    Ok(())
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_uncooperative_delegate() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let delegate = &context.users[2].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+    let delegate_token_owner_record = realm
+        .create_token_owner_record(delegate.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    let delegated_voter = VoterCookie {
+        address: voter.address,
+        authority: delegate.pubkey(),
+        voter_weight_record: voter.voter_weight_record,
+        token_owner_record: delegate_token_owner_record.address,
+    };
+
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    let mint_governance = realm
+        .create_mint_governance(
+            context.mints[0].pubkey.unwrap(),
+            &context.mints[0].authority,
+            &voter,
+            &voter_authority,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter),
+        )
+        .await;
+
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            token_owner_record.address,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    // the delegate opens a proposal, votes and never relinquishes
+    let proposal = realm
+        .create_proposal(
+            mint_governance.address,
+            &delegate,
+            &delegated_voter,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter),
+        )
+        .await
+        .unwrap();
+    realm
+        .cast_vote(
+            mint_governance.address,
+            &proposal,
+            &delegated_voter,
+            &delegate,
+            payer,
+            addin.update_voter_weight_record_instruction(&registrar, &voter),
+        )
+        .await
+        .unwrap();
+    assert_eq!(approve_weight(&context, &proposal).await, 2 * 1000);
+
+    // the voter authority can still revoke the delegate, but the revocation
+    // stays pending while the delegate's vote is unrelinquished
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            delegate_token_owner_record.address,
+            None,
+        )
+        .await
+        .unwrap();
+    let voter_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Voter>(voter.address)
+        .await;
+    assert_eq!(voter_data.delegate, delegate.pubkey());
+    assert!(voter_data.delegate_revoked);
+
+    // nobody can vote with the voter weight record meanwhile
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.governing_token_owner, Pubkey::default());
+
+    addin
+        .withdraw(
+            &registrar,
+            &delegated_voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .expect_err("the delegate's vote is unrelinquished");
+
+    // voting has ended, so anyone can relinquish the delegate's vote
+    realm
+        .relinquish_vote(
+            mint_governance.address,
+            &proposal,
+            delegate_token_owner_record.address,
+            &payer,
+            payer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // the delegate's open proposal doesn't keep the tokens locked
+    addin
+        .withdraw(
+            &registrar,
+            &delegated_voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_mngo,
+            0,
+            500,
+        )
+        .await
+        .unwrap();
+
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            delegate_token_owner_record.address,
+            None,
+        )
+        .await
+        .unwrap();
+    let voter_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Voter>(voter.address)
+        .await;
+    assert_eq!(voter_data.delegate, Pubkey::default());
+    assert!(!voter_data.delegate_revoked);
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.governing_token_owner, voter_authority.pubkey());
+    assert_eq!(vwr.voter_weight, 2 * 500);
+
+    Ok(())
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_withdraw_with_delegate_without_record() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let delegate = &context.users[2].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    // the delegate never created a token owner record
+    addin
+        .set_delegate(
+            &registrar,
+            &voter,
+            voter_authority,
+            token_owner_record.address,
+            Some(delegate.pubkey()),
+        )
+        .await
+        .unwrap();
+    let delegated_voter = VoterCookie {
+        address: voter.address,
+        authority: delegate.pubkey(),
+        voter_weight_record: voter.voter_weight_record,
+        token_owner_record:
+            spl_governance::state::token_owner_record::get_token_owner_record_address(
+                &context.governance.program_id,
+                &realm.realm,
+                &context.mints[0].pubkey.unwrap(),
+                &delegate.pubkey(),
+            ),
+    };
+
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            400,
+        )
+        .await
+        .expect_err("must pass the delegate's token owner record");
+    addin
+        .withdraw(
+            &registrar,
+            &delegated_voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            400,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 600);
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+fn deserialize_event<T: anchor_lang::Event>(event: &str) -> Option<T> {
+    let data = base64::decode(event).ok()?;
+    if data.len() < 8 || data[0..8] != T::discriminator() {
+        return None;
+    }
+    T::try_from_slice(&data[8..]).ok()
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_deposit_cliff_monthly_vesting() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let month = LockupKind::CliffMonthly.period_secs();
+    addin
+        .grant_with_cliff(
+            &registrar,
+            voter_authority.pubkey(),
+            &mngo_voting_mint,
+            LockupKind::Monthly,
+            None,
+            12,
+            36,
+            true,
+            48000,
+            voter_mngo,
+            &voter_authority,
+            &realm_authority,
+        )
+        .await
+        .expect_err("fails because only CliffMonthly lockups have a cliff");
+
+    // 1 year cliff, then 3 years of monthly vesting
+    let voter = addin
+        .grant_with_cliff(
+            &registrar,
+            voter_authority.pubkey(),
+            &mngo_voting_mint,
+            LockupKind::CliffMonthly,
+            None,
+            12,
+            36,
+            true,
+            48000,
+            voter_mngo,
+            &voter_authority,
+            &realm_authority,
+        )
+        .await
+        .unwrap();
+
+    addin
+        .set_time_offset(&registrar, &realm_authority, month as i64)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+
+    addin.log_voter_info(&registrar, &voter, 0).await;
+    let data_log = context.solana.program_output().data;
+    assert_eq!(data_log.len(), 2);
+
+    // the cliff's 12000 tokens unlock in 11 months, the rest is saturated
+    let voter_event =
+        deserialize_event::<voter_stake_registry::events::VoterInfo>(&data_log[0]).unwrap();
+    assert_eq!(voter_event.voting_power, 48000 + 11000 + 36000);
+
+    let deposit_event =
+        deserialize_event::<voter_stake_registry::events::DepositEntryInfo>(&data_log[1]).unwrap();
+    assert_eq!(deposit_event.unlocked, 0);
+    let locking = deposit_event.locking.unwrap();
+    let vesting = locking.vesting.unwrap();
+    assert_eq!(locking.amount, 48000);
+    assert_eq!(vesting.rate, 1000);
+    assert_eq!(vesting.cliff_end_timestamp, Some(vesting.next_timestamp));
+    assert_eq!(
+        locking.end_timestamp.unwrap(),
+        vesting.next_timestamp + 36 * month
+    );
+
+    // at the cliff, a quarter vests at once and monthly vesting starts
+    addin
+        .set_time_offset(&registrar, &realm_authority, 12 * month as i64)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+
+    addin.log_voter_info(&registrar, &voter, 0).await;
+    let data_log = context.solana.program_output().data;
+    let deposit_event =
+        deserialize_event::<voter_stake_registry::events::DepositEntryInfo>(&data_log[1]).unwrap();
+    assert_eq!(deposit_event.unlocked, 12000);
+    let locking = deposit_event.locking.unwrap();
+    let vesting = locking.vesting.unwrap();
+    assert_eq!(locking.amount, 36000);
+    assert_eq!(vesting.rate, 1000);
+    assert_eq!(vesting.cliff_end_timestamp, None);
+    assert_eq!(
+        locking.end_timestamp.unwrap(),
+        vesting.next_timestamp + 35 * month
+    );
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_deposit_limits() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter2_authority = &context.users[2].key;
+    let voter2_mngo = context.users[2].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    addin
+        .configure_deposit_limits(&registrar, &context.users[2].key, 0, 100, 1000, 1500)
+        .await
+        .expect_err("fails because the realm authority must sign");
+    addin
+        .configure_deposit_limits(&registrar, &realm_authority, 1, 100, 1000, 1500)
+        .await
+        .expect_err("fails because the index is unused");
+    // at least 100 per deposit, 1000 per voter and 1500 in total
+    addin
+        .configure_deposit_limits(&registrar, &realm_authority, 0, 100, 1000, 1500)
+        .await
+        .unwrap();
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    let deposit = |amount: u64| {
+        addin.deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            amount,
+        )
+    };
+
+    deposit(99).await.expect_err("below the minimum");
+    deposit(100).await.unwrap();
+    deposit(901).await.expect_err("above the per-voter cap");
+    deposit(900).await.unwrap();
+
+    let grant = |amount: u64| {
+        addin.grant(
+            &registrar,
+            voter2_authority.pubkey(),
+            &mngo_voting_mint,
+            LockupKind::Daily,
+            None,
+            10,
+            false,
+            amount,
+            voter2_mngo,
+            voter2_authority,
+            voter2_authority,
+        )
+    };
+    grant(99).await.expect_err("below the minimum");
+    grant(501).await.expect_err("above the registrar-wide cap");
+    let voter2 = grant(500).await.unwrap();
+
+    // withdrawing frees up room under the registrar-wide cap
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            200,
+        )
+        .await
+        .unwrap();
+    let deposit2 = |amount: u64| {
+        addin.deposit(
+            &registrar,
+            &voter2,
+            &mngo_voting_mint,
+            voter2_authority,
+            voter2_mngo,
+            0,
+            amount,
+        )
+    };
+    deposit2(201)
+        .await
+        .expect_err("above the registrar-wide cap");
+    deposit2(200).await.unwrap();
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &voter)
+            .await,
+        800
+    );
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &voter2)
+            .await,
+        700
+    );
+
+    // reconfiguring keeps the limits
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    deposit(1).await.expect_err("below the minimum");
+
+    addin
+        .configure_deposit_limits(&registrar, &realm_authority, 0, 0, 0, 0)
+        .await
+        .unwrap();
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    deposit(1).await.unwrap();
+
+    Ok(())
+}
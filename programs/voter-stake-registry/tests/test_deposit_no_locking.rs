@@ -1,4 +1,3 @@
-use anchor_spl::token::TokenAccount;
 use solana_program_test::*;
 use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transport::TransportError};
 
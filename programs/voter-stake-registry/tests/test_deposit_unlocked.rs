@@ -0,0 +1,198 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_deposit_unlocked() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    // someone unrelated to the voter, like an airdrop wallet
+    let depositor = &context.users[2].key;
+    let depositor_mngo = context.users[2].token_accounts[0];
+    let depositor_usdc = context.users[2].token_accounts[1];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    let usdc_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::Cliff,
+            None,
+            1,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    // the locked entry isn't reused, a new unlocked entry is created
+    addin
+        .deposit_unlocked(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            depositor,
+            payer,
+            depositor_mngo,
+            100,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 1000);
+    assert_eq!(voter.deposit_amount(&context.solana, 1).await, 100);
+
+    // the unlocked entry is reused
+    addin
+        .deposit_unlocked(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            depositor,
+            payer,
+            depositor_mngo,
+            50,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 1).await, 150);
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &voter)
+            .await,
+        1150
+    );
+    let voter_data = voter.load(&context.solana).await;
+    assert!(!voter_data.deposit(2).unwrap().is_used);
+
+    // the vault for another mint gets created on the fly
+    addin
+        .deposit_unlocked(
+            &registrar,
+            &voter,
+            &usdc_voting_mint,
+            depositor,
+            payer,
+            depositor_usdc,
+            70,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 2).await, 70);
+    assert_eq!(
+        usdc_voting_mint
+            .vault_balance(&context.solana, &voter)
+            .await,
+        70
+    );
+
+    // the deposit authority must own the source tokens
+    addin
+        .deposit_unlocked(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            depositor,
+            payer,
+            voter_mngo,
+            1,
+        )
+        .await
+        .expect_err("depositor doesn't own voter_mngo");
+
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000 + 150 + 70);
+
+    // the voter authority is free to withdraw
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            150,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 1).await, 0);
+
+    Ok(())
+}
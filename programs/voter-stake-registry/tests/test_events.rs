@@ -0,0 +1,135 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::events::*;
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+fn deserialize_event<T: anchor_lang::Event>(event: &str) -> Option<T> {
+    let data = base64::decode(event).ok()?;
+    if data.len() < 8 || data[0..8] != T::discriminator() {
+        return None;
+    }
+    T::try_from_slice(&data[8..]).ok()
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_events() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    let data_log = context.solana.program_output().data;
+    let created = deserialize_event::<VoterCreatedEvent>(&data_log[0]).unwrap();
+    assert_eq!(created.registrar, registrar.address);
+    assert_eq!(created.voter, voter.address);
+    assert_eq!(created.voter_authority, voter_authority.pubkey());
+
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            &voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    let data_log = context.solana.program_output().data;
+    let created = deserialize_event::<DepositEntryCreatedEvent>(&data_log[0]).unwrap();
+    assert_eq!(created.deposit_entry_index, 0);
+    assert_eq!(created.deposit_entry.lockup.kind, LockupKind::None);
+    assert_eq!(created.deposit_entry.amount_deposited_native, 0);
+
+    let reference_account = context.users[1].token_accounts[0];
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            reference_account,
+            0,
+            10000,
+        )
+        .await
+        .unwrap();
+    let data_log = context.solana.program_output().data;
+    assert_eq!(data_log.len(), 1);
+    let deposit = deserialize_event::<DepositEvent>(&data_log[0]).unwrap();
+    assert_eq!(deposit.registrar, registrar.address);
+    assert_eq!(deposit.voter, voter.address);
+    assert_eq!(deposit.deposit_entry_index, 0);
+    assert_eq!(deposit.amount, 10000);
+    assert_eq!(deposit.before.amount_deposited_native, 0);
+    assert_eq!(deposit.after.amount_deposited_native, 10000);
+    assert_eq!(deposit.voter_weight, 10000);
+
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            reference_account,
+            0,
+            4000,
+        )
+        .await
+        .unwrap();
+    let data_log = context.solana.program_output().data;
+    assert_eq!(data_log.len(), 1);
+    let withdraw = deserialize_event::<WithdrawEvent>(&data_log[0]).unwrap();
+    assert_eq!(withdraw.destination, reference_account);
+    assert_eq!(withdraw.amount, 4000);
+    assert_eq!(withdraw.before.amount_deposited_native, 10000);
+    assert_eq!(withdraw.after.amount_deposited_native, 6000);
+    assert_eq!(withdraw.voter_weight, 6000);
+
+    Ok(())
+}
@@ -0,0 +1,294 @@
+use program_test::*;
+use serde_json::json;
+use solana_program_test::*;
+use solana_sdk::{bs58, signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+/// Collects transactions like the rpc getTransaction responses the indexer
+/// reads, in the "json" encoding
+struct TransactionRecorder {
+    lines: Vec<String>,
+    time_offset: i64,
+    block_time: i64,
+}
+
+impl TransactionRecorder {
+    /// Records the transaction that was processed last
+    async fn record(
+        &mut self,
+        solana: &SolanaCookie,
+        result: std::result::Result<(), BanksClientError>,
+    ) {
+        let clock = solana.get_clock().await;
+        let output = solana.program_output();
+        let err = match result {
+            Ok(()) => serde_json::Value::Null,
+            Err(BanksClientError::TransactionError(err)) => serde_json::to_value(err).unwrap(),
+            Err(err) => panic!("unexpected error: {:?}", err),
+        };
+        let message = &output.message;
+        let instructions: Vec<_> = message
+            .instructions
+            .iter()
+            .map(|ix| {
+                json!({
+                    "programIdIndex": ix.program_id_index,
+                    "accounts": ix.accounts,
+                    "data": bs58::encode(&ix.data).into_string(),
+                })
+            })
+            .collect();
+        self.block_time = clock.unix_timestamp + self.time_offset;
+        self.lines.push(
+            json!({
+                "slot": clock.slot,
+                "blockTime": self.block_time,
+                "transaction": {
+                    "signatures": [output.signature],
+                    "message": {
+                        "accountKeys": message.account_keys.iter().map(|k| k.to_string()).collect::<Vec<_>>(),
+                        "instructions": instructions,
+                    },
+                },
+                "meta": { "err": err, "logMessages": output.messages },
+            })
+            .to_string(),
+        );
+    }
+
+    /// Moves to a new slot and shifts the registrar's time, so the next
+    /// transaction executes at `block_time`
+    async fn set_block_time(
+        &mut self,
+        context: &TestContext,
+        registrar: &RegistrarCookie,
+        realm_authority: &Keypair,
+        block_time: i64,
+    ) {
+        context.solana.advance_clock_by_slots(2).await;
+        let clock = context.solana.get_clock().await;
+        self.time_offset = block_time - clock.unix_timestamp;
+        context
+            .addin
+            .set_time_offset(registrar, realm_authority, self.time_offset)
+            .await;
+    }
+}
+
+/// Records the transactions of indexer/tests/fixtures/transactions.jsonl.
+///
+/// To regenerate the fixture, run
+///   VSR_INDEXER_FIXTURE=$PWD/indexer/tests/fixtures/transactions.jsonl \
+///     cargo test -p voter-stake-registry --test test_indexer_fixture
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_indexer_fixture() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+    let solana = &context.solana;
+    let mut recorder = TransactionRecorder {
+        lines: vec![],
+        time_offset: 0,
+        block_time: 0,
+    };
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    recorder.record(solana, Ok(())).await;
+
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    recorder.record(solana, Ok(())).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    recorder.record(solana, Ok(())).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::Cliff,
+            None,
+            365,
+            false,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            1,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            500,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            200,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    // a failed transaction, which the indexer skips
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            5,
+            100,
+        )
+        .await;
+    assert!(result.is_err());
+    recorder.record(solana, result).await;
+
+    let next = recorder.block_time + 10;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    addin.log_voter_info(&registrar, &voter, 0).await;
+    recorder.record(solana, Ok(())).await;
+
+    let next = recorder.block_time + 30;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .reset_lockup(
+            &registrar,
+            &voter,
+            voter_authority,
+            0,
+            LockupKind::Cliff,
+            2 * 365,
+        )
+        .await;
+    recorder.record(solana, result).await;
+
+    // remove the voting mint 1000 seconds after the lockup ended
+    let next = recorder.block_time + 2 * 365 * 24 * 60 * 60 + 1000;
+    recorder
+        .set_block_time(&context, &registrar, &realm_authority, next)
+        .await;
+    let result = addin
+        .remove_voting_mint(&registrar, &realm_authority, 0)
+        .await;
+    recorder.record(solana, result).await;
+
+    assert_eq!(recorder.lines.len(), 12);
+    if let Ok(path) = std::env::var("VSR_INDEXER_FIXTURE") {
+        std::fs::write(path, recorder.lines.join("\n") + "\n").unwrap();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::{
+    LockupCurve, LockupCurveKind, LockupCurvePoint, LockupKind, Registrar,
+};
+
+mod program_test;
+
+async fn voter_weight(
+    context: &TestContext,
+    registrar: &RegistrarCookie,
+    voter: &VoterCookie,
+) -> u64 {
+    // Advance slots to avoid caching of the UpdateVoterWeightRecord call
+    context.solana.advance_clock_by_slots(2).await;
+    context
+        .addin
+        .update_voter_weight_record(registrar, voter)
+        .await
+        .unwrap()
+        .voter_weight
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_lockup_curve() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let day = 24 * 60 * 60;
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            4 * day as u64,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::Cliff,
+            None,
+            3, // days
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    let voter_weight = || voter_weight(&context, &registrar, &voter);
+
+    // linear: 3 of 4 days
+    assert_eq!(voter_weight().await, 1000 + 750);
+
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &context.users[2].key,
+            0,
+            LockupCurveKind::Step,
+            &[(4 * day, 1.0)],
+        )
+        .await
+        .expect_err("fails because the realm authority must sign");
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            1,
+            LockupCurveKind::Step,
+            &[(4 * day, 1.0)],
+        )
+        .await
+        .expect_err("fails because the index is unused");
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupCurveKind::Step,
+            &[(day, 0.25), (2 * day, 0.5), (4 * day, 0.9)],
+        )
+        .await
+        .expect_err("fails because the last tier must have the full factor");
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupCurveKind::Linear,
+            &[(4 * day, 1.0)],
+        )
+        .await
+        .expect_err("fails because linear curves have no points");
+
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupCurveKind::Step,
+            &[(day, 0.25), (2 * day, 0.5), (4 * day, 1.0)],
+        )
+        .await
+        .unwrap();
+
+    // 3 days left: the 2 day tier
+    assert_eq!(voter_weight().await, 1000 + 500);
+
+    // 1.5 days left: the 1 day tier
+    addin
+        .set_time_offset(&registrar, &realm_authority, 3 * day as i64 / 2)
+        .await;
+    assert_eq!(voter_weight().await, 1000 + 250);
+
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupCurveKind::PiecewiseLinear,
+            &[(day, 0.6), (2 * day, 1.0)],
+        )
+        .await
+        .unwrap();
+
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    let mint_config = registrar_data.voting_mints[0];
+    let lockup_saturation_secs = mint_config.lockup_saturation_secs;
+    assert_eq!(
+        mint_config.lockup_curve(),
+        LockupCurve::new(
+            LockupCurveKind::PiecewiseLinear,
+            2 * day as u64,
+            &[
+                LockupCurvePoint {
+                    lockup_secs: day,
+                    scaled_factor: 600_000_000,
+                },
+                LockupCurvePoint {
+                    lockup_secs: 2 * day,
+                    scaled_factor: 1_000_000_000,
+                },
+            ],
+        )
+        .unwrap()
+    );
+    assert_eq!(lockup_saturation_secs, 2 * day as u64);
+
+    // 1.5 days left: halfway between the points
+    assert_eq!(voter_weight().await, 1000 + 800);
+
+    addin
+        .configure_lockup_curve(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupCurveKind::Linear,
+            &[],
+        )
+        .await
+        .unwrap();
+
+    // linear again, keeping the 2 day saturation
+    assert_eq!(voter_weight().await, 1000 + 750);
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_lockup_limits() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    let create_deposit_entry = |index: u8, kind: LockupKind, periods: u32| {
+        addin.create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            index,
+            kind,
+            None,
+            periods,
+            false,
+        )
+    };
+
+    // entries set up before the restrictions
+    create_deposit_entry(0, LockupKind::Daily, 10)
+        .await
+        .unwrap();
+    create_deposit_entry(1, LockupKind::Constant, 100)
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    // only cliff lockups of up to four years
+    addin
+        .configure_lockup_limits(
+            &registrar,
+            &context.users[2].key,
+            0,
+            LockupKind::Cliff.mask(),
+            4 * 365,
+        )
+        .await
+        .expect_err("fails because the realm authority must sign");
+    addin
+        .configure_lockup_limits(
+            &registrar,
+            &realm_authority,
+            1,
+            LockupKind::Cliff.mask(),
+            4 * 365,
+        )
+        .await
+        .expect_err("fails because the index is unused");
+    addin
+        .configure_lockup_limits(
+            &registrar,
+            &realm_authority,
+            0,
+            LockupKind::Cliff.mask(),
+            4 * 365,
+        )
+        .await
+        .unwrap();
+
+    // reconfiguring the mint keeps the restrictions
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    create_deposit_entry(2, LockupKind::Daily, 10)
+        .await
+        .expect_err("kind not allowed");
+    create_deposit_entry(2, LockupKind::None, 0)
+        .await
+        .expect_err("kind not allowed");
+    create_deposit_entry(2, LockupKind::Cliff, 4 * 365 + 1)
+        .await
+        .expect_err("lockup too long");
+    create_deposit_entry(2, LockupKind::Cliff, 4 * 365)
+        .await
+        .unwrap();
+
+    let grant = |kind: LockupKind, periods: u32| {
+        addin.grant(
+            &registrar,
+            voter_authority.pubkey(),
+            &mngo_voting_mint,
+            kind,
+            None,
+            periods,
+            false,
+            100,
+            voter_mngo,
+            voter_authority,
+            &realm_authority,
+        )
+    };
+    grant(LockupKind::Constant, 10)
+        .await
+        .expect_err("kind not allowed");
+    grant(LockupKind::Cliff, 4 * 365 + 1)
+        .await
+        .expect_err("lockup too long");
+    grant(LockupKind::Cliff, 10).await.unwrap();
+    let granted = *voter.load(&context.solana).await.deposit(3).unwrap();
+    assert_eq!(granted.lockup.kind, LockupKind::Cliff);
+
+    // existing lockups stay, but locked tokens can't move into them
+    addin
+        .internal_transfer_locked(&registrar, &voter, voter_authority, 0, 1, 100)
+        .await
+        .expect_err("target kind not allowed");
+    addin
+        .internal_transfer_locked(&registrar, &voter, voter_authority, 0, 2, 100)
+        .await
+        .unwrap();
+
+    let reset_lockup = |index: u8, kind: LockupKind, periods: u32| {
+        addin.reset_lockup(&registrar, &voter, voter_authority, index, kind, periods)
+    };
+    reset_lockup(1, LockupKind::Constant, 200)
+        .await
+        .expect_err("kind not allowed");
+    reset_lockup(0, LockupKind::Cliff, 4 * 365 + 1)
+        .await
+        .expect_err("lockup too long");
+    reset_lockup(0, LockupKind::Cliff, 4 * 365).await.unwrap();
+
+    Ok(())
+}
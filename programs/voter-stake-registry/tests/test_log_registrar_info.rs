@@ -0,0 +1,180 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signer::Signer, transport::TransportError};
+use voter_stake_registry::events::VotingMintInfo;
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+fn deserialize_event<T: anchor_lang::Event>(event: &str) -> Option<T> {
+    let data = base64::decode(event).ok()?;
+    if data.len() < 8 || data[0..8] != T::discriminator() {
+        return None;
+    }
+    T::try_from_slice(&data[8..]).ok()
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_log_registrar_info() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = &context.users[0].key;
+    let realm_authority_mngo = context.users[0].token_accounts[0];
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter2_authority = &context.users[2].key;
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let log_totals = || async {
+        context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+        addin.log_registrar_info(&registrar).await;
+        let data_log = context.solana.program_output().data;
+        assert_eq!(data_log.len(), 1);
+        let info = deserialize_event::<VotingMintInfo>(&data_log[0]).unwrap();
+        assert_eq!(info.voting_mint_config_index, 0);
+        assert_eq!(info.mint, mngo_voting_mint.mint.pubkey.unwrap());
+        (
+            info.deposited.unwrap(),
+            info.locked.unwrap(),
+            info.clawback_deposited.unwrap(),
+        )
+    };
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    for (index, kind, periods) in [(0, LockupKind::Cliff, 10), (1, LockupKind::None, 0)] {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                &mngo_voting_mint,
+                index,
+                kind,
+                None,
+                periods,
+                false,
+            )
+            .await
+            .unwrap();
+    }
+    let deposit = |index: u8, amount: u64| {
+        addin.deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            index,
+            amount,
+        )
+    };
+    deposit(0, 1000).await.unwrap();
+    deposit(1, 500).await.unwrap();
+
+    let voter2 = addin
+        .grant(
+            &registrar,
+            voter2_authority.pubkey(),
+            &mngo_voting_mint,
+            LockupKind::Daily,
+            None,
+            10,
+            true,
+            300,
+            voter_mngo,
+            voter_authority,
+            &realm_authority,
+        )
+        .await
+        .unwrap();
+    assert_eq!(log_totals().await, (1800, 1300, 300));
+
+    // unlocked tokens moving into a lockup only count as locked once the
+    // lockup covers them
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            200,
+        )
+        .await
+        .unwrap();
+    addin
+        .internal_transfer_unlocked(&registrar, &voter, voter_authority, 1, 0, 100)
+        .await
+        .unwrap();
+    addin
+        .reset_lockup(
+            &registrar,
+            &voter,
+            voter_authority,
+            1,
+            LockupKind::Cliff,
+            20,
+        )
+        .await
+        .unwrap();
+    assert_eq!(log_totals().await, (1600, 1500, 300));
+
+    // after a day, the clawback takes the 270 still locked tokens and ends
+    // the clawback-enabled lockup
+    addin
+        .set_time_offset(&registrar, &realm_authority, 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+    addin
+        .clawback(
+            &registrar,
+            &voter2,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_mngo,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(log_totals().await, (1330, 1200, 0));
+
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use anchor_spl::token::TokenAccount;
 use program_test::*;
 use solana_program_test::*;
 use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
@@ -136,6 +135,7 @@ async fn test_log_voter_info() -> Result<(), TransportError> {
     let vesting = locking.vesting.unwrap();
     assert_eq!(locking.amount, 11000);
     assert_eq!(vesting.rate, 1000);
+    assert_eq!(vesting.cliff_end_timestamp, None);
     assert_eq!(
         locking.end_timestamp.unwrap(),
         vesting.next_timestamp + 10 * (365 * 24 * 60 * 60 / 12)
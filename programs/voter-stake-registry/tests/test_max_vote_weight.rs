@@ -0,0 +1,174 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::{LockupKind, MaxVoteWeightMode};
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_max_vote_weight() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_mint = context.mints[0].pubkey.unwrap();
+    let usdc_mint = context.mints[1].pubkey.unwrap();
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            2.0,
+            365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    let usdc_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            0.5,
+            0.0,
+            365 * 24 * 60 * 60,
+            None,
+            Some(&[mngo_mint]),
+        )
+        .await;
+
+    context
+        .solana
+        .mint_to(
+            mngo_mint,
+            &context.mints[0].authority,
+            context.users[1].token_accounts[0],
+            1000,
+        )
+        .await;
+    context
+        .solana
+        .mint_to(
+            usdc_mint,
+            &context.mints[1].authority,
+            context.users[1].token_accounts[1],
+            2000,
+        )
+        .await;
+
+    let max_voter_weight_record = addin
+        .create_max_voter_weight_record(&registrar, payer)
+        .await;
+
+    // all voting mints must be passed
+    assert!(addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[mngo_mint])
+        .await
+        .is_err());
+
+    let record = addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[mngo_mint, usdc_mint])
+        .await
+        .unwrap();
+    assert_eq!(record.realm, realm.realm);
+    assert_eq!(record.governing_token_mint, mngo_mint);
+    assert_eq!(record.max_voter_weight, 1000 * 3 + 2000 / 2);
+    assert_eq!(
+        record.max_voter_weight_expiry,
+        Some(context.solana.get_clock().await.slot)
+    );
+
+    // deposit 300 locked and 200 unlocked mngo, and 400 usdc
+    let voter_authority = &context.users[1].key;
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    let deposits = [
+        (0, &mngo_voting_mint, LockupKind::Cliff, 10, 300),
+        (1, &mngo_voting_mint, LockupKind::None, 0, 200),
+        (2, &usdc_voting_mint, LockupKind::None, 0, 400),
+    ];
+    for (index, voting_mint, kind, periods, amount) in deposits {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                voting_mint,
+                index,
+                kind,
+                None,
+                periods,
+                false,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                voting_mint,
+                voter_authority,
+                context.users[1].token_accounts[voting_mint.mint.index],
+                index,
+                amount,
+            )
+            .await
+            .unwrap();
+    }
+
+    addin
+        .configure_max_vote_weight_mode(&registrar, payer, MaxVoteWeightMode::Deposited)
+        .await
+        .expect_err("only the realm authority may change the mode");
+    addin
+        .configure_max_vote_weight_mode(&registrar, &realm_authority, MaxVoteWeightMode::Deposited)
+        .await
+        .unwrap();
+
+    // the lockup bonus only applies to the locked mngo, mint accounts aren't needed
+    let record = addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[])
+        .await
+        .unwrap();
+    assert_eq!(record.max_voter_weight, 500 + 300 * 2 + 400 / 2);
+
+    addin
+        .configure_max_vote_weight_mode(&registrar, &realm_authority, MaxVoteWeightMode::MintSupply)
+        .await
+        .unwrap();
+    context.solana.advance_clock_by_slots(2).await;
+    let record = addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[mngo_mint, usdc_mint])
+        .await
+        .unwrap();
+    assert_eq!(record.max_voter_weight, 1000 * 3 + 2000 / 2);
+
+    Ok(())
+}
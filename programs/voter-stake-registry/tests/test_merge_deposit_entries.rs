@@ -0,0 +1,198 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_merge_deposit_entries() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter_usdc = context.users[1].token_accounts[1];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    let usdc_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    // index 4 is for another mint, see below
+    let mngo_entries = [
+        (0, LockupKind::None, 0, false, 100),
+        (1, LockupKind::Daily, 3, false, 300),
+        (2, LockupKind::Cliff, 5, false, 500),
+        (3, LockupKind::Cliff, 5, true, 100),
+        (5, LockupKind::Daily, 10, false, 1000),
+    ];
+    for (index, kind, periods, allow_clawback, amount) in mngo_entries {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                &mngo_voting_mint,
+                index,
+                kind,
+                None,
+                periods,
+                allow_clawback,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                &mngo_voting_mint,
+                voter_authority,
+                voter_mngo,
+                index,
+                amount,
+            )
+            .await
+            .unwrap();
+    }
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &usdc_voting_mint,
+            4,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &usdc_voting_mint,
+            voter_authority,
+            voter_usdc,
+            4,
+            100,
+        )
+        .await
+        .unwrap();
+
+    let merge = |indexes: &[u8]| {
+        addin.merge_deposit_entries(&registrar, &voter, voter_authority, indexes.to_vec())
+    };
+    let withdraw = |index: u8, amount: u64| {
+        addin.withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            index,
+            amount,
+        )
+    };
+
+    merge(&[2]).await.expect_err("nothing to merge with");
+    merge(&[0, 2, 0])
+        .await
+        .expect_err("indexes must be distinct");
+    merge(&[0, 2, 3])
+        .await
+        .expect_err("clawback entries can't be merged");
+    merge(&[0, 4]).await.expect_err("different mints");
+    merge(&[2, 5])
+        .await
+        .expect_err("locked tokens can't get a shorter lockup");
+    merge(&[0, 6]).await.expect_err("unused entry");
+
+    // a day in, 100 of the daily vesting entry are unlocked
+    addin
+        .set_time_offset(&registrar, &realm_authority, 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+
+    // the cliff entry is picked as the target regardless of the order
+    merge(&[1, 0, 2]).await.unwrap();
+    let voter_data = voter.load(&context.solana).await;
+    assert!(!voter_data.deposit(0).unwrap().is_used);
+    assert!(!voter_data.deposit(1).unwrap().is_used);
+    let target = *voter_data.deposit(2).unwrap();
+    assert!(target.is_used);
+    assert_eq!(target.lockup.kind, LockupKind::Cliff);
+    assert_eq!(target.amount_deposited_native, 900);
+    assert_eq!(target.amount_initially_locked_native, 700);
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &voter)
+            .await,
+        2000
+    );
+
+    // only the previously unlocked tokens can be withdrawn
+    withdraw(2, 201).await.expect_err("700 are locked");
+    withdraw(2, 200).await.unwrap();
+
+    // after the cliff everything is unlocked
+    addin
+        .set_time_offset(&registrar, &realm_authority, 5 * 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+    withdraw(2, 700).await.unwrap();
+
+    Ok(())
+}
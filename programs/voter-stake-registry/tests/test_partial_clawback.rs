@@ -0,0 +1,296 @@
+use solana_program_test::*;
+use solana_sdk::{signer::Signer, transport::TransportError};
+
+use program_test::*;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_partial_clawback() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+
+    let community_token_mint = &context.mints[0];
+
+    let realm_authority = &context.users[0].key;
+    let realm_authority_ata = context.users[0].token_accounts[0];
+
+    let voter_authority = &context.users[1].key;
+    let voter_authority_ata = context.users[1].token_accounts[0];
+
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            community_token_mint,
+            &realm_authority,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &realm_authority)
+        .await;
+
+    let registrar = context
+        .addin
+        .create_registrar(&realm, realm_authority, realm_authority)
+        .await;
+
+    let mngo_voting_mint = context
+        .addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            realm_authority,
+            0,
+            community_token_mint,
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = context
+        .addin
+        .create_voter(
+            &registrar,
+            &token_owner_record,
+            &voter_authority,
+            &realm_authority,
+        )
+        .await;
+
+    let realm_ata_initial = context
+        .solana
+        .token_account_balance(realm_authority_ata)
+        .await;
+    let voter_ata_initial = context
+        .solana
+        .token_account_balance(voter_authority_ata)
+        .await;
+
+    context
+        .addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            voter_stake_registry::state::LockupKind::Daily,
+            None,
+            10,
+            true,
+        )
+        .await?;
+    context
+        .addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            0,
+            10000,
+        )
+        .await?;
+
+    // Advance almost three days: two of the ten days have vested
+    context
+        .addin
+        .set_time_offset(&registrar, &realm_authority, (3 * 24 - 1) * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+
+    context
+        .addin
+        .partial_clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            realm_authority_ata,
+            0,
+            4000,
+        )
+        .await
+        .expect_err("fails because realm_authority is invalid");
+    context
+        .addin
+        .partial_clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            0,
+            8001,
+        )
+        .await
+        .expect_err("fails because only 8000 tokens are locked");
+
+    context
+        .addin
+        .partial_clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            0,
+            4000,
+        )
+        .await?;
+
+    assert_eq!(
+        context
+            .solana
+            .token_account_balance(realm_authority_ata)
+            .await,
+        realm_ata_initial - 6000
+    );
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 6000);
+
+    // the remaining 4000 locked tokens keep vesting over the remaining 8 days
+    let voter_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Voter>(voter.address)
+        .await;
+    let deposit = &voter_data.deposits[0];
+    assert_eq!(deposit.allow_clawback, true);
+    assert_eq!(deposit.amount_initially_locked_native, 4000);
+    assert_eq!(
+        deposit.lockup.kind,
+        voter_stake_registry::state::LockupKind::Daily
+    );
+    assert_eq!(deposit.lockup.periods_total().unwrap(), 8);
+
+    context
+        .addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_authority_ata,
+            0,
+            2001,
+        )
+        .await
+        .expect_err("fails because only the vested tokens are unlocked");
+    context
+        .addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_authority_ata,
+            0,
+            2000,
+        )
+        .await?;
+
+    // Advance almost four days: one more day of the remaining eight has vested
+    context
+        .addin
+        .set_time_offset(&registrar, &realm_authority, (4 * 24 - 1) * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+
+    context
+        .addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_authority_ata,
+            0,
+            501,
+        )
+        .await
+        .expect_err("fails because only 500 more tokens vested");
+
+    context
+        .addin
+        .partial_clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            0,
+            0,
+        )
+        .await
+        .expect_err("fails because the amount is zero");
+
+    // clawing back all 3500 locked tokens ends the lockup
+    context
+        .addin
+        .partial_clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            0,
+            3500,
+        )
+        .await?;
+    let voter_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Voter>(voter.address)
+        .await;
+    let deposit = &voter_data.deposits[0];
+    assert_eq!(deposit.allow_clawback, false);
+    assert_eq!(deposit.amount_initially_locked_native, 0);
+    assert_eq!(deposit.amount_deposited_native, 500);
+    assert_eq!(
+        deposit.lockup.kind,
+        voter_stake_registry::state::LockupKind::None
+    );
+
+    context
+        .addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &voter_authority,
+            voter_authority_ata,
+            0,
+            500,
+        )
+        .await?;
+
+    assert_eq!(
+        context
+            .solana
+            .token_account_balance(voter_authority_ata)
+            .await,
+        voter_ata_initial + 2500
+    );
+    assert_eq!(
+        context
+            .solana
+            .token_account_balance(realm_authority_ata)
+            .await,
+        realm_ata_initial - 2500
+    );
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &voter)
+            .await,
+        0
+    );
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::{LockupKind, Registrar};
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_remove_voting_mint() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter_usdc = context.users[1].token_accounts[1];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    let usdc_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    for (index, voting_mint, token_account) in [
+        (0, &mngo_voting_mint, voter_mngo),
+        (1, &usdc_voting_mint, voter_usdc),
+    ] {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                voting_mint,
+                index,
+                LockupKind::None,
+                None,
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                voting_mint,
+                voter_authority,
+                token_account,
+                index,
+                1000,
+            )
+            .await
+            .unwrap();
+    }
+
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000 + 2 * 1000);
+
+    addin
+        .remove_voting_mint(&registrar, &context.users[2].key, 1)
+        .await
+        .expect_err("fails because the realm authority must sign");
+    addin
+        .remove_voting_mint(&registrar, &realm_authority, 2)
+        .await
+        .expect_err("fails because the index is unused");
+    addin
+        .remove_voting_mint(&registrar, &realm_authority, 1)
+        .await
+        .unwrap();
+
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    let usdc_config = &registrar_data.voting_mints[1];
+    assert!(usdc_config.deprecated);
+    assert_eq!(usdc_config.deposited_native, 1000);
+
+    let other_mint_authority = Keypair::new();
+    let other_mint = MintCookie {
+        index: 2,
+        decimals: 6,
+        unit: 10u64.pow(6) as f64,
+        base_lot: 0 as f64,
+        quote_lot: 0 as f64,
+        pubkey: Some(
+            context
+                .solana
+                .create_mint(&other_mint_authority.pubkey(), 6)
+                .await,
+        ),
+        authority: other_mint_authority,
+        token_program: spl_token::id(),
+    };
+    let mngo_mint = [context.mints[0].pubkey.unwrap()];
+    let configure_other_mint = || {
+        addin.try_configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &other_mint,
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&mngo_mint),
+        )
+    };
+    configure_other_mint()
+        .await
+        .expect_err("fails because the index is reserved for the removed mint");
+
+    // the usdc deposit no longer counts
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000);
+
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &usdc_voting_mint,
+            voter_authority,
+            voter_usdc,
+            1,
+            1000,
+        )
+        .await
+        .expect_err("fails because the mint was removed");
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &usdc_voting_mint,
+            2,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .expect_err("fails because the mint was removed");
+
+    // withdraws of the removed mint still check the token owner record
+    let voter_with_other_record = VoterCookie {
+        token_owner_record: realm
+            .create_token_owner_record(context.users[2].key.pubkey(), &payer)
+            .await
+            .address,
+        ..voter
+    };
+    addin
+        .withdraw(
+            &registrar,
+            &voter_with_other_record,
+            &usdc_voting_mint,
+            voter_authority,
+            voter_usdc,
+            1,
+            500,
+        )
+        .await
+        .expect_err("fails because the token owner record is not the voter's");
+    let withdraw_usdc = |amount: u64| {
+        addin.withdraw(
+            &registrar,
+            &voter,
+            &usdc_voting_mint,
+            voter_authority,
+            voter_usdc,
+            1,
+            amount,
+        )
+    };
+    withdraw_usdc(500).await.unwrap();
+
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    configure_other_mint()
+        .await
+        .expect_err("fails because usdc tokens are still deposited");
+
+    // configuring the mint again makes it a regular voting mint
+    addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &usdc_voting_mint,
+            voter_authority,
+            voter_usdc,
+            1,
+            500,
+        )
+        .await
+        .unwrap();
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000 + 2 * 1000);
+
+    // once all its tokens are withdrawn, the index of a removed mint can be reused
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .remove_voting_mint(&registrar, &realm_authority, 1)
+        .await
+        .unwrap();
+    withdraw_usdc(1000).await.unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 1).await, 0);
+
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let other_voting_mint = configure_other_mint().await.unwrap();
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    let other_config = &registrar_data.voting_mints[1];
+    assert_eq!(other_config.mint, other_mint.pubkey.unwrap());
+    assert!(!other_config.deprecated);
+    assert_eq!(other_config.deposited_native, 0);
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 1000);
+
+    // the emptied deposit entry can be replaced by one for the new mint
+    addin
+        .close_deposit_entry(&voter, voter_authority, 1)
+        .await
+        .unwrap();
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &other_voting_mint,
+            1,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    let voter_other = context
+        .solana
+        .create_token_account(&voter_authority.pubkey(), other_mint.pubkey.unwrap())
+        .await;
+    context
+        .solana
+        .mint_to(
+            other_mint.pubkey.unwrap(),
+            &other_mint.authority,
+            voter_other,
+            300,
+        )
+        .await;
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &other_voting_mint,
+            voter_authority,
+            voter_other,
+            1,
+            300,
+        )
+        .await
+        .unwrap();
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000 + 300);
+
+    Ok(())
+}
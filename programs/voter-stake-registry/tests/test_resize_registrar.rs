@@ -0,0 +1,483 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError, transport::TransportError,
+};
+use std::mem::size_of;
+use voter_stake_registry::state::*;
+
+mod program_test;
+
+fn assert_error_code(err: BanksClientError, expected: u32) {
+    match err {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected),
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+fn assert_invalid_registrar_size(err: BanksClientError) {
+    assert_error_code(err, 6040); // InvalidRegistrarSize
+}
+
+/// Turns the registrar into one with the layout of the first release:
+/// four voting mints, whose configs ended after digit_shift, and no totals
+async fn set_legacy_registrar_layout(context: &TestContext, registrar: &RegistrarCookie) {
+    let data = context.solana.get_account_data(registrar.address).await;
+    let head_len = 8 + 5 * 32;
+    let config_len = size_of::<VotingMintConfig>();
+    let tail_start = head_len + MAX_VOTING_MINTS * config_len;
+    let mut legacy_data = data[..head_len + 4 * config_len].to_vec();
+    for config in legacy_data[head_len..].chunks_mut(config_len) {
+        config[2 * 32 + 3 * 8 + 1..].fill(0);
+    }
+    legacy_data.extend_from_slice(&data[tail_start..tail_start + 8 + 1 + 95]);
+    assert_eq!(legacy_data.len(), 8 + 5 * 32 + 4 * 152 + 8 + 1 + 95);
+    context
+        .solana
+        .set_account_data(registrar.address, &legacy_data)
+        .await;
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_resize_registrar() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            2.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+
+    set_legacy_registrar_layout(&context, &registrar).await;
+
+    // the registrar can't be used before it's migrated
+    assert_invalid_registrar_size(
+        addin
+            .update_voter_weight_record(&registrar, &voter)
+            .await
+            .map(|_| ())
+            .expect_err("fails because the registrar has an older layout"),
+    );
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    assert_invalid_registrar_size(
+        addin
+            .try_configure_voting_mint(
+                &registrar,
+                &realm_authority,
+                payer,
+                0,
+                &context.mints[0],
+                0,
+                1.0,
+                0.0,
+                5 * 365 * 24 * 60 * 60,
+                None,
+                None,
+            )
+            .await
+            .expect_err("fails because the registrar has an older layout"),
+    );
+
+    addin.resize_registrar(&registrar, payer).await.unwrap();
+    assert_eq!(
+        context
+            .solana
+            .get_account_data(registrar.address)
+            .await
+            .len(),
+        REGISTRAR_ACCOUNT_LEN
+    );
+
+    // existing configs and deposits keep their indexes, the new ones are unused
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    assert_eq!(
+        registrar_data.voting_mints[0].mint,
+        context.mints[0].pubkey.unwrap()
+    );
+    assert_eq!(
+        registrar_data.voting_mints[1].mint,
+        context.mints[1].pubkey.unwrap()
+    );
+    assert!(!registrar_data.voting_mints[0].deposited_native_tracked);
+    assert!(registrar_data.voting_mints[4..]
+        .iter()
+        .all(|config| !config.in_use()));
+    assert!(!registrar_data.voting_mint_totals_tracked[0]);
+    assert_eq!(
+        registrar_data.max_vote_weight_mode,
+        MaxVoteWeightMode::MintSupply
+    );
+
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 1000);
+
+    // the new indexes can be configured
+    let mut configured_mints = vec![
+        context.mints[0].pubkey.unwrap(),
+        context.mints[1].pubkey.unwrap(),
+    ];
+    for index in 4..8 {
+        let mint_authority = Keypair::new();
+        let mint = MintCookie {
+            index: index as usize,
+            decimals: 6,
+            unit: 10u64.pow(6) as f64,
+            base_lot: 0 as f64,
+            quote_lot: 0 as f64,
+            pubkey: Some(
+                context
+                    .solana
+                    .create_mint(&mint_authority.pubkey(), 6)
+                    .await,
+            ),
+            authority: mint_authority,
+            token_program: spl_token::id(),
+        };
+        addin
+            .configure_voting_mint(
+                &registrar,
+                &realm_authority,
+                payer,
+                index,
+                &mint,
+                0,
+                1.0,
+                0.0,
+                5 * 365 * 24 * 60 * 60,
+                None,
+                Some(&configured_mints),
+            )
+            .await;
+        configured_mints.push(mint.pubkey.unwrap());
+    }
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    for index in 4..8 {
+        assert_eq!(
+            registrar_data.voting_mints[index].mint,
+            configured_mints[index - 2]
+        );
+        assert!(registrar_data.voting_mints[index].deposited_native_tracked);
+        assert!(registrar_data.voting_mint_totals_tracked[index]);
+    }
+
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            0,
+            1000,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 0);
+
+    // resizing again does nothing
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin.resize_registrar(&registrar, payer).await.unwrap();
+
+    Ok(())
+}
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_backfill_voting_mint_totals() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    // one voter with a locked deposit, one with a clawback-enabled one
+    let mut voters = vec![];
+    for (user, lockup_kind, allow_clawback, amount) in [
+        (1, LockupKind::Cliff, false, 1000),
+        (2, LockupKind::Daily, true, 500),
+    ] {
+        let voter_authority = &context.users[user].key;
+        let token_owner_record = realm
+            .create_token_owner_record(voter_authority.pubkey(), payer)
+            .await;
+        let voter = addin
+            .create_voter(&registrar, &token_owner_record, voter_authority, payer)
+            .await;
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                &mngo_voting_mint,
+                0,
+                lockup_kind,
+                None,
+                10,
+                allow_clawback,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                &mngo_voting_mint,
+                voter_authority,
+                context.users[user].token_accounts[0],
+                0,
+                amount,
+            )
+            .await
+            .unwrap();
+        voters.push(voter);
+    }
+    let (locked_voter, clawback_voter) = (&voters[0], &voters[1]);
+
+    set_legacy_registrar_layout(&context, &registrar).await;
+    addin.resize_registrar(&registrar, payer).await.unwrap();
+
+    let load_registrar = || context.solana.get_account::<Registrar>(registrar.address);
+    let registrar_data = load_registrar().await;
+    assert!(!registrar_data.voting_mints[0].deposited_native_tracked);
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 0);
+    assert!(!registrar_data.voting_mint_totals_tracked[0]);
+    assert_eq!(registrar_data.voting_mint_totals[0].locked_native, 0);
+
+    // changing a voter's deposits counts all of them first
+    addin
+        .deposit(
+            &registrar,
+            locked_voter,
+            &mngo_voting_mint,
+            &context.users[1].key,
+            context.users[1].token_accounts[0],
+            0,
+            100,
+        )
+        .await
+        .unwrap();
+    let registrar_data = load_registrar().await;
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 1100);
+    assert_eq!(registrar_data.voting_mint_totals[0].locked_native, 1100);
+    assert_eq!(
+        registrar_data.voting_mint_totals[0].clawback_deposited_native,
+        0
+    );
+
+    // anyone can count the other voters, counting a voter again does nothing
+    addin
+        .backfill_voting_mint_totals(&registrar, &[locked_voter, clawback_voter])
+        .await
+        .unwrap();
+    addin
+        .backfill_voting_mint_totals(&registrar, &[clawback_voter])
+        .await
+        .unwrap();
+    let registrar_data = load_registrar().await;
+    assert!(!registrar_data.voting_mints[0].deposited_native_tracked);
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 1600);
+    assert_eq!(registrar_data.voting_mint_totals[0].locked_native, 1600);
+    assert_eq!(
+        registrar_data.voting_mint_totals[0].clawback_deposited_native,
+        500
+    );
+
+    // the max vote weight can't be based on deposits before the backfill is finished
+    let max_voter_weight_record = addin
+        .create_max_voter_weight_record(&registrar, payer)
+        .await;
+    assert_error_code(
+        addin
+            .configure_max_vote_weight_mode(
+                &registrar,
+                &realm_authority,
+                MaxVoteWeightMode::Deposited,
+            )
+            .await
+            .expect_err("fails because deposits of mngo aren't tracked yet"),
+        6054, // VotingMintDepositsNotTracked
+    );
+
+    // only the realm authority can finish the backfill
+    assert_error_code(
+        addin
+            .finish_voting_mint_backfill(&registrar, payer, 0)
+            .await
+            .expect_err("fails because the payer isn't the realm authority"),
+        2001, // ConstraintHasOne
+    );
+    addin
+        .finish_voting_mint_backfill(&registrar, &realm_authority, 0)
+        .await
+        .unwrap();
+    let registrar_data = load_registrar().await;
+    assert!(registrar_data.voting_mints[0].deposited_native_tracked);
+    assert!(registrar_data.voting_mint_totals_tracked[0]);
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    assert_error_code(
+        addin
+            .finish_voting_mint_backfill(&registrar, &realm_authority, 0)
+            .await
+            .expect_err("fails because the mint is tracked already"),
+        6059, // VotingMintNotBackfilling
+    );
+    addin
+        .configure_max_vote_weight_mode(&registrar, &realm_authority, MaxVoteWeightMode::Deposited)
+        .await
+        .unwrap();
+    let record = addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[])
+        .await
+        .unwrap();
+    assert_eq!(record.max_voter_weight, 1600);
+
+    // tracked totals follow the deposits
+    addin
+        .clawback(
+            &registrar,
+            clawback_voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            context.users[0].token_accounts[0],
+            0,
+        )
+        .await
+        .unwrap();
+    let registrar_data = load_registrar().await;
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 1100);
+    assert_eq!(registrar_data.voting_mint_totals[0].locked_native, 1100);
+    assert_eq!(
+        registrar_data.voting_mint_totals[0].clawback_deposited_native,
+        0
+    );
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let record = addin
+        .update_max_vote_weight(&registrar, max_voter_weight_record, &[])
+        .await
+        .unwrap();
+    assert_eq!(record.max_voter_weight, 1100);
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use anchor_spl::token::TokenAccount;
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_resize_voter() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    let create_entry = |index: u8| {
+        addin.create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            index,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+    };
+
+    create_entry(32)
+        .await
+        .expect_err("fails because the voter only has 32 deposit entries");
+
+    addin
+        .resize_voter(&registrar, &voter, voter_authority, payer, 40)
+        .await
+        .unwrap();
+    assert_eq!(voter.load(&context.solana).await.deposit_entry_count(), 40);
+
+    addin
+        .resize_voter(&registrar, &voter, voter_authority, payer, 36)
+        .await
+        .expect_err("fails because voters can't shrink");
+    addin
+        .resize_voter(&registrar, &voter, voter_authority, payer, 129)
+        .await
+        .expect_err("fails because it's more than MAX_DEPOSIT_ENTRIES");
+    addin
+        .resize_voter(&registrar, &voter, &context.users[2].key, payer, 48)
+        .await
+        .expect_err("fails because voter_authority is invalid");
+
+    create_entry(40)
+        .await
+        .expect_err("fails because the voter only has 40 deposit entries");
+    create_entry(39).await.unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            39,
+            10000,
+        )
+        .await
+        .unwrap();
+    assert_eq!(voter.deposit_amount(&context.solana, 39).await, 10000);
+
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 10000);
+
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            39,
+            10000,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        context
+            .solana
+            .get_account::<TokenAccount>(voter.vault_address(&mngo_voting_mint))
+            .await
+            .amount,
+        0
+    );
+
+    Ok(())
+}
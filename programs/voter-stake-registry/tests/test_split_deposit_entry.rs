@@ -0,0 +1,170 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_split_deposit_entry() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    // three days of vesting and a clawback-enabled entry
+    for (index, allow_clawback) in [(0, false), (1, true)] {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                &mngo_voting_mint,
+                index,
+                LockupKind::Daily,
+                None,
+                3,
+                allow_clawback,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                &mngo_voting_mint,
+                voter_authority,
+                voter_mngo,
+                index,
+                900,
+            )
+            .await
+            .unwrap();
+    }
+
+    let split = |index: u8, amount: u64| {
+        addin.split_deposit_entry(&registrar, &voter, voter_authority, index, amount)
+    };
+    let withdraw = |index: u8, amount: u64| {
+        addin.withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            index,
+            amount,
+        )
+    };
+
+    split(1, 300)
+        .await
+        .expect_err("clawback entries can't be split");
+    split(0, 0).await.expect_err("nothing to split");
+    split(0, 900)
+        .await
+        .expect_err("must leave something behind");
+
+    split(0, 300).await.unwrap();
+    let voter_data = voter.load(&context.solana).await;
+    let source = *voter_data.deposit(0).unwrap();
+    let target = *voter_data.deposit(2).unwrap();
+    assert_eq!(source.amount_deposited_native, 600);
+    assert_eq!(source.amount_initially_locked_native, 600);
+    assert_eq!(target.amount_deposited_native, 300);
+    assert_eq!(target.amount_initially_locked_native, 300);
+    assert!(target.is_used);
+    assert!(!target.allow_clawback);
+    assert_eq!(target.lockup.kind, LockupKind::Daily);
+    assert_eq!(target.lockup.start_ts(), source.lockup.start_ts());
+    assert_eq!(target.lockup.end_ts(), source.lockup.end_ts());
+
+    // both entries vest a third per day
+    addin
+        .set_time_offset(&registrar, &realm_authority, 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+    withdraw(0, 201).await.expect_err("only 200 vested");
+    withdraw(0, 150).await.unwrap();
+    withdraw(2, 101).await.expect_err("only 100 vested");
+    withdraw(2, 100).await.unwrap();
+
+    // splitting 450 deposited of which 400 are still locked: the new entry's
+    // locked share is rounded down and the remainder stays locked in the source
+    split(0, 100).await.unwrap();
+    let voter_data = voter.load(&context.solana).await;
+    let source = *voter_data.deposit(0).unwrap();
+    let target = *voter_data.deposit(3).unwrap();
+    assert_eq!(source.amount_deposited_native, 350);
+    assert_eq!(source.amount_initially_locked_native, 312);
+    assert_eq!(target.amount_deposited_native, 100);
+    assert_eq!(target.amount_initially_locked_native, 88);
+    assert_eq!(target.lockup.end_ts(), source.lockup.end_ts());
+    let registrar_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Registrar>(registrar.address)
+        .await;
+    assert_eq!(
+        registrar_data.voting_mint_totals[0].locked_native,
+        312 + 88 + 200 + 900
+    );
+
+    // the rest vests on schedule
+    addin
+        .set_time_offset(&registrar, &realm_authority, 2 * 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+    withdraw(0, 195).await.expect_err("only 38 + 156 unlocked");
+    withdraw(0, 194).await.unwrap();
+    withdraw(3, 57).await.expect_err("only 12 + 44 unlocked");
+    withdraw(3, 56).await.unwrap();
+    addin
+        .set_time_offset(&registrar, &realm_authority, 3 * 25 * 60 * 60)
+        .await;
+    context.solana.advance_clock_by_slots(2).await;
+    withdraw(0, 156).await.unwrap();
+    withdraw(3, 44).await.unwrap();
+
+    Ok(())
+}
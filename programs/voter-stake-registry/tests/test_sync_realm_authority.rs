@@ -0,0 +1,86 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::Registrar;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_sync_realm_authority() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    for index in 0..2 {
+        addin
+            .configure_voting_mint(
+                &registrar,
+                &realm_authority,
+                payer,
+                index,
+                &context.mints[index as usize],
+                0,
+                1.0,
+                0.0,
+                5 * 365 * 24 * 60 * 60,
+                None,
+                Some(&[context.mints[0].pubkey.unwrap()]),
+            )
+            .await;
+    }
+
+    // hand the realm to a new authority
+    let new_authority = Keypair::new();
+    realm
+        .set_realm_authority(&realm_authority, Some(&new_authority.pubkey()))
+        .await;
+
+    addin
+        .remove_voting_mint(&registrar, &new_authority, 1)
+        .await
+        .expect_err("fails because the registrar wasn't synced yet");
+
+    addin.sync_realm_authority(&registrar, &realm).await;
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    assert_eq!(registrar_data.realm_authority, new_authority.pubkey());
+
+    addin
+        .remove_voting_mint(&registrar, &realm_authority, 1)
+        .await
+        .expect_err("fails because it's the old authority");
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .remove_voting_mint(&registrar, &new_authority, 1)
+        .await
+        .unwrap();
+
+    // removing the realm authority clears it on the registrar too
+    realm.set_realm_authority(&new_authority, None).await;
+    addin.sync_realm_authority(&registrar, &realm).await;
+    let registrar_data = context
+        .solana
+        .get_account::<Registrar>(registrar.address)
+        .await;
+    assert_eq!(registrar_data.realm_authority, Pubkey::default());
+
+    Ok(())
+}
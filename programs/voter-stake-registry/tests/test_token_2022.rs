@@ -0,0 +1,316 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_token_2022_transfer_fee() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter2_authority = &context.users[2].key;
+    let grant_authority = &context.users[3].key;
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+
+    // a token-2022 mint charging 1% on every transfer
+    let mint_authority = Keypair::new();
+    let fee_mint = MintCookie {
+        index: 2,
+        decimals: 6,
+        unit: 10u64.pow(6) as f64,
+        base_lot: 0 as f64,
+        quote_lot: 0 as f64,
+        pubkey: Some(
+            context
+                .solana
+                .create_mint_with_transfer_fee(&mint_authority.pubkey(), 6, 100, 1_000_000)
+                .await,
+        ),
+        authority: mint_authority,
+        token_program: spl_token_2022::id(),
+    };
+    let fee_mint_pk = fee_mint.pubkey.unwrap();
+    let voter_tokens = context
+        .solana
+        .create_token_account(&voter_authority.pubkey(), fee_mint_pk)
+        .await;
+    let grant_funds = context
+        .solana
+        .create_token_account(&grant_authority.pubkey(), fee_mint_pk)
+        .await;
+    let realm_tokens = context
+        .solana
+        .create_token_account(&realm_authority.pubkey(), fee_mint_pk)
+        .await;
+    context
+        .solana
+        .mint_to(fee_mint_pk, &fee_mint.authority, voter_tokens, 10000)
+        .await;
+    context
+        .solana
+        .mint_to(fee_mint_pk, &fee_mint.authority, grant_funds, 20000)
+        .await;
+
+    let fee_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &fee_mint,
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            Some(grant_authority.pubkey()),
+            None,
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &fee_voting_mint,
+            0,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+
+    // only what arrives in the vault is credited
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &fee_voting_mint,
+            voter_authority,
+            voter_tokens,
+            0,
+            10000,
+        )
+        .await
+        .unwrap();
+    assert_eq!(context.solana.token_account_balance(voter_tokens).await, 0);
+    assert_eq!(
+        fee_voting_mint.vault_balance(&context.solana, &voter).await,
+        9900
+    );
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 9900);
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.voter_weight, 9900);
+
+    // withdrawing debits the full amount, the destination receives it minus the fee
+    context.solana.advance_clock_by_slots(2).await;
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &fee_voting_mint,
+            voter_authority,
+            voter_tokens,
+            0,
+            9900,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fee_voting_mint.vault_balance(&context.solana, &voter).await,
+        0
+    );
+    assert_eq!(voter.deposit_amount(&context.solana, 0).await, 0);
+    assert_eq!(
+        context.solana.token_account_balance(voter_tokens).await,
+        9801
+    );
+
+    // grants are credited with what arrives in the vault too
+    let voter2 = addin
+        .grant(
+            &registrar,
+            voter2_authority.pubkey(),
+            &fee_voting_mint,
+            LockupKind::Monthly,
+            None,
+            12,
+            true,
+            20000,
+            grant_funds,
+            &grant_authority,
+            &grant_authority,
+        )
+        .await
+        .unwrap();
+    assert_eq!(context.solana.token_account_balance(grant_funds).await, 0);
+    assert_eq!(
+        fee_voting_mint
+            .vault_balance(&context.solana, &voter2)
+            .await,
+        19800
+    );
+    let voter2_data = voter2.load(&context.solana).await;
+    let deposit = voter2_data.deposit(0).unwrap();
+    assert_eq!(deposit.amount_deposited_native, 19800);
+    assert_eq!(deposit.amount_initially_locked_native, 19800);
+
+    // clawback takes the full locked amount out of the vault
+    addin
+        .clawback(
+            &registrar,
+            &voter2,
+            &fee_voting_mint,
+            &realm_authority,
+            realm_tokens,
+            0,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fee_voting_mint
+            .vault_balance(&context.solana, &voter2)
+            .await,
+        0
+    );
+    assert_eq!(voter2.deposit_amount(&context.solana, 0).await, 0);
+    assert_eq!(
+        context.solana.token_account_balance(realm_tokens).await,
+        19602
+    );
+
+    // the registrar's deposit total follows the credited amounts
+    let registrar_data = context
+        .solana
+        .get_account::<voter_stake_registry::state::Registrar>(registrar.address)
+        .await;
+    assert_eq!(registrar_data.voting_mints[0].deposited_native, 0);
+
+    // a voter with vaults of both token programs can be closed
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            Some(&[fee_mint_pk]),
+        )
+        .await;
+    let voter_mngo = context.users[1].token_accounts[0];
+    addin
+        .create_deposit_entry(
+            &registrar,
+            &voter,
+            voter_authority,
+            &mngo_voting_mint,
+            1,
+            LockupKind::None,
+            None,
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+    addin
+        .deposit(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            100,
+        )
+        .await
+        .unwrap();
+    addin
+        .withdraw(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            voter_authority,
+            voter_mngo,
+            1,
+            100,
+        )
+        .await
+        .unwrap();
+    // the vault can only be closed once its withheld fees are harvested
+    context
+        .solana
+        .process_transaction(
+            &[
+                spl_token_2022::extension::transfer_fee::instruction::harvest_withheld_tokens_to_mint(
+                    &spl_token_2022::id(),
+                    &fee_mint_pk,
+                    &[&voter.vault_address(&fee_voting_mint)],
+                )
+                .unwrap(),
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+    addin
+        .close_voter(
+            &registrar,
+            &voter,
+            &[&fee_voting_mint, &mngo_voting_mint],
+            voter_authority,
+        )
+        .await
+        .unwrap();
+    for vault in [
+        voter.vault_address(&fee_voting_mint),
+        voter.vault_address(&mngo_voting_mint),
+    ] {
+        assert!(context
+            .solana
+            .context
+            .borrow_mut()
+            .banks_client
+            .get_account(vault)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_update_voter_weight_records() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            0.0,
+            5 * 365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+
+    // two voters with 1000 and 2000 deposited
+    let mut voters = vec![];
+    for (user, amount) in [(&context.users[1], 1000), (&context.users[2], 2000)] {
+        let token_owner_record = realm
+            .create_token_owner_record(user.key.pubkey(), &payer)
+            .await;
+        let voter = addin
+            .create_voter(&registrar, &token_owner_record, &user.key, &payer)
+            .await;
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                &user.key,
+                &mngo_voting_mint,
+                0,
+                LockupKind::None,
+                None,
+                0,
+                false,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                &mngo_voting_mint,
+                &user.key,
+                user.token_accounts[0],
+                0,
+                amount,
+            )
+            .await
+            .unwrap();
+        voters.push(voter);
+    }
+    let (voter1, voter2) = (&voters[0], &voters[1]);
+
+    // needs at least one voter
+    assert!(addin
+        .update_voter_weight_records(&registrar, &[])
+        .await
+        .is_err());
+    // the voter weight record must be the voter's
+    let mismatched = VoterCookie {
+        address: voter1.address,
+        authority: voter1.authority,
+        voter_weight_record: voter2.voter_weight_record,
+        token_owner_record: voter1.token_owner_record,
+    };
+    assert!(addin
+        .update_voter_weight_records(&registrar, &[voter2, &mismatched])
+        .await
+        .is_err());
+
+    let records = addin
+        .update_voter_weight_records(&registrar, &[voter1, voter2])
+        .await
+        .unwrap();
+    let slot = context.solana.get_clock().await.slot;
+    assert_eq!(records[0].voter_weight, 1000);
+    assert_eq!(records[0].voter_weight_expiry, Some(slot));
+    assert_eq!(records[1].voter_weight, 2000);
+    assert_eq!(records[1].voter_weight_expiry, Some(slot));
+
+    Ok(())
+}
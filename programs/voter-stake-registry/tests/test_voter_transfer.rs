@@ -0,0 +1,297 @@
+use program_test::*;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transport::TransportError};
+use voter_stake_registry::state::LockupKind;
+
+mod program_test;
+
+#[allow(unaligned_references)]
+#[tokio::test]
+async fn test_voter_transfer() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let addin = &context.addin;
+
+    let payer = &context.users[0].key;
+    let realm_authority = Keypair::new();
+    let realm_authority_ata = context.users[0].token_accounts[0];
+    let realm = context
+        .governance
+        .create_realm(
+            "testrealm",
+            realm_authority.pubkey(),
+            &context.mints[0],
+            &payer,
+            &context.addin.program_id,
+        )
+        .await;
+
+    let voter_authority = &context.users[1].key;
+    let voter_mngo = context.users[1].token_accounts[0];
+    let voter_usdc = context.users[1].token_accounts[1];
+    let new_authority = &context.users[2].key;
+    let new_mngo = context.users[2].token_accounts[0];
+    let new_usdc = context.users[2].token_accounts[1];
+    let token_owner_record = realm
+        .create_token_owner_record(voter_authority.pubkey(), &payer)
+        .await;
+    let new_token_owner_record = realm
+        .create_token_owner_record(new_authority.pubkey(), &payer)
+        .await;
+
+    let registrar = addin
+        .create_registrar(&realm, &realm_authority, payer)
+        .await;
+    let mngo_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            0,
+            &context.mints[0],
+            0,
+            1.0,
+            1.0,
+            365 * 24 * 60 * 60,
+            None,
+            None,
+        )
+        .await;
+    let usdc_voting_mint = addin
+        .configure_voting_mint(
+            &registrar,
+            &realm_authority,
+            payer,
+            1,
+            &context.mints[1],
+            0,
+            1.0,
+            0.0,
+            365 * 24 * 60 * 60,
+            None,
+            Some(&[context.mints[0].pubkey.unwrap()]),
+        )
+        .await;
+
+    let voter = addin
+        .create_voter(&registrar, &token_owner_record, &voter_authority, &payer)
+        .await;
+
+    // a locked entry, an unlocked one and a locked one with clawback
+    for (index, voting_mint, kind, periods, allow_clawback, token, amount) in [
+        (
+            0,
+            &mngo_voting_mint,
+            LockupKind::Cliff,
+            100,
+            false,
+            voter_mngo,
+            1000,
+        ),
+        (
+            1,
+            &usdc_voting_mint,
+            LockupKind::None,
+            0,
+            false,
+            voter_usdc,
+            500,
+        ),
+        (
+            2,
+            &mngo_voting_mint,
+            LockupKind::Cliff,
+            100,
+            true,
+            voter_mngo,
+            200,
+        ),
+    ] {
+        addin
+            .create_deposit_entry(
+                &registrar,
+                &voter,
+                voter_authority,
+                voting_mint,
+                index,
+                kind,
+                None,
+                periods,
+                allow_clawback,
+            )
+            .await
+            .unwrap();
+        addin
+            .deposit(
+                &registrar,
+                &voter,
+                voting_mint,
+                voter_authority,
+                token,
+                index,
+                amount,
+            )
+            .await
+            .unwrap();
+    }
+    let all_mints = [&mngo_voting_mint, &usdc_voting_mint];
+    addin
+        .accept_voter_transfer(
+            &registrar,
+            &voter,
+            &all_mints,
+            &new_token_owner_record,
+            new_authority,
+            payer,
+        )
+        .await
+        .expect_err("transfer wasn't proposed");
+
+    addin
+        .propose_voter_transfer(
+            &registrar,
+            &voter,
+            voter_authority,
+            Some(voter_authority.pubkey()),
+        )
+        .await
+        .expect_err("can't transfer to the same authority");
+    addin
+        .propose_voter_transfer(
+            &registrar,
+            &voter,
+            voter_authority,
+            Some(new_authority.pubkey()),
+        )
+        .await
+        .unwrap();
+
+    addin
+        .accept_voter_transfer(
+            &registrar,
+            &voter,
+            &all_mints,
+            &new_token_owner_record,
+            &context.users[3].key,
+            payer,
+        )
+        .await
+        .expect_err("only the proposed authority can accept");
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    addin
+        .accept_voter_transfer(
+            &registrar,
+            &voter,
+            &all_mints,
+            &new_token_owner_record,
+            new_authority,
+            payer,
+        )
+        .await
+        .expect_err("the clawback entry blocks the transfer");
+
+    addin
+        .clawback(
+            &registrar,
+            &voter,
+            &mngo_voting_mint,
+            &realm_authority,
+            realm_authority_ata,
+            2,
+        )
+        .await
+        .unwrap();
+    let weight_before = addin
+        .update_voter_weight_record(&registrar, &voter)
+        .await
+        .unwrap()
+        .voter_weight;
+
+    addin
+        .accept_voter_transfer(
+            &registrar,
+            &voter,
+            &[&mngo_voting_mint],
+            &new_token_owner_record,
+            new_authority,
+            payer,
+        )
+        .await
+        .expect_err("the usdc vault must be transferred too");
+    context.solana.advance_clock_by_slots(2).await; // avoid cache when sending same transaction again
+    let new_voter = addin
+        .accept_voter_transfer(
+            &registrar,
+            &voter,
+            &all_mints,
+            &new_token_owner_record,
+            new_authority,
+            payer,
+        )
+        .await
+        .unwrap();
+
+    // the old voter is gone
+    assert!(context
+        .solana
+        .context
+        .borrow_mut()
+        .banks_client
+        .get_account(voter.address)
+        .await
+        .unwrap()
+        .is_none());
+
+    // deposits, lockups and vault balances moved over
+    let new_data = new_voter.load(&context.solana).await;
+    assert_eq!(new_data.voter_authority, new_authority.pubkey());
+    let d0 = new_data.deposit(0).unwrap();
+    assert_eq!(d0.amount_deposited_native, 1000);
+    assert_eq!(d0.lockup.kind, LockupKind::Cliff);
+    assert_eq!(new_data.deposit(1).unwrap().amount_deposited_native, 500);
+    assert_eq!(
+        mngo_voting_mint
+            .vault_balance(&context.solana, &new_voter)
+            .await,
+        1000
+    );
+    assert_eq!(
+        usdc_voting_mint
+            .vault_balance(&context.solana, &new_voter)
+            .await,
+        500
+    );
+    let vwr = addin
+        .update_voter_weight_record(&registrar, &new_voter)
+        .await
+        .unwrap();
+    assert_eq!(vwr.governing_token_owner, new_authority.pubkey());
+    assert_eq!(vwr.voter_weight, weight_before);
+
+    // the lockup still applies to the new authority
+    addin
+        .withdraw(
+            &registrar,
+            &new_voter,
+            &mngo_voting_mint,
+            new_authority,
+            new_mngo,
+            0,
+            1,
+        )
+        .await
+        .expect_err("still locked");
+    addin
+        .withdraw(
+            &registrar,
+            &new_voter,
+            &usdc_voting_mint,
+            new_authority,
+            new_usdc,
+            1,
+            500,
+        )
+        .await
+        .unwrap();
+
+    Ok(())
+}
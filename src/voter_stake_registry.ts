@@ -1,5 +1,5 @@
 export type VoterStakeRegistry = {
-  "version": "0.2.1",
+  "version": "0.2.4",
   "name": "voter_stake_registry",
   "instructions": [
     {
@@ -102,188 +102,217 @@ export type VoterStakeRegistry = {
       ]
     },
     {
-      "name": "createVoter",
+      "name": "configureDepositLimits",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "voterAuthority",
+          "name": "realmAuthority",
           "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "voterWeightRecord",
-          "isMut": true,
-          "isSigner": false
+          "name": "idx",
+          "type": "u16"
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "minDepositNative",
+          "type": "u64"
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxVoterDepositedNative",
+          "type": "u64"
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "maxDepositedNative",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "configureLockupCurve",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "realmAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "voterBump",
-          "type": "u8"
+          "name": "idx",
+          "type": "u16"
         },
         {
-          "name": "voterWeightRecordBump",
-          "type": "u8"
+          "name": "kind",
+          "type": {
+            "defined": "LockupCurveKind"
+          }
+        },
+        {
+          "name": "points",
+          "type": {
+            "vec": {
+              "defined": "LockupCurvePoint"
+            }
+          }
         }
       ]
     },
     {
-      "name": "createDepositEntry",
+      "name": "configureLockupLimits",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "voterAuthority",
+          "name": "realmAuthority",
           "isMut": false,
           "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "allowedLockupKinds",
+          "type": "u16"
         },
         {
-          "name": "depositMint",
-          "isMut": false,
+          "name": "maxLockupDays",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "configureMaxVoteWeightMode",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "realmAuthority",
           "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "mode",
+          "type": {
+            "defined": "MaxVoteWeightMode"
+          }
+        }
+      ]
+    },
+    {
+      "name": "syncRealmAuthority",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "realm",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeVotingMint",
+      "accounts": [
         {
-          "name": "associatedTokenProgram",
-          "isMut": false,
+          "name": "registrar",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "rent",
+          "name": "realmAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "depositEntryIndex",
-          "type": "u8"
-        },
-        {
-          "name": "kind",
-          "type": {
-            "defined": "LockupKind"
-          }
-        },
-        {
-          "name": "startTs",
-          "type": {
-            "option": "u64"
-          }
-        },
-        {
-          "name": "periods",
-          "type": "u32"
-        },
-        {
-          "name": "allowClawback",
-          "type": "bool"
+          "name": "idx",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "deposit",
+      "name": "resizeRegistrar",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "voter",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "vault",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "backfillVotingMintTotals",
+      "accounts": [
+        {
+          "name": "registrar",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finishVotingMintBackfill",
+      "accounts": [
         {
-          "name": "depositToken",
+          "name": "registrar",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "depositAuthority",
+          "name": "realmAuthority",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "depositEntryIndex",
-          "type": "u8"
-        },
-        {
-          "name": "amount",
-          "type": "u64"
+          "name": "idx",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "withdraw",
+      "name": "createVoter",
       "accounts": [
         {
           "name": "registrar",
@@ -300,45 +329,45 @@ export type VoterStakeRegistry = {
           "isMut": false,
           "isSigner": true
         },
-        {
-          "name": "tokenOwnerRecord",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "voterWeightRecord",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "payer",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "destination",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "depositEntryIndex",
+          "name": "voterBump",
           "type": "u8"
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "voterWeightRecordBump",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "grant",
+      "name": "resizeVoter",
       "accounts": [
         {
           "name": "registrar",
@@ -353,101 +382,99 @@ export type VoterStakeRegistry = {
         {
           "name": "voterAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "voterWeightRecord",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "vault",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setDelegate",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "depositToken",
+          "name": "voter",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenAuthority",
+          "name": "voterAuthority",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "grantAuthority",
+          "name": "tokenOwnerRecord",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "voterWeightRecord",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "depositMint",
-          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "systemProgram",
+          "name": "delegate",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "proposeVoterTransfer",
+      "accounts": [
+        {
+          "name": "registrar",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "voter",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "voterAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "rent",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "voterBump",
-          "type": "u8"
-        },
-        {
-          "name": "voterWeightRecordBump",
-          "type": "u8"
-        },
-        {
-          "name": "kind",
-          "type": {
-            "defined": "LockupKind"
-          }
-        },
-        {
-          "name": "startTs",
+          "name": "newVoterAuthority",
           "type": {
-            "option": "u64"
+            "option": "publicKey"
           }
-        },
-        {
-          "name": "periods",
-          "type": "u32"
-        },
-        {
-          "name": "allowClawback",
-          "type": "bool"
-        },
-        {
-          "name": "amount",
-          "type": "u64"
         }
       ]
     },
     {
-      "name": "clawback",
+      "name": "acceptVoterTransfer",
       "accounts": [
         {
           "name": "registrar",
@@ -455,61 +482,70 @@ export type VoterStakeRegistry = {
           "isSigner": false
         },
         {
-          "name": "realmAuthority",
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerRecord",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "voter",
+          "name": "newVoter",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "vault",
+          "name": "newVoterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "newVoterWeightRecord",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destination",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "solDestination",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "depositEntryIndex",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "closeDepositEntry",
-      "accounts": [
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "voter",
-          "isMut": true,
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "voterAuthority",
+          "name": "instructions",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "resetLockup",
+      "name": "createDepositEntry",
       "accounts": [
         {
           "name": "registrar",
@@ -521,10 +557,45 @@ export type VoterStakeRegistry = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "voterAuthority",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -538,14 +609,24 @@ export type VoterStakeRegistry = {
             "defined": "LockupKind"
           }
         },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
         {
           "name": "periods",
           "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "internalTransferLocked",
+      "name": "createDepositEntryWithCliff",
       "accounts": [
         {
           "name": "registrar",
@@ -557,109 +638,134 @@ export type VoterStakeRegistry = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "voterAuthority",
           "isMut": false,
           "isSigner": true
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "sourceDepositEntryIndex",
-          "type": "u8"
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "targetDepositEntryIndex",
-          "type": "u8"
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "internalTransferUnlocked",
-      "accounts": [
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
         {
-          "name": "registrar",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "voter",
-          "isMut": true,
+          "name": "associatedTokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "voterAuthority",
+          "name": "rent",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "sourceDepositEntryIndex",
+          "name": "depositEntryIndex",
           "type": "u8"
         },
         {
-          "name": "targetDepositEntryIndex",
-          "type": "u8"
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "cliffPeriods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "updateVoterWeightRecord",
+      "name": "deposit",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "voter",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "voterWeightRecord",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "depositToken",
+          "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "updateMaxVoteWeight",
-      "accounts": [
+        },
         {
-          "name": "registrar",
+          "name": "depositAuthority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "maxVoteWeightRecord",
+          "name": "depositMint",
           "isMut": false,
           "isSigner": false
-        }
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "closeVoter",
+      "name": "depositUnlocked",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
@@ -668,50 +774,120 @@ export type VoterStakeRegistry = {
           "isSigner": false
         },
         {
-          "name": "voterAuthority",
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositAuthority",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "solDestination",
+          "name": "payer",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "logVoterInfo",
+      "name": "withdraw",
       "accounts": [
         {
           "name": "registrar",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false
         },
         {
           "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenOwnerRecord",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "depositEntryBegin",
+          "name": "depositEntryIndex",
           "type": "u8"
         },
         {
-          "name": "depositEntryCount",
-          "type": "u8"
+          "name": "amount",
+          "type": "u64"
         }
       ]
     },
     {
-      "name": "setTimeOffset",
+      "name": "grant",
       "accounts": [
         {
           "name": "registrar",
@@ -719,1669 +895,5507 @@ export type VoterStakeRegistry = {
           "isSigner": false
         },
         {
-          "name": "realmAuthority",
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "grantAuthority",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "timeOffset",
-          "type": "i64"
+          "name": "voterBump",
+          "type": "u8"
+        },
+        {
+          "name": "voterWeightRecordBump",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "registrar",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "governanceProgramId",
-            "type": "publicKey"
-          },
-          {
-            "name": "realm",
-            "type": "publicKey"
-          },
-          {
-            "name": "realmGoverningTokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "realmAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "reserved1",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "votingMints",
-            "type": {
-              "array": [
-                {
-                  "defined": "VotingMintConfig"
-                },
-                4
-              ]
-            }
-          },
-          {
-            "name": "timeOffset",
-            "type": "i64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "reserved2",
+      "name": "grantWithCliff",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "grantAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "voterBump",
+          "type": "u8"
+        },
+        {
+          "name": "voterWeightRecordBump",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "cliffPeriods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "clawback",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "partialClawback",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "closeDepositEntry",
+      "accounts": [
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resetLockup",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "internalTransferLocked",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "splitDepositEntry",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mergeDepositEntries",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndexes",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "internalTransferUnlocked",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateVoterWeightRecord",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateVoterWeightRecords",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createMaxVoterWeightRecord",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "maxVoterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateMaxVoteWeight",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "maxVoteWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeVoter",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "solDestination",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "logRegistrarInfo",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "logVoterInfo",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryBegin",
+          "type": "u8"
+        },
+        {
+          "name": "depositEntryCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setTimeOffset",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "timeOffset",
+          "type": "i64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "registrar",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "governanceProgramId",
+            "type": "publicKey"
+          },
+          {
+            "name": "realm",
+            "type": "publicKey"
+          },
+          {
+            "name": "realmGoverningTokenMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "realmAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "reserved1",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "votingMints",
+            "type": {
+              "array": [
+                {
+                  "defined": "VotingMintConfig"
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "timeOffset",
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "maxVoteWeightMode",
+            "type": {
+              "defined": "MaxVoteWeightMode"
+            }
+          },
+          {
+            "name": "reserved2",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "maxDepositedNative",
+            "type": {
+              "array": [
+                "u64",
+                8
+              ]
+            }
+          },
+          {
+            "name": "allowedLockupKinds",
+            "type": {
+              "array": [
+                "u16",
+                8
+              ]
+            }
+          },
+          {
+            "name": "reserved3",
+            "type": {
+              "array": [
+                "u64",
+                1
+              ]
+            }
+          },
+          {
+            "name": "votingMintTotals",
+            "type": {
+              "array": [
+                {
+                  "defined": "VotingMintTotals"
+                },
+                8
+              ]
+            }
+          },
+          {
+            "name": "votingMintTotalsTracked",
+            "type": {
+              "array": [
+                "bool",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "voter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voterAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "registrar",
+            "type": "publicKey"
+          },
+          {
+            "name": "deposits",
+            "type": {
+              "array": [
+                {
+                  "defined": "DepositEntry"
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "voterBump",
+            "type": "u8"
+          },
+          {
+            "name": "voterWeightRecordBump",
+            "type": "u8"
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingVoterAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegateRevoked",
+            "type": "bool"
+          },
+          {
+            "name": "votingMintsCounted",
+            "type": {
+              "array": [
+                "bool",
+                8
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                21
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "VestingInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rate",
+            "type": "u64"
+          },
+          {
+            "name": "nextTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "cliffEndTimestamp",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockingInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "endTimestamp",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "vesting",
+            "type": {
+              "option": {
+                "defined": "VestingInfo"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockupState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": {
+              "defined": "LockupKind"
+            }
+          },
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "cliffPeriods",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DepositEntryState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "votingMintConfigIndex",
+            "type": "u8"
+          },
+          {
+            "name": "amountDepositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "amountInitiallyLockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "allowClawback",
+            "type": "bool"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "LockupState"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "DepositEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "Lockup"
+            }
+          },
+          {
+            "name": "amountDepositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "amountInitiallyLockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "isUsed",
+            "type": "bool"
+          },
+          {
+            "name": "allowClawback",
+            "type": "bool"
+          },
+          {
+            "name": "votingMintConfigIdx",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                29
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockupCurvePoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockupSecs",
+            "type": "u32"
+          },
+          {
+            "name": "scaledFactor",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Lockup",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "kind",
+            "type": {
+              "defined": "LockupKind"
+            }
+          },
+          {
+            "name": "reserved1",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "cliffPeriods",
+            "type": "u32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                8
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "VotingMintTotals",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "clawbackDepositedNative",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "VotingMintConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "grantAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "baselineVoteWeightScaledFactor",
+            "type": "u64"
+          },
+          {
+            "name": "maxExtraLockupVoteWeightScaledFactor",
+            "type": "u64"
+          },
+          {
+            "name": "lockupSaturationSecs",
+            "type": "u64"
+          },
+          {
+            "name": "digitShift",
+            "type": "i8"
+          },
+          {
+            "name": "deprecated",
+            "type": "bool"
+          },
+          {
+            "name": "depositedNativeTracked",
+            "type": "bool"
+          },
+          {
+            "name": "lockupCurveKind",
+            "type": {
+              "defined": "LockupCurveKind"
+            }
+          },
+          {
+            "name": "lockupCurvePointCount",
+            "type": "u8"
+          },
+          {
+            "name": "reserved1",
+            "type": "u8"
+          },
+          {
+            "name": "maxLockupDays",
+            "type": "u16"
+          },
+          {
+            "name": "depositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "lockupCurvePoints",
             "type": {
               "array": [
-                "u8",
-                7
+                {
+                  "defined": "LockupCurvePoint"
+                },
+                4
               ]
             }
           },
-          {
-            "name": "reserved3",
-            "type": {
-              "array": [
-                "u64",
-                11
-              ]
+          {
+            "name": "minDepositNative",
+            "type": "u64"
+          },
+          {
+            "name": "maxVoterDepositedNative",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockupCurveKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Linear"
+          },
+          {
+            "name": "PiecewiseLinear"
+          },
+          {
+            "name": "Step"
+          }
+        ]
+      }
+    },
+    {
+      "name": "LockupKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "None"
+          },
+          {
+            "name": "Daily"
+          },
+          {
+            "name": "Monthly"
+          },
+          {
+            "name": "Cliff"
+          },
+          {
+            "name": "Constant"
+          },
+          {
+            "name": "Weekly"
+          },
+          {
+            "name": "Quarterly"
+          },
+          {
+            "name": "CliffMonthly"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MaxVoteWeightMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "MintSupply"
+          },
+          {
+            "name": "Deposited"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "VoterInfo",
+      "fields": [
+        {
+          "name": "votingPower",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "votingPowerBaseline",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEntryInfo",
+      "fields": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "unlocked",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "votingPower",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "votingPowerBaseline",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "locking",
+          "type": {
+            "option": {
+              "defined": "LockingInfo"
+            }
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VotingMintInfo",
+      "fields": [
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "deposited",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "locked",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        },
+        {
+          "name": "clawbackDeposited",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RegistrarCreatedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "realm",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "realmAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VotingMintConfiguredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "digitShift",
+          "type": "i8",
+          "index": false
+        },
+        {
+          "name": "baselineVoteWeightScaledFactor",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxExtraLockupVoteWeightScaledFactor",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "lockupSaturationSecs",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "grantAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockupCurveConfiguredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupCurveKind"
+          },
+          "index": false
+        },
+        {
+          "name": "lockupSaturationSecs",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "points",
+          "type": {
+            "vec": {
+              "defined": "LockupCurvePoint"
+            }
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositLimitsConfiguredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "minDepositNative",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxVoterDepositedNative",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxDepositedNative",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "LockupLimitsConfiguredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "allowedLockupKinds",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "maxLockupDays",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "MaxVoteWeightModeConfiguredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mode",
+          "type": {
+            "defined": "MaxVoteWeightMode"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VotingMintRemovedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositedNative",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VotingMintBackfillFinishedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositedNative",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "lockedNative",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "clawbackDepositedNative",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "RealmAuthoritySyncedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldRealmAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newRealmAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VoterCreatedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VoterResizedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryCount",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VoterClosedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "solDestination",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DelegateSetEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldDelegate",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "newDelegate",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VoterTransferProposedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoterAuthority",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "VoterTransferredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldVoter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldVoterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoterAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEntryCreatedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "depositEntry",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEntryClosedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GrantEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "grantAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "depositEntry",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClawbackEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ResetLockupEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "InternalTransferEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "locked",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sourceBefore",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "sourceAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "targetBefore",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "targetAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEntrySplitEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sourceBefore",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "sourceAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "target",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEntriesMergedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sourceDepositEntryIndexes",
+          "type": "bytes",
+          "index": false
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "targetBefore",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "targetAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "InvalidRate",
+      "msg": "Exchange rate must be greater than zero"
+    },
+    {
+      "code": 6001,
+      "name": "RatesFull",
+      "msg": ""
+    },
+    {
+      "code": 6002,
+      "name": "VotingMintNotFound",
+      "msg": ""
+    },
+    {
+      "code": 6003,
+      "name": "DepositEntryNotFound",
+      "msg": ""
+    },
+    {
+      "code": 6004,
+      "name": "DepositEntryFull",
+      "msg": ""
+    },
+    {
+      "code": 6005,
+      "name": "VotingTokenNonZero",
+      "msg": ""
+    },
+    {
+      "code": 6006,
+      "name": "OutOfBoundsDepositEntryIndex",
+      "msg": ""
+    },
+    {
+      "code": 6007,
+      "name": "UnusedDepositEntryIndex",
+      "msg": ""
+    },
+    {
+      "code": 6008,
+      "name": "InsufficientUnlockedTokens",
+      "msg": ""
+    },
+    {
+      "code": 6009,
+      "name": "UnableToConvert",
+      "msg": ""
+    },
+    {
+      "code": 6010,
+      "name": "InvalidLockupPeriod",
+      "msg": ""
+    },
+    {
+      "code": 6011,
+      "name": "InvalidEndTs",
+      "msg": ""
+    },
+    {
+      "code": 6012,
+      "name": "InvalidDays",
+      "msg": ""
+    },
+    {
+      "code": 6013,
+      "name": "VotingMintConfigIndexAlreadyInUse",
+      "msg": ""
+    },
+    {
+      "code": 6014,
+      "name": "OutOfBoundsVotingMintConfigIndex",
+      "msg": ""
+    },
+    {
+      "code": 6015,
+      "name": "InvalidDecimals",
+      "msg": "Exchange rate decimals cannot be larger than registrar decimals"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidToDepositAndWithdrawInOneSlot",
+      "msg": ""
+    },
+    {
+      "code": 6017,
+      "name": "ShouldBeTheFirstIxInATx",
+      "msg": ""
+    },
+    {
+      "code": 6018,
+      "name": "ForbiddenCpi",
+      "msg": ""
+    },
+    {
+      "code": 6019,
+      "name": "InvalidMint",
+      "msg": ""
+    },
+    {
+      "code": 6020,
+      "name": "DebugInstruction",
+      "msg": ""
+    },
+    {
+      "code": 6021,
+      "name": "ClawbackNotAllowedOnDeposit",
+      "msg": ""
+    },
+    {
+      "code": 6022,
+      "name": "DepositStillLocked",
+      "msg": ""
+    },
+    {
+      "code": 6023,
+      "name": "InvalidAuthority",
+      "msg": ""
+    },
+    {
+      "code": 6024,
+      "name": "InvalidTokenOwnerRecord",
+      "msg": ""
+    },
+    {
+      "code": 6025,
+      "name": "InvalidRealmAuthority",
+      "msg": ""
+    },
+    {
+      "code": 6026,
+      "name": "VoterWeightOverflow",
+      "msg": ""
+    },
+    {
+      "code": 6027,
+      "name": "LockupSaturationMustBePositive",
+      "msg": ""
+    },
+    {
+      "code": 6028,
+      "name": "VotingMintConfiguredWithDifferentIndex",
+      "msg": ""
+    },
+    {
+      "code": 6029,
+      "name": "InternalProgramError",
+      "msg": ""
+    },
+    {
+      "code": 6030,
+      "name": "InsufficientLockedTokens",
+      "msg": ""
+    },
+    {
+      "code": 6031,
+      "name": "MustKeepTokensLocked",
+      "msg": ""
+    },
+    {
+      "code": 6032,
+      "name": "InvalidLockupKind",
+      "msg": ""
+    },
+    {
+      "code": 6033,
+      "name": "InvalidChangeToClawbackDepositEntry",
+      "msg": ""
+    },
+    {
+      "code": 6034,
+      "name": "InternalErrorBadLockupVoteWeight",
+      "msg": ""
+    },
+    {
+      "code": 6035,
+      "name": "DepositStartTooFarInFuture",
+      "msg": ""
+    },
+    {
+      "code": 6036,
+      "name": "VaultTokenNonZero",
+      "msg": ""
+    },
+    {
+      "code": 6037,
+      "name": "InvalidTimestampArguments",
+      "msg": ""
+    },
+    {
+      "code": 6038,
+      "name": "InvalidTokenProgram",
+      "msg": "Token program must be spl-token or token-2022"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidVault",
+      "msg": ""
+    },
+    {
+      "code": 6040,
+      "name": "InvalidRegistrarSize",
+      "msg": ""
+    },
+    {
+      "code": 6041,
+      "name": "InvalidVoterSize",
+      "msg": ""
+    },
+    {
+      "code": 6042,
+      "name": "VotingMintDeprecated",
+      "msg": "Voting mint was removed and accepts no new deposits"
+    },
+    {
+      "code": 6043,
+      "name": "VotingMintHasDeposits",
+      "msg": "Voting mint was removed, but tokens of it may still be deposited"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidLockupCurve",
+      "msg": "Lockup curve points must increase up to the full factor at lockup saturation"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidLockupCliff",
+      "msg": "Lockup cliffs are only allowed for CliffMonthly and can't be shortened"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidDelegate",
+      "msg": "The delegate must differ from the voter authority"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidNewVoterAuthority",
+      "msg": "The new voter authority must be proposed and differ from the current one"
+    },
+    {
+      "code": 6048,
+      "name": "VaultNotTransferred",
+      "msg": "The vaults of all deposited mints must be transferred in full"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidSplitAmount",
+      "msg": "Split amount must be positive and less than the deposited amount"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidMergeDepositEntries",
+      "msg": "Merging needs at least two distinct deposit entries"
+    },
+    {
+      "code": 6051,
+      "name": "DepositBelowMinimum",
+      "msg": "Deposit is below the voting mint's minimum deposit"
+    },
+    {
+      "code": 6052,
+      "name": "VoterDepositCapExceeded",
+      "msg": "Deposit exceeds the voting mint's per-voter cap"
+    },
+    {
+      "code": 6053,
+      "name": "RegistrarDepositCapExceeded",
+      "msg": "Deposit exceeds the voting mint's registrar-wide cap"
+    },
+    {
+      "code": 6054,
+      "name": "VotingMintDepositsNotTracked",
+      "msg": "Registrar-wide deposit caps and the Deposited max vote weight mode need voting mints with tracked deposits"
+    },
+    {
+      "code": 6055,
+      "name": "LockupKindNotAllowed",
+      "msg": "Lockup kind is not allowed for the voting mint"
+    },
+    {
+      "code": 6056,
+      "name": "LockupTooLong",
+      "msg": "Lockup is longer than the voting mint's maximum lockup duration"
+    },
+    {
+      "code": 6057,
+      "name": "InvalidVoterWeightRecordPairs",
+      "msg": "Remaining accounts must be pairs of a voter and its voter weight record"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidClawbackAmount",
+      "msg": "Partial clawback amount must be positive"
+    },
+    {
+      "code": 6059,
+      "name": "VotingMintNotBackfilling",
+      "msg": "Deposits and voting mint totals of the voting mint are already tracked"
+    }
+  ]
+};
+
+export const IDL: VoterStakeRegistry = {
+  "version": "0.2.4",
+  "name": "voter_stake_registry",
+  "instructions": [
+    {
+      "name": "createRegistrar",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realm",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "governanceProgramId",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "realmGoverningTokenMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "registrarBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "configureVotingMint",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        },
+        {
+          "name": "digitShift",
+          "type": "i8"
+        },
+        {
+          "name": "baselineVoteWeightScaledFactor",
+          "type": "u64"
+        },
+        {
+          "name": "maxExtraLockupVoteWeightScaledFactor",
+          "type": "u64"
+        },
+        {
+          "name": "lockupSaturationSecs",
+          "type": "u64"
+        },
+        {
+          "name": "grantAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "configureDepositLimits",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        },
+        {
+          "name": "minDepositNative",
+          "type": "u64"
+        },
+        {
+          "name": "maxVoterDepositedNative",
+          "type": "u64"
+        },
+        {
+          "name": "maxDepositedNative",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "configureLockupCurve",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupCurveKind"
+          }
+        },
+        {
+          "name": "points",
+          "type": {
+            "vec": {
+              "defined": "LockupCurvePoint"
             }
           }
-        ]
-      }
+        }
+      ]
+    },
+    {
+      "name": "configureLockupLimits",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        },
+        {
+          "name": "allowedLockupKinds",
+          "type": "u16"
+        },
+        {
+          "name": "maxLockupDays",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "configureMaxVoteWeightMode",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "mode",
+          "type": {
+            "defined": "MaxVoteWeightMode"
+          }
+        }
+      ]
+    },
+    {
+      "name": "syncRealmAuthority",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realm",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeVotingMint",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "resizeRegistrar",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "backfillVotingMintTotals",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "finishVotingMintBackfill",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "idx",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createVoter",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "voterBump",
+          "type": "u8"
+        },
+        {
+          "name": "voterWeightRecordBump",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resizeVoter",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setDelegate",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenOwnerRecord",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "delegate",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "proposeVoterTransfer",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "newVoterAuthority",
+          "type": {
+            "option": "publicKey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "acceptVoterTransfer",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenOwnerRecord",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "newVoter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newVoterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "newVoterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "solDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createDepositEntry",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "createDepositEntryWithCliff",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "cliffPeriods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "deposit",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositUnlocked",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdraw",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenOwnerRecord",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "grant",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "grantAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "voterBump",
+          "type": "u8"
+        },
+        {
+          "name": "voterWeightRecordBump",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "grantWithCliff",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "depositToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "grantAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "depositMint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "associatedTokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "voterBump",
+          "type": "u8"
+        },
+        {
+          "name": "voterWeightRecordBump",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "startTs",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        },
+        {
+          "name": "cliffPeriods",
+          "type": "u32"
+        },
+        {
+          "name": "allowClawback",
+          "type": "bool"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "clawback",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "partialClawback",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "closeDepositEntry",
+      "accounts": [
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resetLockup",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "kind",
+          "type": {
+            "defined": "LockupKind"
+          }
+        },
+        {
+          "name": "periods",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "internalTransferLocked",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "splitDepositEntry",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "mergeDepositEntries",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryIndexes",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "internalTransferUnlocked",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateVoterWeightRecord",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateVoterWeightRecords",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createMaxVoterWeightRecord",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "maxVoterWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateMaxVoteWeight",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "maxVoteWeightRecord",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "voter",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "voterAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "registrar",
-            "type": "publicKey"
-          },
-          {
-            "name": "deposits",
-            "type": {
-              "array": [
-                {
-                  "defined": "DepositEntry"
-                },
-                32
-              ]
-            }
-          },
-          {
-            "name": "voterBump",
-            "type": "u8"
-          },
-          {
-            "name": "voterWeightRecordBump",
-            "type": "u8"
-          },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                94
-              ]
-            }
-          }
-        ]
-      }
+      "name": "closeVoter",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "voterAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "solDestination",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "logRegistrarInfo",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "logVoterInfo",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "voter",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositEntryBegin",
+          "type": "u8"
+        },
+        {
+          "name": "depositEntryCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "setTimeOffset",
+      "accounts": [
+        {
+          "name": "registrar",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "realmAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "timeOffset",
+          "type": "i64"
+        }
+      ]
     }
   ],
-  "types": [
+  "accounts": [
     {
-      "name": "DepositEntry",
+      "name": "registrar",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "lockup",
-            "type": {
-              "defined": "Lockup"
-            }
-          },
-          {
-            "name": "amountDepositedNative",
-            "type": "u64"
-          },
-          {
-            "name": "amountInitiallyLockedNative",
-            "type": "u64"
+            "name": "governanceProgramId",
+            "type": "publicKey"
           },
           {
-            "name": "isUsed",
-            "type": "bool"
+            "name": "realm",
+            "type": "publicKey"
           },
           {
-            "name": "allowClawback",
-            "type": "bool"
+            "name": "realmGoverningTokenMint",
+            "type": "publicKey"
           },
           {
-            "name": "votingMintConfigIdx",
-            "type": "u8"
+            "name": "realmAuthority",
+            "type": "publicKey"
           },
           {
-            "name": "reserved",
+            "name": "reserved1",
             "type": {
               "array": [
                 "u8",
-                29
+                32
               ]
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "VestingInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "rate",
-            "type": "u64"
-          },
-          {
-            "name": "nextTimestamp",
-            "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "LockingInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "endTimestamp",
-            "type": {
-              "option": "u64"
-            }
-          },
-          {
-            "name": "vesting",
-            "type": {
-              "option": {
-                "defined": "VestingInfo"
-              }
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "Lockup",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "startTs",
-            "type": "i64"
-          },
-          {
-            "name": "endTs",
-            "type": "i64"
-          },
-          {
-            "name": "kind",
-            "type": {
-              "defined": "LockupKind"
-            }
           },
           {
-            "name": "reserved",
+            "name": "votingMints",
             "type": {
               "array": [
-                "u8",
-                15
+                {
+                  "defined": "VotingMintConfig"
+                },
+                8
               ]
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "VotingMintConfig",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "grantAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "baselineVoteWeightScaledFactor",
-            "type": "u64"
           },
           {
-            "name": "maxExtraLockupVoteWeightScaledFactor",
-            "type": "u64"
+            "name": "timeOffset",
+            "type": "i64"
           },
           {
-            "name": "lockupSaturationSecs",
-            "type": "u64"
+            "name": "bump",
+            "type": "u8"
           },
           {
-            "name": "digitShift",
-            "type": "i8"
+            "name": "maxVoteWeightMode",
+            "type": {
+              "defined": "MaxVoteWeightMode"
+            }
           },
           {
-            "name": "reserved1",
+            "name": "reserved2",
             "type": {
               "array": [
                 "u8",
-                7
+                6
               ]
             }
           },
           {
-            "name": "reserved2",
+            "name": "maxDepositedNative",
             "type": {
               "array": [
                 "u64",
-                7
+                8
               ]
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "LockupKind",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "None"
           },
           {
-            "name": "Daily"
+            "name": "allowedLockupKinds",
+            "type": {
+              "array": [
+                "u16",
+                8
+              ]
+            }
           },
           {
-            "name": "Monthly"
+            "name": "reserved3",
+            "type": {
+              "array": [
+                "u64",
+                1
+              ]
+            }
           },
           {
-            "name": "Cliff"
+            "name": "votingMintTotals",
+            "type": {
+              "array": [
+                {
+                  "defined": "VotingMintTotals"
+                },
+                8
+              ]
+            }
           },
           {
-            "name": "Constant"
+            "name": "votingMintTotalsTracked",
+            "type": {
+              "array": [
+                "bool",
+                8
+              ]
+            }
           }
         ]
       }
-    }
-  ],
-  "events": [
-    {
-      "name": "VoterInfo",
-      "fields": [
-        {
-          "name": "votingPower",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "votingPowerBaseline",
-          "type": "u64",
-          "index": false
-        }
-      ]
     },
     {
-      "name": "DepositEntryInfo",
-      "fields": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8",
-          "index": false
-        },
-        {
-          "name": "votingMintConfigIndex",
-          "type": "u8",
-          "index": false
-        },
-        {
-          "name": "unlocked",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "votingPower",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "votingPowerBaseline",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "locking",
-          "type": {
-            "option": {
-              "defined": "LockingInfo"
-            }
+      "name": "voter",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voterAuthority",
+            "type": "publicKey"
           },
-          "index": false
-        }
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "InvalidRate",
-      "msg": "Exchange rate must be greater than zero"
-    },
-    {
-      "code": 6001,
-      "name": "RatesFull",
-      "msg": ""
-    },
-    {
-      "code": 6002,
-      "name": "VotingMintNotFound",
-      "msg": ""
-    },
-    {
-      "code": 6003,
-      "name": "DepositEntryNotFound",
-      "msg": ""
-    },
-    {
-      "code": 6004,
-      "name": "DepositEntryFull",
-      "msg": ""
-    },
-    {
-      "code": 6005,
-      "name": "VotingTokenNonZero",
-      "msg": ""
-    },
-    {
-      "code": 6006,
-      "name": "OutOfBoundsDepositEntryIndex",
-      "msg": ""
-    },
-    {
-      "code": 6007,
-      "name": "UnusedDepositEntryIndex",
-      "msg": ""
-    },
-    {
-      "code": 6008,
-      "name": "InsufficientUnlockedTokens",
-      "msg": ""
-    },
-    {
-      "code": 6009,
-      "name": "UnableToConvert",
-      "msg": ""
-    },
-    {
-      "code": 6010,
-      "name": "InvalidLockupPeriod",
-      "msg": ""
-    },
-    {
-      "code": 6011,
-      "name": "InvalidEndTs",
-      "msg": ""
-    },
-    {
-      "code": 6012,
-      "name": "InvalidDays",
-      "msg": ""
-    },
-    {
-      "code": 6013,
-      "name": "VotingMintConfigIndexAlreadyInUse",
-      "msg": ""
-    },
-    {
-      "code": 6014,
-      "name": "OutOfBoundsVotingMintConfigIndex",
-      "msg": ""
-    },
-    {
-      "code": 6015,
-      "name": "InvalidDecimals",
-      "msg": "Exchange rate decimals cannot be larger than registrar decimals"
-    },
-    {
-      "code": 6016,
-      "name": "InvalidToDepositAndWithdrawInOneSlot",
-      "msg": ""
-    },
-    {
-      "code": 6017,
-      "name": "ShouldBeTheFirstIxInATx",
-      "msg": ""
-    },
-    {
-      "code": 6018,
-      "name": "ForbiddenCpi",
-      "msg": ""
-    },
-    {
-      "code": 6019,
-      "name": "InvalidMint",
-      "msg": ""
-    },
-    {
-      "code": 6020,
-      "name": "DebugInstruction",
-      "msg": ""
-    },
-    {
-      "code": 6021,
-      "name": "ClawbackNotAllowedOnDeposit",
-      "msg": ""
-    },
-    {
-      "code": 6022,
-      "name": "DepositStillLocked",
-      "msg": ""
-    },
-    {
-      "code": 6023,
-      "name": "InvalidAuthority",
-      "msg": ""
-    },
-    {
-      "code": 6024,
-      "name": "InvalidTokenOwnerRecord",
-      "msg": ""
-    },
-    {
-      "code": 6025,
-      "name": "InvalidRealmAuthority",
-      "msg": ""
-    },
-    {
-      "code": 6026,
-      "name": "VoterWeightOverflow",
-      "msg": ""
-    },
-    {
-      "code": 6027,
-      "name": "LockupSaturationMustBePositive",
-      "msg": ""
-    },
-    {
-      "code": 6028,
-      "name": "VotingMintConfiguredWithDifferentIndex",
-      "msg": ""
-    },
+          {
+            "name": "registrar",
+            "type": "publicKey"
+          },
+          {
+            "name": "deposits",
+            "type": {
+              "array": [
+                {
+                  "defined": "DepositEntry"
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "voterBump",
+            "type": "u8"
+          },
+          {
+            "name": "voterWeightRecordBump",
+            "type": "u8"
+          },
+          {
+            "name": "delegate",
+            "type": "publicKey"
+          },
+          {
+            "name": "pendingVoterAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "delegateRevoked",
+            "type": "bool"
+          },
+          {
+            "name": "votingMintsCounted",
+            "type": {
+              "array": [
+                "bool",
+                8
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                21
+              ]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
     {
-      "code": 6029,
-      "name": "InternalProgramError",
-      "msg": ""
+      "name": "VestingInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rate",
+            "type": "u64"
+          },
+          {
+            "name": "nextTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "cliffEndTimestamp",
+            "type": {
+              "option": "u64"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6030,
-      "name": "InsufficientLockedTokens",
-      "msg": ""
+      "name": "LockingInfo",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "endTimestamp",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "vesting",
+            "type": {
+              "option": {
+                "defined": "VestingInfo"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6031,
-      "name": "MustKeepTokensLocked",
-      "msg": ""
+      "name": "LockupState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "kind",
+            "type": {
+              "defined": "LockupKind"
+            }
+          },
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "cliffPeriods",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "code": 6032,
-      "name": "InvalidLockupKind",
-      "msg": ""
+      "name": "DepositEntryState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "votingMintConfigIndex",
+            "type": "u8"
+          },
+          {
+            "name": "amountDepositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "amountInitiallyLockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "allowClawback",
+            "type": "bool"
+          },
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "LockupState"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6033,
-      "name": "InvalidChangeToClawbackDepositEntry",
-      "msg": ""
+      "name": "DepositEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockup",
+            "type": {
+              "defined": "Lockup"
+            }
+          },
+          {
+            "name": "amountDepositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "amountInitiallyLockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "isUsed",
+            "type": "bool"
+          },
+          {
+            "name": "allowClawback",
+            "type": "bool"
+          },
+          {
+            "name": "votingMintConfigIdx",
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                29
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6034,
-      "name": "InternalErrorBadLockupVoteWeight",
-      "msg": ""
+      "name": "LockupCurvePoint",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockupSecs",
+            "type": "u32"
+          },
+          {
+            "name": "scaledFactor",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "code": 6035,
-      "name": "DepositStartTooFarInFuture",
-      "msg": ""
+      "name": "Lockup",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startTs",
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "type": "i64"
+          },
+          {
+            "name": "kind",
+            "type": {
+              "defined": "LockupKind"
+            }
+          },
+          {
+            "name": "reserved1",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "cliffPeriods",
+            "type": "u32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                8
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6036,
-      "name": "VaultTokenNonZero",
-      "msg": ""
+      "name": "VotingMintTotals",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lockedNative",
+            "type": "u64"
+          },
+          {
+            "name": "clawbackDepositedNative",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6037,
-      "name": "InvalidTimestampArguments",
-      "msg": ""
-    }
-  ]
-};
-
-export const IDL: VoterStakeRegistry = {
-  "version": "0.2.1",
-  "name": "voter_stake_registry",
-  "instructions": [
-    {
-      "name": "createRegistrar",
-      "accounts": [
-        {
-          "name": "registrar",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "realm",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "governanceProgramId",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "realmGoverningTokenMint",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "realmAuthority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "registrarBump",
-          "type": "u8"
-        }
-      ]
+      "name": "VotingMintConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "grantAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "baselineVoteWeightScaledFactor",
+            "type": "u64"
+          },
+          {
+            "name": "maxExtraLockupVoteWeightScaledFactor",
+            "type": "u64"
+          },
+          {
+            "name": "lockupSaturationSecs",
+            "type": "u64"
+          },
+          {
+            "name": "digitShift",
+            "type": "i8"
+          },
+          {
+            "name": "deprecated",
+            "type": "bool"
+          },
+          {
+            "name": "depositedNativeTracked",
+            "type": "bool"
+          },
+          {
+            "name": "lockupCurveKind",
+            "type": {
+              "defined": "LockupCurveKind"
+            }
+          },
+          {
+            "name": "lockupCurvePointCount",
+            "type": "u8"
+          },
+          {
+            "name": "reserved1",
+            "type": "u8"
+          },
+          {
+            "name": "maxLockupDays",
+            "type": "u16"
+          },
+          {
+            "name": "depositedNative",
+            "type": "u64"
+          },
+          {
+            "name": "lockupCurvePoints",
+            "type": {
+              "array": [
+                {
+                  "defined": "LockupCurvePoint"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "minDepositNative",
+            "type": "u64"
+          },
+          {
+            "name": "maxVoterDepositedNative",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "name": "configureVotingMint",
-      "accounts": [
-        {
-          "name": "registrar",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "realmAuthority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "mint",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "idx",
-          "type": "u16"
-        },
-        {
-          "name": "digitShift",
-          "type": "i8"
-        },
-        {
-          "name": "baselineVoteWeightScaledFactor",
-          "type": "u64"
-        },
-        {
-          "name": "maxExtraLockupVoteWeightScaledFactor",
-          "type": "u64"
-        },
-        {
-          "name": "lockupSaturationSecs",
-          "type": "u64"
-        },
-        {
-          "name": "grantAuthority",
-          "type": {
-            "option": "publicKey"
+      "name": "LockupCurveKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Linear"
+          },
+          {
+            "name": "PiecewiseLinear"
+          },
+          {
+            "name": "Step"
           }
-        }
-      ]
+        ]
+      }
     },
     {
-      "name": "createVoter",
-      "accounts": [
-        {
-          "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "voterWeightRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
+      "name": "LockupKind",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "None"
+          },
+          {
+            "name": "Daily"
+          },
+          {
+            "name": "Monthly"
+          },
+          {
+            "name": "Cliff"
+          },
+          {
+            "name": "Constant"
+          },
+          {
+            "name": "Weekly"
+          },
+          {
+            "name": "Quarterly"
+          },
+          {
+            "name": "CliffMonthly"
+          }
+        ]
+      }
+    },
+    {
+      "name": "MaxVoteWeightMode",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "MintSupply"
+          },
+          {
+            "name": "Deposited"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "VoterInfo",
+      "fields": [
         {
-          "name": "voterBump",
-          "type": "u8"
+          "name": "votingPower",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "voterWeightRecordBump",
-          "type": "u8"
+          "name": "votingPowerBaseline",
+          "type": "u64",
+          "index": false
         }
       ]
     },
     {
-      "name": "createDepositEntry",
-      "accounts": [
-        {
-          "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "depositMint",
-          "isMut": false,
-          "isSigner": false
-        },
+      "name": "DepositEntryInfo",
+      "fields": [
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "votingMintConfigIndex",
+          "type": "u8",
+          "index": false
         },
         {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "unlocked",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "votingPower",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "kind",
-          "type": {
-            "defined": "LockupKind"
-          }
+          "name": "votingPowerBaseline",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "startTs",
+          "name": "locking",
           "type": {
-            "option": "u64"
-          }
-        },
-        {
-          "name": "periods",
-          "type": "u32"
-        },
-        {
-          "name": "allowClawback",
-          "type": "bool"
+            "option": {
+              "defined": "LockingInfo"
+            }
+          },
+          "index": false
         }
       ]
     },
     {
-      "name": "deposit",
-      "accounts": [
-        {
-          "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
+      "name": "VotingMintInfo",
+      "fields": [
         {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
+          "name": "votingMintConfigIndex",
+          "type": "u8",
+          "index": false
         },
         {
-          "name": "depositToken",
-          "isMut": true,
-          "isSigner": false
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "depositAuthority",
-          "isMut": false,
-          "isSigner": true
+          "name": "deposited",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "locked",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "clawbackDeposited",
+          "type": {
+            "option": "u64"
+          },
+          "index": false
         }
       ]
     },
     {
-      "name": "withdraw",
-      "accounts": [
+      "name": "RegistrarCreatedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "tokenOwnerRecord",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voterWeightRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "destination",
-          "isMut": true,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "realm",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "realmAuthority",
+          "type": "publicKey",
+          "index": false
         }
       ]
     },
     {
-      "name": "grant",
-      "accounts": [
+      "name": "VotingMintConfiguredEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voterWeightRecord",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "depositToken",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAuthority",
-          "isMut": false,
-          "isSigner": true
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "grantAuthority",
-          "isMut": false,
-          "isSigner": true
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "payer",
-          "isMut": true,
-          "isSigner": true
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "depositMint",
-          "isMut": false,
-          "isSigner": false
+          "name": "digitShift",
+          "type": "i8",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "baselineVoteWeightScaledFactor",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "maxExtraLockupVoteWeightScaledFactor",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "associatedTokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "lockupSaturationSecs",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
+          "name": "grantAuthority",
+          "type": "publicKey",
+          "index": false
         }
-      ],
-      "args": [
+      ]
+    },
+    {
+      "name": "LockupCurveConfiguredEvent",
+      "fields": [
         {
-          "name": "voterBump",
-          "type": "u8"
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "voterWeightRecordBump",
-          "type": "u8"
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
         },
         {
           "name": "kind",
           "type": {
-            "defined": "LockupKind"
-          }
-        },
-        {
-          "name": "startTs",
-          "type": {
-            "option": "u64"
-          }
-        },
-        {
-          "name": "periods",
-          "type": "u32"
+            "defined": "LockupCurveKind"
+          },
+          "index": false
         },
         {
-          "name": "allowClawback",
-          "type": "bool"
+          "name": "lockupSaturationSecs",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "points",
+          "type": {
+            "vec": {
+              "defined": "LockupCurvePoint"
+            }
+          },
+          "index": false
         }
       ]
     },
     {
-      "name": "clawback",
-      "accounts": [
+      "name": "DepositLimitsConfiguredEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "realmAuthority",
-          "isMut": false,
-          "isSigner": true
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
+          "name": "minDepositNative",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "destination",
-          "isMut": true,
-          "isSigner": false
+          "name": "maxVoterDepositedNative",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "maxDepositedNative",
+          "type": "u64",
+          "index": false
         }
       ]
     },
     {
-      "name": "closeDepositEntry",
-      "accounts": [
+      "name": "LockupLimitsConfiguredEvent",
+      "fields": [
         {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
+        },
         {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "allowedLockupKinds",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "maxLockupDays",
+          "type": "u16",
+          "index": false
         }
       ]
     },
     {
-      "name": "resetLockup",
-      "accounts": [
+      "name": "MaxVoteWeightModeConfiguredEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
+          "name": "mode",
+          "type": {
+            "defined": "MaxVoteWeightMode"
+          },
+          "index": false
         }
-      ],
-      "args": [
+      ]
+    },
+    {
+      "name": "VotingMintRemovedEvent",
+      "fields": [
         {
-          "name": "depositEntryIndex",
-          "type": "u8"
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "kind",
-          "type": {
-            "defined": "LockupKind"
-          }
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "periods",
-          "type": "u32"
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositedNative",
+          "type": "u64",
+          "index": false
         }
       ]
     },
     {
-      "name": "internalTransferLocked",
-      "accounts": [
+      "name": "VotingMintBackfillFinishedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
+          "name": "votingMintConfigIndex",
+          "type": "u16",
+          "index": false
         },
         {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
         {
-          "name": "sourceDepositEntryIndex",
-          "type": "u8"
+          "name": "depositedNative",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "targetDepositEntryIndex",
-          "type": "u8"
+          "name": "lockedNative",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "clawbackDepositedNative",
+          "type": "u64",
+          "index": false
         }
       ]
     },
     {
-      "name": "internalTransferUnlocked",
-      "accounts": [
+      "name": "RealmAuthoritySyncedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voter",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
-        {
-          "name": "sourceDepositEntryIndex",
-          "type": "u8"
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "targetDepositEntryIndex",
-          "type": "u8"
+          "name": "oldRealmAuthority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "newRealmAuthority",
+          "type": "publicKey",
+          "index": false
         }
       ]
     },
     {
-      "name": "updateVoterWeightRecord",
-      "accounts": [
+      "name": "VoterCreatedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
           "name": "voter",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "voterWeightRecord",
-          "isMut": true,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": []
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "updateMaxVoteWeight",
-      "accounts": [
+      "name": "VoterResizedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "maxVoteWeightRecord",
-          "isMut": false,
-          "isSigner": false
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryCount",
+          "type": "u8",
+          "index": false
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "closeVoter",
-      "accounts": [
+      "name": "VoterClosedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
           "name": "voter",
-          "isMut": true,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
           "name": "voterAuthority",
-          "isMut": false,
-          "isSigner": true
+          "type": "publicKey",
+          "index": false
         },
         {
           "name": "solDestination",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "logVoterInfo",
-      "accounts": [
+      "name": "DelegateSetEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": false,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
           "name": "voter",
-          "isMut": false,
-          "isSigner": false
-        }
-      ],
-      "args": [
+          "type": "publicKey",
+          "index": false
+        },
         {
-          "name": "depositEntryBegin",
-          "type": "u8"
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "depositEntryCount",
-          "type": "u8"
+          "name": "oldDelegate",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
+        },
+        {
+          "name": "newDelegate",
+          "type": {
+            "option": "publicKey"
+          },
+          "index": false
         }
       ]
     },
     {
-      "name": "setTimeOffset",
-      "accounts": [
+      "name": "VoterTransferProposedEvent",
+      "fields": [
         {
           "name": "registrar",
-          "isMut": true,
-          "isSigner": false
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "realmAuthority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
         {
-          "name": "timeOffset",
-          "type": "i64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "registrar",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "governanceProgramId",
-            "type": "publicKey"
-          },
-          {
-            "name": "realm",
-            "type": "publicKey"
-          },
-          {
-            "name": "realmGoverningTokenMint",
-            "type": "publicKey"
-          },
-          {
-            "name": "realmAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "reserved1",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "votingMints",
-            "type": {
-              "array": [
-                {
-                  "defined": "VotingMintConfig"
-                },
-                4
-              ]
-            }
-          },
-          {
-            "name": "timeOffset",
-            "type": "i64"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "reserved2",
-            "type": {
-              "array": [
-                "u8",
-                7
-              ]
-            }
-          },
-          {
-            "name": "reserved3",
-            "type": {
-              "array": [
-                "u64",
-                11
-              ]
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "voter",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "voterAuthority",
-            "type": "publicKey"
-          },
-          {
-            "name": "registrar",
-            "type": "publicKey"
-          },
-          {
-            "name": "deposits",
-            "type": {
-              "array": [
-                {
-                  "defined": "DepositEntry"
-                },
-                32
-              ]
-            }
-          },
-          {
-            "name": "voterBump",
-            "type": "u8"
-          },
-          {
-            "name": "voterWeightRecordBump",
-            "type": "u8"
-          },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                94
-              ]
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
-    {
-      "name": "DepositEntry",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "lockup",
-            "type": {
-              "defined": "Lockup"
-            }
-          },
-          {
-            "name": "amountDepositedNative",
-            "type": "u64"
-          },
-          {
-            "name": "amountInitiallyLockedNative",
-            "type": "u64"
-          },
-          {
-            "name": "isUsed",
-            "type": "bool"
-          },
-          {
-            "name": "allowClawback",
-            "type": "bool"
-          },
-          {
-            "name": "votingMintConfigIdx",
-            "type": "u8"
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoterAuthority",
+          "type": {
+            "option": "publicKey"
           },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                29
-              ]
-            }
-          }
-        ]
-      }
+          "index": false
+        }
+      ]
     },
     {
-      "name": "VestingInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "rate",
-            "type": "u64"
-          },
-          {
-            "name": "nextTimestamp",
-            "type": "u64"
-          }
-        ]
-      }
+      "name": "VoterTransferredEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldVoter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "oldVoterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "newVoterAuthority",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "LockingInfo",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "amount",
-            "type": "u64"
-          },
-          {
-            "name": "endTimestamp",
-            "type": {
-              "option": "u64"
-            }
+      "name": "DepositEntryCreatedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "depositEntry",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "vesting",
-            "type": {
-              "option": {
-                "defined": "VestingInfo"
-              }
-            }
-          }
-        ]
-      }
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Lockup",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "startTs",
-            "type": "i64"
-          },
-          {
-            "name": "endTs",
-            "type": "i64"
+      "name": "DepositEntryClosedEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "kind",
-            "type": {
-              "defined": "LockupKind"
-            }
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                15
-              ]
-            }
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "VotingMintConfig",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "mint",
-            "type": "publicKey"
+      "name": "WithdrawEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "grantAuthority",
-            "type": "publicKey"
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "baselineVoteWeightScaledFactor",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GrantEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voterAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "grantAuthority",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "depositEntry",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "maxExtraLockupVoteWeightScaledFactor",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ClawbackEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "lockupSaturationSecs",
-            "type": "u64"
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "digitShift",
-            "type": "i8"
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "ResetLockupEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "depositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "before",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "reserved1",
-            "type": {
-              "array": [
-                "u8",
-                7
-              ]
-            }
+          "index": false
+        },
+        {
+          "name": "after",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "reserved2",
-            "type": {
-              "array": [
-                "u64",
-                7
-              ]
-            }
-          }
-        ]
-      }
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "LockupKind",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "None"
+      "name": "InternalTransferEvent",
+      "fields": [
+        {
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "locked",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "sourceDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sourceBefore",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "Daily"
+          "index": false
+        },
+        {
+          "name": "sourceAfter",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "Monthly"
+          "index": false
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "targetBefore",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "Cliff"
+          "index": false
+        },
+        {
+          "name": "targetAfter",
+          "type": {
+            "defined": "DepositEntryState"
           },
-          {
-            "name": "Constant"
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
     {
-      "name": "VoterInfo",
+      "name": "DepositEntrySplitEvent",
       "fields": [
         {
-          "name": "votingPower",
+          "name": "registrar",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "voter",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
           "type": "u64",
           "index": false
         },
         {
-          "name": "votingPowerBaseline",
+          "name": "sourceDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sourceBefore",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "sourceAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "target",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
           "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "DepositEntryInfo",
+      "name": "DepositEntriesMergedEvent",
       "fields": [
         {
-          "name": "depositEntryIndex",
-          "type": "u8",
+          "name": "registrar",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "votingMintConfigIndex",
-          "type": "u8",
+          "name": "voter",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "unlocked",
+          "name": "amount",
           "type": "u64",
           "index": false
         },
         {
-          "name": "votingPower",
-          "type": "u64",
+          "name": "sourceDepositEntryIndexes",
+          "type": "bytes",
           "index": false
         },
         {
-          "name": "votingPowerBaseline",
-          "type": "u64",
+          "name": "targetDepositEntryIndex",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "locking",
+          "name": "targetBefore",
           "type": {
-            "option": {
-              "defined": "LockingInfo"
-            }
+            "defined": "DepositEntryState"
           },
           "index": false
+        },
+        {
+          "name": "targetAfter",
+          "type": {
+            "defined": "DepositEntryState"
+          },
+          "index": false
+        },
+        {
+          "name": "voterWeight",
+          "type": "u64",
+          "index": false
         }
       ]
     }
@@ -2576,6 +6590,116 @@ export const IDL: VoterStakeRegistry = {
       "code": 6037,
       "name": "InvalidTimestampArguments",
       "msg": ""
+    },
+    {
+      "code": 6038,
+      "name": "InvalidTokenProgram",
+      "msg": "Token program must be spl-token or token-2022"
+    },
+    {
+      "code": 6039,
+      "name": "InvalidVault",
+      "msg": ""
+    },
+    {
+      "code": 6040,
+      "name": "InvalidRegistrarSize",
+      "msg": ""
+    },
+    {
+      "code": 6041,
+      "name": "InvalidVoterSize",
+      "msg": ""
+    },
+    {
+      "code": 6042,
+      "name": "VotingMintDeprecated",
+      "msg": "Voting mint was removed and accepts no new deposits"
+    },
+    {
+      "code": 6043,
+      "name": "VotingMintHasDeposits",
+      "msg": "Voting mint was removed, but tokens of it may still be deposited"
+    },
+    {
+      "code": 6044,
+      "name": "InvalidLockupCurve",
+      "msg": "Lockup curve points must increase up to the full factor at lockup saturation"
+    },
+    {
+      "code": 6045,
+      "name": "InvalidLockupCliff",
+      "msg": "Lockup cliffs are only allowed for CliffMonthly and can't be shortened"
+    },
+    {
+      "code": 6046,
+      "name": "InvalidDelegate",
+      "msg": "The delegate must differ from the voter authority"
+    },
+    {
+      "code": 6047,
+      "name": "InvalidNewVoterAuthority",
+      "msg": "The new voter authority must be proposed and differ from the current one"
+    },
+    {
+      "code": 6048,
+      "name": "VaultNotTransferred",
+      "msg": "The vaults of all deposited mints must be transferred in full"
+    },
+    {
+      "code": 6049,
+      "name": "InvalidSplitAmount",
+      "msg": "Split amount must be positive and less than the deposited amount"
+    },
+    {
+      "code": 6050,
+      "name": "InvalidMergeDepositEntries",
+      "msg": "Merging needs at least two distinct deposit entries"
+    },
+    {
+      "code": 6051,
+      "name": "DepositBelowMinimum",
+      "msg": "Deposit is below the voting mint's minimum deposit"
+    },
+    {
+      "code": 6052,
+      "name": "VoterDepositCapExceeded",
+      "msg": "Deposit exceeds the voting mint's per-voter cap"
+    },
+    {
+      "code": 6053,
+      "name": "RegistrarDepositCapExceeded",
+      "msg": "Deposit exceeds the voting mint's registrar-wide cap"
+    },
+    {
+      "code": 6054,
+      "name": "VotingMintDepositsNotTracked",
+      "msg": "Registrar-wide deposit caps and the Deposited max vote weight mode need voting mints with tracked deposits"
+    },
+    {
+      "code": 6055,
+      "name": "LockupKindNotAllowed",
+      "msg": "Lockup kind is not allowed for the voting mint"
+    },
+    {
+      "code": 6056,
+      "name": "LockupTooLong",
+      "msg": "Lockup is longer than the voting mint's maximum lockup duration"
+    },
+    {
+      "code": 6057,
+      "name": "InvalidVoterWeightRecordPairs",
+      "msg": "Remaining accounts must be pairs of a voter and its voter weight record"
+    },
+    {
+      "code": 6058,
+      "name": "InvalidClawbackAmount",
+      "msg": "Partial clawback amount must be positive"
+    },
+    {
+      "code": 6059,
+      "name": "VotingMintNotBackfilling",
+      "msg": "Deposits and voting mint totals of the voting mint are already tracked"
     }
   ]
 };